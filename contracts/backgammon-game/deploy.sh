@@ -84,11 +84,12 @@ echo ""
 echo "Get GAME_ADDRESS: xiond query tx <TXHASH> --node $NODE | grep -A1 '_contract_address'"
 echo ""
 
-# Step 3: Update config with escrow and server addresses (if not set at instantiation)
-echo "Step 3: Update config with escrow/server addresses (replace addresses):"
+# Step 3: Set the escrow address and add game servers (if not set at instantiation)
+echo "Step 3: Update config with the escrow address and grant the server the reporter role (replace addresses):"
 echo ""
 cat << EOF
-UPDATE_MSG='{"update_config":{"escrow_contract":"ESCROW_ADDRESS","server_address":"SERVER_ADDRESS"}}'
+UPDATE_MSG='{"update_config":{"escrow_contract":"ESCROW_ADDRESS"}}'
+GRANT_MSG='{"grant_role":{"role":"reporter","address":"SERVER_ADDRESS"}}'
 
 xiond tx wasm execute GAME_ADDRESS "\$UPDATE_MSG" \\
   --from $WALLET_ADDRESS \\
@@ -98,6 +99,15 @@ xiond tx wasm execute GAME_ADDRESS "\$UPDATE_MSG" \\
   --gas-adjustment 1.5 \\
   --gas-prices 0.025uxion \\
   -y
+
+xiond tx wasm execute GAME_ADDRESS "\$GRANT_MSG" \\
+  --from $WALLET_ADDRESS \\
+  --chain-id $CHAIN_ID \\
+  --node $NODE \\
+  --gas auto \\
+  --gas-adjustment 1.5 \\
+  --gas-prices 0.025uxion \\
+  -y
 EOF
 echo ""
 echo "==================================="
@@ -107,6 +117,7 @@ echo "==================================="
 echo ""
 echo "Notes:"
 echo "- escrow_contract: Wager escrow contract for settlement"
-echo "- server_address: Authorized game server that can report results"
-echo "- Both can be set at instantiation or updated later via UpdateConfig"
+echo "- server_address: Initial game server (reporter role) that can report results"
+echo "- Add or rotate servers with GrantRole/RevokeRole; the admin holds every role"
+echo "- update_config server_address/arbiter are deprecated: they replace every holder of the role"
 echo "==================================="
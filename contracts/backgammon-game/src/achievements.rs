@@ -0,0 +1,143 @@
+//! Achievement badges.
+//!
+//! Milestones are checked against a player's stats each time a result is
+//! booked. Each is earned once, and if an achievement NFT contract is
+//! configured a badge is minted to the player with the token ID
+//! `<achievement>-<address>`, the same CW721 `mint` message
+//! `pyramid-splitter` sends. A badge whose result a dispute overturns is
+//! revoked and burned if the player no longer meets its milestone.
+
+use cosmwasm_std::{to_json_binary, Addr, CosmosMsg, StdResult, WasmMsg};
+
+use crate::state::{Achievement, PlayerStats};
+
+/// Every achievement, in the order they are checked
+pub const ALL: [Achievement; 5] = [
+    Achievement::FirstWin,
+    Achievement::FirstBackgammon,
+    Achievement::HundredGames,
+    Achievement::WinStreak10,
+    Achievement::Rating1800,
+];
+
+/// Stable identifier used in storage keys and token IDs
+pub fn slug(achievement: &Achievement) -> &'static str {
+    match achievement {
+        Achievement::FirstWin => "first-win",
+        Achievement::FirstBackgammon => "first-backgammon",
+        Achievement::HundredGames => "hundred-games",
+        Achievement::WinStreak10 => "win-streak-10",
+        Achievement::Rating1800 => "rating-1800",
+    }
+}
+
+/// Whether the stats meet the achievement's milestone
+pub fn is_met(achievement: &Achievement, stats: &PlayerStats) -> bool {
+    match achievement {
+        Achievement::FirstWin => stats.games_won >= 1,
+        Achievement::FirstBackgammon => stats.backgammons_won >= 1,
+        Achievement::HundredGames => stats.games_played >= 100,
+        Achievement::WinStreak10 => stats.win_streak >= 10,
+        Achievement::Rating1800 => stats.rating >= 180_000,
+    }
+}
+
+pub fn token_id(achievement: &Achievement, owner: &Addr) -> String {
+    format!("{}-{}", slug(achievement), owner)
+}
+
+/// CW-721 Mint message structure
+#[derive(serde::Serialize)]
+struct Cw721MintMsg<T> {
+    mint: Cw721MintPayload<T>,
+}
+
+#[derive(serde::Serialize)]
+struct Cw721MintPayload<T> {
+    token_id: String,
+    owner: String,
+    token_uri: Option<String>,
+    extension: T,
+}
+
+/// Empty struct for CW-721 extension
+#[derive(serde::Serialize)]
+struct Empty {}
+
+/// Mint a badge to `owner` on the achievement NFT contract
+pub fn mint_msg(nft_contract: &Addr, token_id: &str, owner: &Addr) -> StdResult<CosmosMsg> {
+    let mint_msg = Cw721MintMsg {
+        mint: Cw721MintPayload {
+            token_id: token_id.to_string(),
+            owner: owner.to_string(),
+            token_uri: None,
+            extension: Empty {},
+        },
+    };
+
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: nft_contract.to_string(),
+        msg: to_json_binary(&mint_msg)?,
+        funds: vec![],
+    }))
+}
+
+/// CW-721 Burn message structure
+#[derive(serde::Serialize)]
+struct Cw721BurnMsg {
+    burn: Cw721BurnPayload,
+}
+
+#[derive(serde::Serialize)]
+struct Cw721BurnPayload {
+    token_id: String,
+}
+
+/// Burn a revoked badge on the achievement NFT contract
+pub fn burn_msg(nft_contract: &Addr, token_id: &str) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: nft_contract.to_string(),
+        msg: to_json_binary(&Cw721BurnMsg {
+            burn: Cw721BurnPayload {
+                token_id: token_id.to_string(),
+            },
+        })?,
+        funds: vec![],
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_milestones() {
+        let mut stats = PlayerStats::new(Addr::unchecked("p"), 150_000);
+        assert!(ALL.iter().all(|a| !is_met(a, &stats)));
+
+        stats.games_played = 100;
+        stats.games_won = 1;
+        stats.win_streak = 9;
+        stats.rating = 179_999;
+        let met: Vec<_> = ALL.iter().filter(|a| is_met(a, &stats)).collect();
+        assert_eq!(
+            met,
+            vec![&Achievement::FirstWin, &Achievement::HundredGames]
+        );
+
+        stats.win_streak = 10;
+        stats.rating = 180_000;
+        stats.backgammons_won = 1;
+        assert!(ALL.iter().all(|a| is_met(a, &stats)));
+    }
+
+    #[test]
+    fn test_token_ids_are_unique_per_player() {
+        let alice = Addr::unchecked("alice");
+        assert_eq!(token_id(&Achievement::FirstWin, &alice), "first-win-alice");
+        assert_ne!(
+            token_id(&Achievement::FirstWin, &alice),
+            token_id(&Achievement::FirstWin, &Addr::unchecked("bob"))
+        );
+    }
+}
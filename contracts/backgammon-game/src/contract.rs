@@ -1,15 +1,74 @@
+use std::cmp::Ordering;
+
 use cosmwasm_std::{
-    entry_point, to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
-    Response, StdResult, WasmMsg,
+    entry_point, from_json, to_json_binary, to_json_vec, Addr, Binary, CosmosMsg, Deps, DepsMut,
+    Empty, Env, HexBinary, MessageInfo, Order, QuerierWrapper, Reply, Response, StdError,
+    StdResult, SubMsg, WasmMsg,
 };
+use cw_storage_plus::{Bound, IndexPrefix, MultiIndex};
+use sha2::{Digest, Sha256};
 
+use crate::achievements;
+use crate::dice;
 use crate::error::ContractError;
+use crate::merkle;
 use crate::msg::*;
+use crate::rating;
+use crate::rules;
 use crate::state::*;
 
 const DEFAULT_RATING: u32 = 150_000; // 1500.00
-const RATING_CHANGE: u32 = 1_000; // +/- 10.00 per game
-const MIN_RATING: u32 = 100_000; // 1000.00 floor
+
+/// Longest match the contract will record
+const MAX_MATCH_LENGTH: u32 = 25;
+
+/// Highest cube value, matching the escrow's limit
+const MAX_CUBE_VALUE: u32 = 64;
+
+/// How long a game proposal stays open by default, and at most
+const DEFAULT_PROPOSAL_EXPIRY: u64 = 24 * 60 * 60;
+const MAX_PROPOSAL_EXPIRY: u64 = 7 * 24 * 60 * 60;
+
+/// How long an open challenge can be accepted by default, and at most
+const DEFAULT_CHALLENGE_EXPIRY: u64 = 24 * 60 * 60;
+const MAX_CHALLENGE_EXPIRY: u64 = 7 * 24 * 60 * 60;
+
+/// How long a proposed admin has to accept by default, and at most
+const DEFAULT_ADMIN_TRANSFER_EXPIRY: u64 = 7 * 24 * 60 * 60;
+const MAX_ADMIN_TRANSFER_EXPIRY: u64 = 30 * 24 * 60 * 60;
+
+/// Reply ID for escrow `Settle`/`Resettle` messages, whose response carries
+/// the exact payout to book in the players' stats
+const SETTLE_ESCROW_REPLY_ID: u64 = 2;
+/// Reply IDs for badge mints and burns, sent so that an NFT contract that
+/// rejects them cannot block the result they come with
+const MINT_BADGE_REPLY_ID: u64 = 3;
+const BURN_BADGE_REPLY_ID: u64 = 4;
+
+const DEFAULT_SEASON_RESET_PERCENT: u32 = 50;
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+/// Most rating index entries one Leaderboard page looks at when filtering
+const MAX_LEADERBOARD_SCAN: usize = 300;
+
+/// cw2 name and version recorded on instantiate and migrate
+const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// First version whose player stats track streaks, abandonments and recent
+/// form; migrating from an older one rebuilds them from the game history
+const STATS_BACKFILL_VERSION: &str = "0.1.0";
+/// Entries re-saved per `migrate` call by default, and at most
+const DEFAULT_MIGRATION_LIMIT: u32 = 200;
+const MAX_MIGRATION_LIMIT: u32 = 1000;
+const DEFAULT_SEASON_END_LIMIT: u32 = 200;
+const MAX_SEASON_END_LIMIT: u32 = 1000;
+
+/// Caller-supplied IDs and keys are at most this long
+const MAX_ID_LENGTH: usize = 64;
+/// Prefixes of generated game and match IDs, which share the escrow's ID
+/// space; caller-supplied game IDs may not use them
+const RESERVED_ID_PREFIXES: [&str; 2] = ["game-", "match-"];
 
 /// Escrow contract execute messages (typed for to_json_binary)
 #[derive(serde::Serialize)]
@@ -30,6 +89,34 @@ enum EscrowExecuteMsg {
         winner: String,
         multiplier: u32,
     },
+    Resettle {
+        game_id: String,
+        winner: String,
+        multiplier: u32,
+    },
+    Cancel {
+        game_id: String,
+    },
+}
+
+/// Escrow contract query messages
+#[derive(serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum EscrowQueryMsg {
+    /// An escrow, or null if the escrow contract holds none by that ID
+    FindEscrow { game_id: String },
+}
+
+/// The parts of the escrow's Escrow query response this contract reads
+#[derive(serde::Deserialize)]
+struct EscrowQueryResponse {
+    status: String,
+    player_a_deposited: u128,
+    player_b_deposited: u128,
+    cube_value: u32,
+    pending_double: Option<PendingDoubleDetails>,
+    projected_payout: Option<u128>,
+    projected_rake: Option<u128>,
 }
 
 #[entry_point]
@@ -43,22 +130,41 @@ pub fn instantiate(
         .escrow_contract
         .map(|addr| deps.api.addr_validate(&addr))
         .transpose()?;
-    let server_address = msg
-        .server_address
+    let achievement_nft_contract = msg
+        .achievement_nft_contract
         .map(|addr| deps.api.addr_validate(&addr))
         .transpose()?;
 
+    let rating_params = msg.rating_params.unwrap_or_default();
+    validate_rating_params(&rating_params)?;
+
     let config = Config {
         admin: info.sender.clone(),
         escrow_contract,
-        server_address,
+        server_address: None,
         usdc_denom: msg.usdc_denom,
+        rating_params,
+        challenge_period_seconds: msg.challenge_period_seconds.unwrap_or(0),
+        arbiter: None,
+        game_ttl_seconds: msg.game_ttl_seconds.unwrap_or(0),
+        dispute_period_seconds: msg.dispute_period_seconds.unwrap_or(0),
+        achievement_nft_contract,
     };
 
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     CONFIG.save(deps.storage, &config)?;
     TOTAL_GAMES.save(deps.storage, &0u64)?;
     GAME_COUNTER.save(deps.storage, &0u64)?;
 
+    for (role, addr) in [
+        (Role::Reporter, msg.server_address),
+        (Role::Arbiter, msg.arbiter),
+    ] {
+        if let Some(addr) = addr {
+            ROLES.grant(deps.storage, &role, &deps.api.addr_validate(&addr)?)?;
+        }
+    }
+
     Ok(Response::new()
         .add_attribute("action", "instantiate")
         .add_attribute("admin", info.sender))
@@ -71,507 +177,8707 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    if let Some(operation) = pausable_operation(&msg) {
+        ensure_not_paused(deps.storage, &operation)?;
+    }
+
     match msg {
         ExecuteMsg::CreateGame {
+            player_a,
+            player_b,
+            wager_amount,
+            game_id,
+            idempotency_key,
+            server_match_id,
+        } => execute_create_game(
+            deps,
+            env,
+            info,
+            player_a,
+            player_b,
+            wager_amount,
+            game_id,
+            idempotency_key,
+            server_match_id,
+        ),
+        ExecuteMsg::ProposeGame {
             opponent,
             wager_amount,
-        } => execute_create_game(deps, env, info, opponent, wager_amount),
-        ExecuteMsg::StartGame { game_id } => execute_start_game(deps, info, game_id),
+            expires_in,
+        } => execute_propose_game(deps, env, info, opponent, wager_amount, expires_in),
+        ExecuteMsg::AcceptGame { proposal_id } => execute_accept_game(deps, env, info, proposal_id),
+        ExecuteMsg::DeclineGame { proposal_id } => execute_decline_game(deps, info, proposal_id),
+        ExecuteMsg::CancelProposal { proposal_id } => {
+            execute_cancel_proposal(deps, info, proposal_id)
+        }
+        ExecuteMsg::CreateOpenChallenge {
+            wager_amount,
+            min_rating,
+            max_rating,
+            invite_pubkey,
+            expires_in,
+        } => execute_create_open_challenge(
+            deps,
+            env,
+            info,
+            wager_amount,
+            min_rating,
+            max_rating,
+            invite_pubkey,
+            expires_in,
+        ),
+        ExecuteMsg::AcceptChallenge {
+            challenge_id,
+            invite_signature,
+        } => execute_accept_challenge(deps, env, info, challenge_id, invite_signature),
+        ExecuteMsg::CancelChallenge { challenge_id } => {
+            execute_cancel_challenge(deps, info, challenge_id)
+        }
+        ExecuteMsg::CreateMatch {
+            player_a,
+            player_b,
+            match_length,
+            wager_amount,
+        } => execute_create_match(
+            deps,
+            env,
+            info,
+            player_a,
+            player_b,
+            match_length,
+            wager_amount,
+        ),
+        ExecuteMsg::StartGame {
+            game_id,
+            commit_dice,
+        } => execute_start_game(deps, info, game_id, commit_dice),
+        ExecuteMsg::EscrowActivated { game_id } => execute_escrow_activated(deps, info, game_id),
+        ExecuteMsg::EscrowForfeited {
+            game_id,
+            winner,
+            loser,
+            payout,
+            rake,
+            winner_stake,
+            loser_stake,
+            cube_value,
+        } => execute_escrow_forfeited(
+            deps,
+            env,
+            info,
+            game_id,
+            winner,
+            loser,
+            payout,
+            rake,
+            winner_stake,
+            loser_stake,
+            cube_value,
+        ),
+        ExecuteMsg::EscrowWithdrawn { game_id } => {
+            execute_escrow_withdrawn(deps, env, info, game_id)
+        }
+        ExecuteMsg::CancelGame { game_id } => execute_cancel_game(deps, env, info, game_id),
+        ExecuteMsg::ExpireGames { limit } => execute_expire_games(deps, env, limit),
+        ExecuteMsg::ExpireMatch { match_id } => execute_expire_match(deps, env, match_id),
         ExecuteMsg::ReportResult {
             game_id,
             winner,
             result_type,
             move_count,
-        } => execute_report_result(deps, env, info, game_id, winner, result_type, move_count),
-        ExecuteMsg::ReportAbandonment { game_id, abandoner } => {
-            execute_report_abandonment(deps, env, info, game_id, abandoner)
+            dice_reveals,
+            move_log,
+            move_log_signatures,
+            cube,
+        } => execute_report_result(
+            deps,
+            env,
+            info,
+            game_id,
+            winner,
+            result_type,
+            move_count,
+            dice_reveals,
+            move_log,
+            move_log_signatures,
+            cube,
+        ),
+        ExecuteMsg::ReportAbandonment {
+            game_id,
+            abandoner,
+            dice_reveals,
+            winner_signature,
+        } => execute_report_abandonment(
+            deps,
+            env,
+            info,
+            game_id,
+            abandoner,
+            dice_reveals,
+            winner_signature,
+        ),
+        ExecuteMsg::ChallengeResult { game_id } => {
+            execute_challenge_result(deps, env, info, game_id)
+        }
+        ExecuteMsg::ResolveChallenge {
+            game_id,
+            winner,
+            result_type,
+        } => execute_resolve_challenge(deps, env, info, game_id, winner, result_type),
+        ExecuteMsg::FinalizeResult { game_id } => execute_finalize_result(deps, env, game_id),
+        ExecuteMsg::RegisterSigningKey { pubkey } => {
+            execute_register_signing_key(deps, info, pubkey)
+        }
+        ExecuteMsg::SubmitSignedResult {
+            game_id,
+            winner,
+            result_type,
+            nonce,
+            cube,
+            dice_reveals,
+            signature_a,
+            signature_b,
+        } => execute_submit_signed_result(
+            deps,
+            env,
+            game_id,
+            winner,
+            result_type,
+            nonce,
+            cube,
+            dice_reveals,
+            signature_a,
+            signature_b,
+        ),
+        ExecuteMsg::DisputeResult { game_id, turns } => {
+            execute_dispute_result(deps, env, info, game_id, turns)
         }
+        ExecuteMsg::StartSeason {
+            name,
+            reset_percent,
+        } => execute_start_season(deps, env, info, name, reset_percent),
+        ExecuteMsg::EndSeason { limit } => execute_end_season(deps, env, info, limit),
+        ExecuteMsg::Pause { operations } => execute_set_paused(deps, info, operations, true),
+        ExecuteMsg::Unpause { operations } => execute_set_paused(deps, info, operations, false),
+        ExecuteMsg::ProposeNewAdmin {
+            address,
+            expires_in,
+        } => execute_propose_new_admin(deps, env, info, address, expires_in),
+        ExecuteMsg::AcceptAdmin {} => execute_accept_admin(deps, env, info),
+        ExecuteMsg::CancelAdminTransfer {} => execute_cancel_admin_transfer(deps, info),
+        ExecuteMsg::RenounceAdmin {} => execute_renounce_admin(deps, env, info),
+        ExecuteMsg::GrantRole { role, address } => execute_grant_role(deps, info, role, address),
+        ExecuteMsg::RevokeRole { role, address } => execute_revoke_role(deps, info, role, address),
         ExecuteMsg::UpdateConfig {
             escrow_contract,
+            rating_params,
+            challenge_period_seconds,
+            game_ttl_seconds,
+            dispute_period_seconds,
+            achievement_nft_contract,
+            server_address,
+            arbiter,
+        } => execute_update_config(
+            deps,
+            info,
+            escrow_contract,
+            rating_params,
+            challenge_period_seconds,
+            game_ttl_seconds,
+            dispute_period_seconds,
+            achievement_nft_contract,
             server_address,
-        } => execute_update_config(deps, info, escrow_contract, server_address),
+            arbiter,
+        ),
     }
 }
 
+/// Server or admin: create a game between two players who agreed to it
+/// off-chain (e.g. in the server's lobby). Players create games between
+/// themselves with `ProposeGame`/`AcceptGame` instead; this path skips the
+/// opponent's on-chain consent, so it stays Reporter-only. A player who
+/// never agreed stakes nothing until they deposit, and can cancel the game.
+#[allow(clippy::too_many_arguments)]
 fn execute_create_game(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    opponent: String,
+    player_a: String,
+    player_b: String,
     wager_amount: u128,
+    game_id: Option<String>,
+    idempotency_key: Option<String>,
+    server_match_id: Option<String>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let opponent_addr = deps.api.addr_validate(&opponent)?;
+    ROLES.ensure(deps.storage, &config.admin, &Role::Reporter, &info.sender)?;
 
-    if info.sender == opponent_addr {
+    let player_a = deps.api.addr_validate(&player_a)?;
+    let player_b = deps.api.addr_validate(&player_b)?;
+    if player_a == player_b {
         return Err(ContractError::SelfPlay {});
     }
 
-    // Generate game ID
-    let counter = GAME_COUNTER.load(deps.storage)?;
-    let game_id = format!("game-{}", counter + 1);
-    GAME_COUNTER.save(deps.storage, &(counter + 1))?;
+    let response = Response::new().add_attribute("action", "create_game");
 
-    let game = Game {
-        game_id: game_id.clone(),
-        player_a: info.sender.clone(),
-        player_b: opponent_addr.clone(),
-        wager_amount,
-        status: GameStatus::Created,
-        winner: None,
-        result_type: None,
-        created_at: env.block.time.seconds(),
-        completed_at: None,
-        move_count: 0,
+    // A retried transaction gets back the game its first attempt created,
+    // as long as it asks for the same game; anything else would silently
+    // hand the caller a game it did not ask for
+    if let Some(key) = &idempotency_key {
+        validate_id(key)?;
+        if let Some(existing) = IDEMPOTENCY_KEYS.may_load(deps.storage, key)? {
+            let game = GAMES.load(deps.storage, &existing)?;
+            if game.player_a != player_a
+                || game.player_b != player_b
+                || game.wager_amount != wager_amount
+                || game.server_match_id != server_match_id
+                || game_id.as_ref().is_some_and(|id| *id != existing)
+            {
+                return Err(ContractError::GameAlreadyExists { game_id: existing });
+            }
+            return Ok(response
+                .add_attribute("game_id", existing)
+                .add_attribute("duplicate", "true"));
+        }
+    }
+
+    if let Some(server_match_id) = &server_match_id {
+        validate_id(server_match_id)?;
+        if let Some(existing) = SERVER_MATCH_GAMES.may_load(deps.storage, server_match_id)? {
+            return Err(ContractError::GameAlreadyExists { game_id: existing });
+        }
+    }
+
+    let game_id = match game_id {
+        Some(game_id) => {
+            validate_id(&game_id)?;
+            if RESERVED_ID_PREFIXES
+                .iter()
+                .any(|prefix| game_id.starts_with(prefix))
+            {
+                return Err(ContractError::InvalidId {
+                    id: game_id,
+                    reason: "the prefix is reserved for generated IDs".to_string(),
+                });
+            }
+            if GAMES.has(deps.storage, &game_id) {
+                return Err(ContractError::GameAlreadyExists { game_id });
+            }
+            game_id
+        }
+        None => next_game_id(deps.storage)?,
     };
 
-    GAMES.save(deps.storage, &game_id, &game)?;
+    if let Some(server_match_id) = &server_match_id {
+        SERVER_MATCH_GAMES.save(deps.storage, server_match_id, &game_id)?;
+    }
+    if let Some(key) = &idempotency_key {
+        IDEMPOTENCY_KEYS.save(deps.storage, key, &game_id)?;
+    }
+
+    open_game(
+        deps.storage,
+        &env,
+        &config,
+        response,
+        game_id,
+        player_a,
+        player_b,
+        wager_amount,
+        server_match_id,
+    )
+}
+
+/// Caller-supplied IDs and keys: 1 to `MAX_ID_LENGTH` ASCII letters,
+/// digits, '-' or '_'
+fn validate_id(id: &str) -> Result<(), ContractError> {
+    let reason = if id.is_empty() || id.len() > MAX_ID_LENGTH {
+        format!("must be 1 to {MAX_ID_LENGTH} characters")
+    } else if !id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        "only letters, digits, '-' and '_' are allowed".to_string()
+    } else {
+        return Ok(());
+    };
+    Err(ContractError::InvalidId {
+        id: id.to_string(),
+        reason,
+    })
+}
 
-    let total = TOTAL_GAMES.load(deps.storage)?;
-    TOTAL_GAMES.save(deps.storage, &(total + 1))?;
+/// Create a standalone game and its escrow between two consenting players.
+#[allow(clippy::too_many_arguments)]
+fn open_game(
+    storage: &mut dyn cosmwasm_std::Storage,
+    env: &Env,
+    config: &Config,
+    response: Response,
+    game_id: String,
+    player_a: Addr,
+    player_b: Addr,
+    wager_amount: u128,
+    server_match_id: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut game = new_game(
+        storage,
+        env,
+        game_id.clone(),
+        player_a,
+        player_b,
+        wager_amount,
+        None,
+    )?;
+    if server_match_id.is_some() {
+        game.server_match_id = server_match_id;
+        GAMES.save(storage, &game_id, &game)?;
+    }
 
     // Initialize player stats if they don't exist
-    ensure_player_stats(deps.storage, &info.sender)?;
-    ensure_player_stats(deps.storage, &opponent_addr)?;
+    ensure_player_stats(storage, &game.player_a)?;
+    ensure_player_stats(storage, &game.player_b)?;
 
-    let mut response = Response::new()
-        .add_attribute("action", "create_game")
+    let mut response = response
         .add_attribute("game_id", &game_id)
-        .add_attribute("player_a", info.sender.to_string())
-        .add_attribute("player_b", opponent_addr.to_string())
+        .add_attribute("player_a", game.player_a.to_string())
+        .add_attribute("player_b", game.player_b.to_string())
         .add_attribute("wager_amount", wager_amount.to_string());
 
     // Create escrow if escrow contract is configured
-    if let Some(escrow_contract) = &config.escrow_contract {
-        let escrow_msg = EscrowExecuteMsg::CreateEscrow {
-            game_id: game_id.clone(),
-            player_a: info.sender.to_string(),
-            player_b: opponent_addr.to_string(),
-            wager_amount,
-        };
-        response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: escrow_contract.to_string(),
-            msg: to_json_binary(&escrow_msg)?,
-            funds: vec![],
-        }));
+    if let Some(msg) = create_escrow_msg(
+        config,
+        &game_id,
+        &game.player_a,
+        &game.player_b,
+        wager_amount,
+    )? {
+        response = response.add_message(msg);
     }
 
     Ok(response)
 }
 
-fn execute_start_game(
+fn next_game_id(storage: &mut dyn cosmwasm_std::Storage) -> StdResult<String> {
+    let counter = GAME_COUNTER.load(storage)?;
+    GAME_COUNTER.save(storage, &(counter + 1))?;
+    Ok(format!("game-{}", counter + 1))
+}
+
+fn execute_propose_game(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    game_id: String,
+    opponent: String,
+    wager_amount: u128,
+    expires_in: Option<u64>,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-
-    // Only admin or server can start games
-    let is_authorized = info.sender == config.admin
-        || config
-            .server_address
-            .as_ref()
-            .map_or(false, |s| info.sender == *s);
-    if !is_authorized {
-        return Err(ContractError::Unauthorized {});
+    let opponent = deps.api.addr_validate(&opponent)?;
+    if info.sender == opponent {
+        return Err(ContractError::SelfPlay {});
     }
-
-    let mut game = GAMES
-        .may_load(deps.storage, &game_id)?
-        .ok_or(ContractError::GameNotFound {
-            game_id: game_id.clone(),
-        })?;
-
-    if game.status != GameStatus::Created {
-        return Err(ContractError::InvalidGameStatus {
-            expected: "Created".to_string(),
-            got: format!("{:?}", game.status),
+    let expires_in = expires_in.unwrap_or(DEFAULT_PROPOSAL_EXPIRY);
+    if expires_in == 0 || expires_in > MAX_PROPOSAL_EXPIRY {
+        return Err(ContractError::InvalidProposalExpiry {
+            seconds: expires_in,
+            max: MAX_PROPOSAL_EXPIRY,
         });
     }
 
-    game.status = GameStatus::InProgress;
-    GAMES.save(deps.storage, &game_id, &game)?;
+    let counter = PROPOSAL_COUNTER.may_load(deps.storage)?.unwrap_or_default();
+    let proposal_id = format!("proposal-{}", counter + 1);
+    PROPOSAL_COUNTER.save(deps.storage, &(counter + 1))?;
+
+    let now = env.block.time.seconds();
+    PROPOSALS.save(
+        deps.storage,
+        &proposal_id,
+        &Proposal {
+            proposal_id: proposal_id.clone(),
+            proposer: info.sender.clone(),
+            opponent: opponent.clone(),
+            wager_amount,
+            status: ProposalStatus::Pending,
+            created_at: now,
+            expires_at: now + expires_in,
+            game_id: None,
+        },
+    )?;
 
     Ok(Response::new()
-        .add_attribute("action", "start_game")
-        .add_attribute("game_id", game_id))
+        .add_attribute("action", "propose_game")
+        .add_attribute("proposal_id", proposal_id)
+        .add_attribute("proposer", info.sender.to_string())
+        .add_attribute("opponent", opponent.to_string())
+        .add_attribute("wager_amount", wager_amount.to_string())
+        .add_attribute("expires_at", (now + expires_in).to_string()))
 }
 
-fn execute_report_result(
+fn execute_accept_game(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    game_id: String,
-    winner: String,
-    result_type_str: String,
-    move_count: u32,
+    proposal_id: String,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-
-    // Only server or admin can report results
-    let is_authorized = info.sender == config.admin
-        || config
-            .server_address
-            .as_ref()
-            .map_or(false, |s| info.sender == *s);
-    if !is_authorized {
+    let mut proposal = load_pending_proposal(deps.storage, &proposal_id)?;
+    if info.sender != proposal.opponent {
         return Err(ContractError::Unauthorized {});
     }
-
-    let mut game = GAMES
-        .may_load(deps.storage, &game_id)?
-        .ok_or(ContractError::GameNotFound {
-            game_id: game_id.clone(),
-        })?;
-
-    // Game must be Created or InProgress
-    if game.status != GameStatus::Created && game.status != GameStatus::InProgress {
-        return Err(ContractError::InvalidGameStatus {
-            expected: "Created or InProgress".to_string(),
-            got: format!("{:?}", game.status),
+    if env.block.time.seconds() >= proposal.expires_at {
+        return Err(ContractError::ProposalExpired {
+            proposal_id,
+            expires_at: proposal.expires_at,
         });
     }
 
-    let winner_addr = deps.api.addr_validate(&winner)?;
-    if winner_addr != game.player_a && winner_addr != game.player_b {
-        return Err(ContractError::InvalidWinner {});
-    }
+    let response = Response::new()
+        .add_attribute("action", "accept_game")
+        .add_attribute("proposal_id", &proposal_id);
+    let game_id = next_game_id(deps.storage)?;
+    let response = open_game(
+        deps.storage,
+        &env,
+        &config,
+        response,
+        game_id.clone(),
+        proposal.proposer.clone(),
+        proposal.opponent.clone(),
+        proposal.wager_amount,
+        None,
+    )?;
 
-    let result_type = match result_type_str.as_str() {
-        "normal" => ResultType::Normal,
-        "gammon" => ResultType::Gammon,
-        "backgammon" => ResultType::Backgammon,
-        _ => {
-            return Err(ContractError::InvalidResultType {
-                result_type: result_type_str,
-            })
-        }
-    };
+    proposal.status = ProposalStatus::Accepted;
+    proposal.game_id = Some(game_id);
+    PROPOSALS.save(deps.storage, &proposal_id, &proposal)?;
 
-    // Determine multiplier for wager settlement
-    let multiplier = match &result_type {
-        ResultType::Normal => 1u32,
-        ResultType::Gammon => 2u32,
-        ResultType::Backgammon => 3u32,
-    };
+    Ok(response)
+}
 
-    // Update game
-    game.status = GameStatus::Completed;
-    game.winner = Some(winner_addr.clone());
-    game.result_type = Some(result_type);
-    game.completed_at = Some(env.block.time.seconds());
-    game.move_count = move_count;
-    GAMES.save(deps.storage, &game_id, &game)?;
+fn execute_decline_game(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: String,
+) -> Result<Response, ContractError> {
+    let mut proposal = load_pending_proposal(deps.storage, &proposal_id)?;
+    if info.sender != proposal.opponent {
+        return Err(ContractError::Unauthorized {});
+    }
 
-    // Determine loser
-    let loser_addr = if winner_addr == game.player_a {
-        game.player_b.clone()
-    } else {
-        game.player_a.clone()
-    };
+    proposal.status = ProposalStatus::Declined;
+    PROPOSALS.save(deps.storage, &proposal_id, &proposal)?;
 
-    // NOTE: Stats are saved before the escrow settlement message is dispatched.
-    // This is safe because CosmWasm executes all messages atomically — if the
-    // escrow settlement fails, the entire transaction (including stat updates) reverts.
+    Ok(Response::new()
+        .add_attribute("action", "decline_game")
+        .add_attribute("proposal_id", proposal_id))
+}
 
-    // Update winner stats
-    let mut winner_stats = PLAYER_STATS.load(deps.storage, &winner_addr)?;
-    winner_stats.games_played += 1;
-    winner_stats.games_won += 1;
-    winner_stats.rating = winner_stats.rating.saturating_add(RATING_CHANGE);
-    winner_stats.total_wagered += game.wager_amount;
-    winner_stats.total_won += game.wager_amount * 2; // approximate: winner gets both wagers
-    PLAYER_STATS.save(deps.storage, &winner_addr, &winner_stats)?;
+fn execute_cancel_proposal(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: String,
+) -> Result<Response, ContractError> {
+    let mut proposal = load_pending_proposal(deps.storage, &proposal_id)?;
+    if info.sender != proposal.proposer {
+        return Err(ContractError::Unauthorized {});
+    }
 
-    // Update loser stats
-    let mut loser_stats = PLAYER_STATS.load(deps.storage, &loser_addr)?;
-    loser_stats.games_played += 1;
-    loser_stats.rating = loser_stats
-        .rating
-        .saturating_sub(RATING_CHANGE)
-        .max(MIN_RATING);
-    loser_stats.total_wagered += game.wager_amount;
-    PLAYER_STATS.save(deps.storage, &loser_addr, &loser_stats)?;
+    proposal.status = ProposalStatus::Cancelled;
+    PROPOSALS.save(deps.storage, &proposal_id, &proposal)?;
 
-    let mut response = Response::new()
-        .add_attribute("action", "report_result")
-        .add_attribute("game_id", &game_id)
-        .add_attribute("winner", winner_addr.to_string())
-        .add_attribute(
-            "result_type",
-            format!("{:?}", game.result_type.as_ref().unwrap()),
-        );
+    Ok(Response::new()
+        .add_attribute("action", "cancel_proposal")
+        .add_attribute("proposal_id", proposal_id))
+}
 
-    // Settle escrow if configured
-    if let Some(escrow_contract) = &config.escrow_contract {
-        if game.wager_amount > 0 {
-            let settle_msg = if multiplier > 1 {
-                EscrowExecuteMsg::SettleWithMultiplier {
-                    game_id: game_id.clone(),
-                    winner: winner_addr.to_string(),
-                    multiplier,
-                }
-            } else {
-                EscrowExecuteMsg::Settle {
-                    game_id: game_id.clone(),
-                    winner: winner_addr.to_string(),
-                }
-            };
-            response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: escrow_contract.to_string(),
-                msg: to_json_binary(&settle_msg)?,
-                funds: vec![],
-            }));
+fn load_pending_proposal(
+    storage: &dyn cosmwasm_std::Storage,
+    proposal_id: &str,
+) -> Result<Proposal, ContractError> {
+    let proposal =
+        PROPOSALS
+            .may_load(storage, proposal_id)?
+            .ok_or(ContractError::ProposalNotFound {
+                proposal_id: proposal_id.to_string(),
+            })?;
+    if proposal.status != ProposalStatus::Pending {
+        return Err(ContractError::ProposalNotPending {
+            proposal_id: proposal_id.to_string(),
+        });
+    }
+    Ok(proposal)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_create_open_challenge(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wager_amount: u128,
+    min_rating: Option<u32>,
+    max_rating: Option<u32>,
+    invite_pubkey: Option<Binary>,
+    expires_in: Option<u64>,
+) -> Result<Response, ContractError> {
+    if let (Some(min), Some(max)) = (min_rating, max_rating) {
+        if min > max {
+            return Err(ContractError::InvalidRatingRange { min, max });
         }
     }
+    if let Some(pubkey) = &invite_pubkey {
+        validate_pubkey(pubkey)?;
+    }
+    let expires_in = expires_in.unwrap_or(DEFAULT_CHALLENGE_EXPIRY);
+    if expires_in == 0 || expires_in > MAX_CHALLENGE_EXPIRY {
+        return Err(ContractError::InvalidChallengeExpiry {
+            seconds: expires_in,
+            max: MAX_CHALLENGE_EXPIRY,
+        });
+    }
+    let now = env.block.time.seconds();
 
-    Ok(response)
+    let counter = CHALLENGE_COUNTER
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let challenge_id = format!("challenge-{}", counter + 1);
+    CHALLENGE_COUNTER.save(deps.storage, &(counter + 1))?;
+
+    let private = invite_pubkey.is_some();
+    CHALLENGES.save(
+        deps.storage,
+        &challenge_id,
+        &Challenge {
+            challenge_id: challenge_id.clone(),
+            creator: info.sender.clone(),
+            wager_amount,
+            min_rating,
+            max_rating,
+            invite_pubkey,
+            status: ChallengeStatus::Open,
+            created_at: now,
+            expires_at: now + expires_in,
+            accepted_by: None,
+            game_id: None,
+        },
+    )?;
+    ensure_player_stats(deps.storage, &info.sender)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_open_challenge")
+        .add_attribute("challenge_id", challenge_id)
+        .add_attribute("creator", info.sender.to_string())
+        .add_attribute("wager_amount", wager_amount.to_string())
+        .add_attribute("private", private.to_string())
+        .add_attribute("expires_at", (now + expires_in).to_string()))
 }
 
-fn execute_report_abandonment(
+fn execute_accept_challenge(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    game_id: String,
-    abandoner: String,
+    challenge_id: String,
+    invite_signature: Option<Binary>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
+    let mut challenge = load_open_challenge(deps.storage, &challenge_id)?;
+    if info.sender == challenge.creator {
+        return Err(ContractError::SelfPlay {});
+    }
+    if env.block.time.seconds() >= challenge.expires_at {
+        return Err(ContractError::ChallengeExpired {
+            challenge_id,
+            expires_at: challenge.expires_at,
+        });
+    }
 
-    let is_authorized = info.sender == config.admin
-        || config
-            .server_address
-            .as_ref()
-            .map_or(false, |s| info.sender == *s);
-    if !is_authorized {
-        return Err(ContractError::Unauthorized {});
+    // The invite signs for one acceptor, so the signature cannot be reused
+    // by whoever sees it in the transaction
+    if let Some(pubkey) = &challenge.invite_pubkey {
+        let signature = invite_signature.ok_or(ContractError::InvalidInvite {})?;
+        let hash = invite_signing_payload(&env, &challenge_id, &info.sender)?.1;
+        let verified = deps
+            .api
+            .secp256k1_verify(&hash, &signature, pubkey)
+            .unwrap_or(false);
+        if !verified {
+            return Err(ContractError::InvalidInvite {});
+        }
     }
 
-    let mut game = GAMES
-        .may_load(deps.storage, &game_id)?
-        .ok_or(ContractError::GameNotFound {
-            game_id: game_id.clone(),
-        })?;
+    let rating = PLAYER_STATS
+        .may_load(deps.storage, &info.sender)?
+        .map_or(DEFAULT_RATING, |stats| stats.rating);
+    if challenge.min_rating.is_some_and(|min| rating < min)
+        || challenge.max_rating.is_some_and(|max| rating > max)
+    {
+        return Err(ContractError::RatingOutOfRange { rating });
+    }
 
-    if game.status != GameStatus::Created && game.status != GameStatus::InProgress {
-        return Err(ContractError::InvalidGameStatus {
-            expected: "Created or InProgress".to_string(),
-            got: format!("{:?}", game.status),
+    let response = Response::new()
+        .add_attribute("action", "accept_challenge")
+        .add_attribute("challenge_id", &challenge_id);
+    let game_id = next_game_id(deps.storage)?;
+    let response = open_game(
+        deps.storage,
+        &env,
+        &config,
+        response,
+        game_id.clone(),
+        challenge.creator.clone(),
+        info.sender.clone(),
+        challenge.wager_amount,
+        None,
+    )?;
+
+    challenge.status = ChallengeStatus::Accepted;
+    challenge.accepted_by = Some(info.sender);
+    challenge.game_id = Some(game_id);
+    CHALLENGES.save(deps.storage, &challenge_id, &challenge)?;
+
+    Ok(response)
+}
+
+fn execute_cancel_challenge(
+    deps: DepsMut,
+    info: MessageInfo,
+    challenge_id: String,
+) -> Result<Response, ContractError> {
+    let mut challenge = load_open_challenge(deps.storage, &challenge_id)?;
+    if info.sender != challenge.creator {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    challenge.status = ChallengeStatus::Cancelled;
+    CHALLENGES.save(deps.storage, &challenge_id, &challenge)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_challenge")
+        .add_attribute("challenge_id", challenge_id))
+}
+
+/// The canonical payload an invite key signs for `acceptor`, and its sha256
+fn invite_signing_payload(
+    env: &Env,
+    challenge_id: &str,
+    acceptor: &Addr,
+) -> StdResult<(Vec<u8>, [u8; 32])> {
+    let payload = to_json_vec(&SignedInvitePayload {
+        chain_id: env.block.chain_id.clone(),
+        contract: env.contract.address.to_string(),
+        challenge_id: challenge_id.to_string(),
+        acceptor: acceptor.to_string(),
+    })?;
+    let hash = Sha256::digest(&payload).into();
+    Ok((payload, hash))
+}
+
+fn load_open_challenge(
+    storage: &dyn cosmwasm_std::Storage,
+    challenge_id: &str,
+) -> Result<Challenge, ContractError> {
+    let challenge =
+        CHALLENGES
+            .may_load(storage, challenge_id)?
+            .ok_or(ContractError::ChallengeNotFound {
+                challenge_id: challenge_id.to_string(),
+            })?;
+    if challenge.status != ChallengeStatus::Open {
+        return Err(ContractError::ChallengeNotOpen {
+            challenge_id: challenge_id.to_string(),
         });
     }
+    Ok(challenge)
+}
 
-    let abandoner_addr = deps.api.addr_validate(&abandoner)?;
-    if abandoner_addr != game.player_a && abandoner_addr != game.player_b {
-        return Err(ContractError::InvalidWinner {}); // reuse error - abandoner must be a player
+/// Create an N-point match between two players, along with its first game.
+/// The wager is escrowed once for the whole match under the match ID.
+fn execute_create_match(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    player_a: String,
+    player_b: String,
+    match_length: u32,
+    wager_amount: u128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ROLES.ensure(deps.storage, &config.admin, &Role::Reporter, &info.sender)?;
+
+    let player_a = deps.api.addr_validate(&player_a)?;
+    let player_b = deps.api.addr_validate(&player_b)?;
+    if player_a == player_b {
+        return Err(ContractError::SelfPlay {});
+    }
+    if match_length == 0 || match_length > MAX_MATCH_LENGTH {
+        return Err(ContractError::InvalidMatchLength {
+            match_length,
+            max: MAX_MATCH_LENGTH,
+        });
     }
 
-    // The non-abandoner wins
-    let winner_addr = if abandoner_addr == game.player_a {
-        game.player_b.clone()
-    } else {
-        game.player_a.clone()
+    let counter = MATCH_COUNTER.may_load(deps.storage)?.unwrap_or_default();
+    let match_id = format!("match-{}", counter + 1);
+    MATCH_COUNTER.save(deps.storage, &(counter + 1))?;
+
+    let first_game_id = format!("{}-1", match_id);
+    let bg_match = Match {
+        match_id: match_id.clone(),
+        player_a: player_a.clone(),
+        player_b: player_b.clone(),
+        match_length,
+        wager_amount,
+        score_a: 0,
+        score_b: 0,
+        crawford: false,
+        crawford_played: false,
+        status: MatchStatus::InProgress,
+        game_ids: vec![first_game_id.clone()],
+        winner: None,
+        created_at: env.block.time.seconds(),
+        completed_at: None,
     };
+    MATCHES.save(deps.storage, &match_id, &bg_match)?;
 
-    game.status = GameStatus::Abandoned;
-    game.winner = Some(winner_addr.clone());
-    game.result_type = Some(ResultType::Normal);
-    game.completed_at = Some(env.block.time.seconds());
-    GAMES.save(deps.storage, &game_id, &game)?;
+    // Games inside a match carry no wager of their own
+    new_game(
+        deps.storage,
+        &env,
+        first_game_id.clone(),
+        player_a.clone(),
+        player_b.clone(),
+        0,
+        Some(match_id.clone()),
+    )?;
 
-    // Update stats (abandoner loses rating)
-    let mut winner_stats = PLAYER_STATS.load(deps.storage, &winner_addr)?;
-    winner_stats.games_played += 1;
-    winner_stats.games_won += 1;
-    winner_stats.rating = winner_stats.rating.saturating_add(RATING_CHANGE);
-    winner_stats.total_wagered += game.wager_amount;
-    winner_stats.total_won += game.wager_amount * 2;
-    PLAYER_STATS.save(deps.storage, &winner_addr, &winner_stats)?;
-
-    let mut abandoner_stats = PLAYER_STATS.load(deps.storage, &abandoner_addr)?;
-    abandoner_stats.games_played += 1;
-    abandoner_stats.rating = abandoner_stats
-        .rating
-        .saturating_sub(RATING_CHANGE)
-        .max(MIN_RATING);
-    abandoner_stats.total_wagered += game.wager_amount;
-    PLAYER_STATS.save(deps.storage, &abandoner_addr, &abandoner_stats)?;
+    ensure_player_stats(deps.storage, &player_a)?;
+    ensure_player_stats(deps.storage, &player_b)?;
 
     let mut response = Response::new()
-        .add_attribute("action", "report_abandonment")
-        .add_attribute("game_id", &game_id)
-        .add_attribute("abandoner", abandoner_addr.to_string())
-        .add_attribute("winner", winner_addr.to_string());
+        .add_attribute("action", "create_match")
+        .add_attribute("match_id", &match_id)
+        .add_attribute("game_id", &first_game_id)
+        .add_attribute("player_a", player_a.to_string())
+        .add_attribute("player_b", player_b.to_string())
+        .add_attribute("match_length", match_length.to_string())
+        .add_attribute("wager_amount", wager_amount.to_string());
 
-    // Settle escrow - winner gets the pot
-    if let Some(escrow_contract) = &config.escrow_contract {
-        if game.wager_amount > 0 {
-            let settle_msg = EscrowExecuteMsg::Settle {
-                game_id: game_id.clone(),
-                winner: winner_addr.to_string(),
-            };
-            response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: escrow_contract.to_string(),
-                msg: to_json_binary(&settle_msg)?,
-                funds: vec![],
-            }));
-        }
+    if let Some(msg) = create_escrow_msg(&config, &match_id, &player_a, &player_b, wager_amount)? {
+        response = response.add_message(msg);
     }
 
     Ok(response)
 }
 
-fn execute_update_config(
+/// Store a new game in `Created` status and bump the game total
+fn new_game(
+    storage: &mut dyn cosmwasm_std::Storage,
+    env: &Env,
+    game_id: String,
+    player_a: Addr,
+    player_b: Addr,
+    wager_amount: u128,
+    match_id: Option<String>,
+) -> Result<Game, ContractError> {
+    let game = Game {
+        game_id: game_id.clone(),
+        player_a,
+        player_b,
+        wager_amount,
+        status: GameStatus::Created,
+        winner: None,
+        result_type: None,
+        created_at: env.block.time.seconds(),
+        completed_at: None,
+        move_count: 0,
+        match_id,
+        dice_committed: false,
+        move_log: None,
+        move_log_signed: false,
+        result_reported_at: None,
+        challenge_deadline: None,
+        abandoned_by: None,
+        challenged_by: None,
+        cube: None,
+        points_won: 0,
+        server_match_id: None,
+    };
+
+    GAMES.save(storage, &game_id, &game)?;
+
+    let total = TOTAL_GAMES.load(storage)?;
+    TOTAL_GAMES.save(storage, &(total + 1))?;
+    record_platform_stats(storage, game.created_at, |stats| stats.games_created += 1)?;
+
+    Ok(game)
+}
+
+fn create_escrow_msg(
+    config: &Config,
+    escrow_id: &str,
+    player_a: &Addr,
+    player_b: &Addr,
+    wager_amount: u128,
+) -> StdResult<Option<CosmosMsg>> {
+    let Some(escrow_contract) = &config.escrow_contract else {
+        return Ok(None);
+    };
+    let escrow_msg = EscrowExecuteMsg::CreateEscrow {
+        game_id: escrow_id.to_string(),
+        player_a: player_a.to_string(),
+        player_b: player_b.to_string(),
+        wager_amount,
+    };
+    Ok(Some(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: escrow_contract.to_string(),
+        msg: to_json_binary(&escrow_msg)?,
+        funds: vec![],
+    })))
+}
+
+fn execute_start_game(
     deps: DepsMut,
     info: MessageInfo,
-    escrow_contract: Option<String>,
-    server_address: Option<String>,
+    game_id: String,
+    commit_dice: Option<bool>,
 ) -> Result<Response, ContractError> {
-    let mut config = CONFIG.load(deps.storage)?;
-    if info.sender != config.admin {
-        return Err(ContractError::Unauthorized {});
+    let config = CONFIG.load(deps.storage)?;
+    ROLES.ensure(deps.storage, &config.admin, &Role::Reporter, &info.sender)?;
+
+    let mut game = GAMES
+        .may_load(deps.storage, &game_id)?
+        .ok_or(ContractError::GameNotFound {
+            game_id: game_id.clone(),
+        })?;
+
+    // A game already started by its escrow or its match can still have its
+    // dice declared committed
+    let commit_dice = commit_dice.unwrap_or(false);
+    let awaiting_commit =
+        game.status == GameStatus::InProgress && !game.dice_committed && commit_dice;
+    if game.status != GameStatus::Created && !awaiting_commit {
+        return Err(ContractError::InvalidGameStatus {
+            expected: "Created".to_string(),
+            got: format!("{:?}", game.status),
+        });
     }
 
-    if let Some(addr) = escrow_contract {
-        config.escrow_contract = Some(deps.api.addr_validate(&addr)?);
+    game.status = GameStatus::InProgress;
+    game.dice_committed = commit_dice;
+    GAMES.save(deps.storage, &game_id, &game)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "start_game")
+        .add_attribute("game_id", &game_id)
+        .add_attribute("dice_committed", commit_dice.to_string()))
+}
+
+fn execute_escrow_activated(
+    deps: DepsMut,
+    info: MessageInfo,
+    escrow_id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.escrow_contract.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
     }
-    if let Some(addr) = server_address {
-        config.server_address = Some(deps.api.addr_validate(&addr)?);
+
+    // A match's escrow covers the whole match; its current game is waiting
+    let game_id = match MATCHES.may_load(deps.storage, &escrow_id)? {
+        Some(bg_match) => bg_match.game_ids.last().cloned().unwrap_or(escrow_id),
+        None => escrow_id,
+    };
+    let mut game = GAMES
+        .may_load(deps.storage, &game_id)?
+        .ok_or(ContractError::GameNotFound {
+            game_id: game_id.clone(),
+        })?;
+
+    if game.status != GameStatus::Created {
+        return Err(ContractError::InvalidGameStatus {
+            expected: "Created".to_string(),
+            got: format!("{:?}", game.status),
+        });
     }
 
-    CONFIG.save(deps.storage, &config)?;
-    Ok(Response::new().add_attribute("action", "update_config"))
+    game.status = GameStatus::InProgress;
+    GAMES.save(deps.storage, &game_id, &game)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "escrow_activated")
+        .add_attribute("game_id", game_id))
 }
 
-/// Ensure player stats exist (initialize if new player)
-fn ensure_player_stats(
-    storage: &mut dyn cosmwasm_std::Storage,
-    addr: &Addr,
-) -> Result<(), ContractError> {
-    if !PLAYER_STATS.has(storage, addr) {
-        PLAYER_STATS.save(
-            storage,
-            addr,
-            &PlayerStats {
-                address: addr.clone(),
-                games_played: 0,
-                games_won: 0,
-                rating: DEFAULT_RATING,
-                total_wagered: 0,
-                total_won: 0,
-            },
+#[allow(clippy::too_many_arguments)]
+fn execute_escrow_forfeited(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    escrow_id: String,
+    winner: String,
+    loser: String,
+    payout: u128,
+    rake: u128,
+    winner_stake: u128,
+    loser_stake: u128,
+    cube_value: u32,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.escrow_contract.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let winner = deps.api.addr_validate(&winner)?;
+    let loser = deps.api.addr_validate(&loser)?;
+    ensure_player_stats(deps.storage, &winner)?;
+    ensure_player_stats(deps.storage, &loser)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "escrow_forfeited")
+        .add_attribute("escrow_id", &escrow_id);
+
+    // The forfeit ends the game being played, and any match, as no
+    // settlement of the escrow can follow it. A match's escrow covers the
+    // whole match; only its current game can still be unfinished.
+    let game_id = match MATCHES.may_load(deps.storage, &escrow_id)? {
+        Some(bg_match) => bg_match.game_ids.last().cloned(),
+        None => Some(escrow_id.clone()),
+    };
+    let game = match game_id {
+        Some(game_id) => GAMES.may_load(deps.storage, &game_id)?,
+        None => None,
+    };
+    if let Some(game) = game.filter(|game| {
+        matches!(
+            game.status,
+            GameStatus::Created | GameStatus::InProgress | GameStatus::PendingResult
+        )
+    }) {
+        response = finalize_forfeit(
+            deps.storage,
+            &env,
+            &config,
+            response,
+            game,
+            winner.clone(),
+            loser.clone(),
+            cube_value,
         )?;
+        let mut settlement = SETTLEMENTS.load(deps.storage, &escrow_id)?;
+        settlement.payout = payout;
+        settlement.rake = rake;
+        settlement.cube_value = cube_value;
+        settlement.winner_stake = winner_stake;
+        settlement.loser_stake = loser_stake;
+        SETTLEMENTS.save(deps.storage, &escrow_id, &settlement)?;
     }
-    Ok(())
+
+    // The escrow paid out directly, so book what it paid like a settlement
+    let mut winner_stats = PLAYER_STATS.load(deps.storage, &winner)?;
+    winner_stats.total_won += payout;
+    winner_stats.rake_paid += rake;
+    winner_stats.total_staked += winner_stake;
+    PLAYER_STATS.save(deps.storage, &winner, &winner_stats)?;
+
+    let mut loser_stats = PLAYER_STATS.load(deps.storage, &loser)?;
+    loser_stats.total_staked += loser_stake;
+    PLAYER_STATS.save(deps.storage, &loser, &loser_stats)?;
+
+    Ok(response
+        .add_attribute("payout", payout.to_string())
+        .add_attribute("rake", rake.to_string()))
 }
 
-#[entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
-        QueryMsg::Game { game_id } => to_json_binary(&query_game(deps, game_id)?),
-        QueryMsg::PlayerStats { address } => to_json_binary(&query_player_stats(deps, address)?),
-        QueryMsg::Stats {} => to_json_binary(&query_stats(deps)?),
-        QueryMsg::PlayerGames {
-            address,
-            start_after,
-            limit,
-        } => to_json_binary(&query_player_games(deps, address, start_after, limit)?),
+fn execute_escrow_withdrawn(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    escrow_id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.escrow_contract.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let response = Response::new()
+        .add_attribute("action", "escrow_withdrawn")
+        .add_attribute("escrow_id", &escrow_id);
+
+    // A match's escrow covers the whole match; only its current game can
+    // still be unfinished
+    let game_id = match MATCHES.may_load(deps.storage, &escrow_id)? {
+        Some(bg_match) => bg_match.game_ids.last().cloned().unwrap_or(escrow_id),
+        None => escrow_id,
+    };
+    let Some(game) = GAMES.may_load(deps.storage, &game_id)? else {
+        return Ok(response);
+    };
+    if !matches!(
+        game.status,
+        GameStatus::Created | GameStatus::InProgress | GameStatus::PendingResult
+    ) {
+        return Ok(response);
     }
+
+    close_unfinished_game(
+        deps.storage,
+        &deps.querier,
+        &env,
+        &config,
+        response.add_attribute("game_id", &game_id),
+        game,
+        GameStatus::Cancelled,
+    )
 }
 
-fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+fn execute_cancel_game(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    game_id: String,
+) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    Ok(ConfigResponse {
-        admin: config.admin,
-        escrow_contract: config.escrow_contract,
-        server_address: config.server_address,
-        usdc_denom: config.usdc_denom,
-    })
-}
+    let game = GAMES
+        .may_load(deps.storage, &game_id)?
+        .ok_or(ContractError::GameNotFound {
+            game_id: game_id.clone(),
+        })?;
 
-fn query_game(deps: Deps, game_id: String) -> StdResult<GameResponse> {
-    let game = GAMES.load(deps.storage, &game_id)?;
-    Ok(GameResponse {
-        game_id: game.game_id,
-        player_a: game.player_a,
-        player_b: game.player_b,
-        wager_amount: game.wager_amount,
-        status: format!("{:?}", game.status),
-        winner: game.winner,
-        result_type: game.result_type.map(|rt| format!("{:?}", rt)),
-        created_at: game.created_at,
-        completed_at: game.completed_at,
-        move_count: game.move_count,
-    })
-}
+    let is_authorized = info.sender == game.player_a
+        || info.sender == game.player_b
+        || ROLES.has(deps.storage, &config.admin, &Role::Reporter, &info.sender);
+    if !is_authorized {
+        return Err(ContractError::Unauthorized {});
+    }
 
-fn query_player_stats(deps: Deps, address: String) -> StdResult<PlayerStatsResponse> {
-    let addr = deps.api.addr_validate(&address)?;
-    match PLAYER_STATS.may_load(deps.storage, &addr)? {
-        Some(stats) => Ok(PlayerStatsResponse {
-            address: stats.address,
-            games_played: stats.games_played,
-            games_won: stats.games_won,
-            rating: stats.rating,
-            total_wagered: stats.total_wagered,
-            total_won: stats.total_won,
-        }),
-        None => Ok(PlayerStatsResponse {
-            address: addr,
-            games_played: 0,
-            games_won: 0,
-            rating: DEFAULT_RATING,
-            total_wagered: 0,
-            total_won: 0,
-        }),
+    if game.status != GameStatus::Created {
+        return Err(ContractError::InvalidGameStatus {
+            expected: "Created".to_string(),
+            got: format!("{:?}", game.status),
+        });
+    }
+    if !is_cancellable(deps.storage, &game)? {
+        return Err(ContractError::CancelNotAllowed {
+            game_id,
+            reason: "the match is already under way".to_string(),
+        });
     }
-}
 
-fn query_stats(deps: Deps) -> StdResult<StatsResponse> {
-    Ok(StatsResponse {
-        total_games: TOTAL_GAMES.load(deps.storage)?,
-    })
+    let response = Response::new()
+        .add_attribute("action", "cancel_game")
+        .add_attribute("game_id", &game_id)
+        .add_attribute("cancelled_by", info.sender.to_string());
+    close_unfinished_game(
+        deps.storage,
+        &deps.querier,
+        &env,
+        &config,
+        response,
+        game,
+        GameStatus::Cancelled,
+    )
 }
 
-// TODO: Add secondary index (e.g., Map<(Addr, u64), String>) for O(1) player game lookup
-// Current implementation is O(n) and will degrade with many games
-fn query_player_games(
-    deps: Deps,
-    address: String,
-    _start_after: Option<String>,
+/// Expire the oldest games still in `Created` past the game TTL
+fn execute_expire_games(
+    deps: DepsMut,
+    env: Env,
     limit: Option<u32>,
-) -> StdResult<GamesListResponse> {
-    let addr = deps.api.addr_validate(&address)?;
-    let limit = limit.unwrap_or(10).min(30) as usize;
-
-    // Note: This is a simple scan. For production, we'd want a secondary index.
-    let games: Vec<GameResponse> = GAMES
-        .range(deps.storage, None, None, cosmwasm_std::Order::Descending)
-        .filter_map(|item| {
-            let (_, game) = item.ok()?;
-            if game.player_a == addr || game.player_b == addr {
-                Some(GameResponse {
-                    game_id: game.game_id,
-                    player_a: game.player_a,
-                    player_b: game.player_b,
-                    wager_amount: game.wager_amount,
-                    status: format!("{:?}", game.status),
-                    winner: game.winner,
-                    result_type: game.result_type.map(|rt| format!("{:?}", rt)),
-                    created_at: game.created_at,
-                    completed_at: game.completed_at,
-                    move_count: game.move_count,
-                })
-            } else {
-                None
-            }
-        })
-        .take(limit)
-        .collect();
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut response = Response::new().add_attribute("action", "expire_games");
+    if config.game_ttl_seconds == 0 {
+        return Ok(response.add_attribute("expired", "0"));
+    }
 
-    Ok(GamesListResponse { games })
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let Some(cutoff) = env
+        .block
+        .time
+        .seconds()
+        .checked_sub(config.game_ttl_seconds)
+    else {
+        return Ok(response.add_attribute("expired", "0"));
+    };
+
+    // (cutoff + 1, "") sorts before every game created after the cutoff.
+    // Later match games expire too, taking their stalled match with them.
+    let stale = GAMES
+        .idx
+        .status
+        .sub_prefix(format!("{:?}", GameStatus::Created))
+        .range(
+            deps.storage,
+            None,
+            Some(Bound::exclusive((cutoff + 1, String::new()))),
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(_, game)| game))
+        .collect::<StdResult<Vec<Game>>>()?;
+
+    let expired = stale.len();
+    for game in stale {
+        response = response.add_attribute("game_id", &game.game_id);
+        response = close_unfinished_game(
+            deps.storage,
+            &deps.querier,
+            &env,
+            &config,
+            response,
+            game,
+            GameStatus::Expired,
+        )?;
+    }
+
+    Ok(response.add_attribute("expired", expired.to_string()))
 }
 
-#[entry_point]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    Ok(Response::new().add_attribute("action", "migrate"))
+/// Expire a match stuck on an unfinished game, e.g. one the server stopped
+/// reporting. Later match games start straight away, so they never wait in
+/// Created for `ExpireGames`.
+fn execute_expire_match(
+    deps: DepsMut,
+    env: Env,
+    match_id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let not_allowed = |reason: &str| ContractError::ExpireNotAllowed {
+        match_id: match_id.clone(),
+        reason: reason.to_string(),
+    };
+    if config.game_ttl_seconds == 0 {
+        return Err(not_allowed("no game TTL is configured"));
+    }
+    let bg_match = MATCHES
+        .may_load(deps.storage, &match_id)?
+        .ok_or_else(|| not_allowed("match not found"))?;
+    if bg_match.status != MatchStatus::InProgress {
+        return Err(not_allowed("the match is not in progress"));
+    }
+    let game_id = bg_match
+        .game_ids
+        .last()
+        .ok_or_else(|| not_allowed("the match has no games"))?;
+    let game = GAMES.load(deps.storage, game_id)?;
+    if game.status != GameStatus::Created && game.status != GameStatus::InProgress {
+        return Err(not_allowed("its current game already has a result"));
+    }
+    let stale_at = game.created_at + config.game_ttl_seconds;
+    if env.block.time.seconds() < stale_at {
+        return Err(not_allowed(&format!(
+            "its current game is not stale until {stale_at}"
+        )));
+    }
+
+    let response = Response::new()
+        .add_attribute("action", "expire_match")
+        .add_attribute("game_id", &game.game_id);
+    close_unfinished_game(
+        deps.storage,
+        &deps.querier,
+        &env,
+        &config,
+        response,
+        game,
+        GameStatus::Expired,
+    )
+}
+
+/// A game can be called off unless it is a later game of a match that is
+/// already being played; those end by result, abandonment or ExpireMatch.
+fn is_cancellable(storage: &dyn cosmwasm_std::Storage, game: &Game) -> StdResult<bool> {
+    match &game.match_id {
+        None => Ok(true),
+        Some(match_id) => Ok(MATCHES.load(storage, match_id)?.game_ids.len() == 1),
+    }
+}
+
+/// Move an unfinished game to `status`, calling off its match if it has
+/// one, and ask the escrow to refund whatever was deposited
+fn close_unfinished_game(
+    storage: &mut dyn cosmwasm_std::Storage,
+    querier: &QuerierWrapper,
+    env: &Env,
+    config: &Config,
+    mut response: Response,
+    mut game: Game,
+    status: GameStatus,
+) -> Result<Response, ContractError> {
+    game.status = status;
+    game.completed_at = Some(env.block.time.seconds());
+    GAMES.save(storage, &game.game_id, &game)?;
+
+    let (escrow_id, wager_amount) = match &game.match_id {
+        None => (game.game_id.clone(), game.wager_amount),
+        Some(match_id) => {
+            let mut bg_match = MATCHES.load(storage, match_id)?;
+            bg_match.status = MatchStatus::Cancelled;
+            bg_match.completed_at = Some(env.block.time.seconds());
+            MATCHES.save(storage, match_id, &bg_match)?;
+            response = response.add_attribute("match_id", match_id);
+            (match_id.clone(), bg_match.wager_amount)
+        }
+    };
+
+    if wager_amount > 0 {
+        if let Some(msg) = cancel_escrow_msg(querier, config, &escrow_id)? {
+            response = response.add_message(msg);
+        }
+    }
+    Ok(response)
+}
+
+/// Escrow statuses that may still hold deposits and accept `Cancel`. A
+/// Withdrawn escrow holds the deposits of whoever has not withdrawn yet.
+const OPEN_ESCROW_STATUSES: [&str; 4] = [
+    "AwaitingDeposits",
+    "Active",
+    "AwaitingDoubleDeposits",
+    "Withdrawn",
+];
+
+/// A `Cancel` for the escrow if it is still open. One that was never
+/// created, or already settled, refunded or timed out, needs nothing from
+/// us; any other failure of the cancel reverts the whole call rather than
+/// closing the game over locked deposits.
+fn cancel_escrow_msg(
+    querier: &QuerierWrapper,
+    config: &Config,
+    escrow_id: &str,
+) -> StdResult<Option<WasmMsg>> {
+    let Some(escrow_contract) = &config.escrow_contract else {
+        return Ok(None);
+    };
+    let Some(escrow) = query_escrow(querier, escrow_contract, escrow_id)? else {
+        return Ok(None);
+    };
+    if !OPEN_ESCROW_STATUSES.contains(&escrow.status.as_str()) {
+        return Ok(None);
+    }
+    Ok(Some(WasmMsg::Execute {
+        contract_addr: escrow_contract.to_string(),
+        msg: to_json_binary(&EscrowExecuteMsg::Cancel {
+            game_id: escrow_id.to_string(),
+        })?,
+        funds: vec![],
+    }))
+}
+
+/// The escrow's state for `escrow_id`, or None if it holds no such escrow
+fn query_escrow(
+    querier: &QuerierWrapper,
+    escrow_contract: &Addr,
+    escrow_id: &str,
+) -> StdResult<Option<EscrowQueryResponse>> {
+    querier.query_wasm_smart(
+        escrow_contract.to_string(),
+        &EscrowQueryMsg::FindEscrow {
+            game_id: escrow_id.to_string(),
+        },
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_report_result(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    game_id: String,
+    winner: String,
+    result_type_str: String,
+    move_count: u32,
+    dice_reveals: Option<Vec<DiceReveal>>,
+    move_log: Option<MoveLogCommitment>,
+    move_log_signatures: Option<MoveLogSignatures>,
+    cube: Option<CubeReport>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ROLES.ensure(deps.storage, &config.admin, &Role::Reporter, &info.sender)?;
+
+    let mut game = load_open_game(deps.storage, &game_id)?;
+    // Between co-signing players the server's word is not enough: both
+    // players vouch for the log the result is reported with
+    if co_signed(deps.storage, &game) && move_log_signatures.is_none() {
+        return Err(ContractError::CoSignatureRequired { game_id });
+    }
+
+    let winner_addr = deps.api.addr_validate(&winner)?;
+    if winner_addr != game.player_a && winner_addr != game.player_b {
+        return Err(ContractError::InvalidWinner {});
+    }
+    let result_type = parse_result_type(result_type_str)?;
+    let cube = parse_cube(deps.as_ref(), &game, &winner_addr, &result_type, cube)?;
+
+    store_dice_reveals(deps.storage, &game, dice_reveals)?;
+
+    if let Some(log) = &move_log {
+        validate_move_log(log)?;
+    }
+    // Signed by both players, the log can later settle a dispute
+    if let Some(signatures) = move_log_signatures {
+        let log = move_log.as_ref().ok_or(ContractError::InvalidMoveLog {
+            reason: "signatures given without a move log".to_string(),
+        })?;
+        let hash = move_log_signing_payload(&env, &game_id, log)?.1;
+        for (player, signature) in [
+            (&game.player_a, &signatures.signature_a),
+            (&game.player_b, &signatures.signature_b),
+        ] {
+            let key = load_signing_key(deps.storage, player)?;
+            verify_signature(deps.api, player, &key, &hash, signature)?;
+        }
+        game.move_log_signed = true;
+    }
+
+    game.move_count = move_count;
+    game.move_log = move_log;
+    game.cube = cube;
+
+    record_result(
+        deps.storage,
+        &env,
+        &config,
+        Response::new().add_attribute("action", "report_result"),
+        game,
+        winner_addr,
+        result_type,
+    )
+}
+
+/// Check and store the seeds revealed for a game with committed dice. A
+/// game without committed dice takes no reveals, since nothing binds them
+/// to the rolls that were played.
+fn store_dice_reveals(
+    storage: &mut dyn cosmwasm_std::Storage,
+    game: &Game,
+    dice_reveals: Option<Vec<DiceReveal>>,
+) -> Result<(), ContractError> {
+    let reveals = match (game.dice_committed, dice_reveals) {
+        (false, None) => return Ok(()),
+        (false, Some(_)) => {
+            return Err(ContractError::DiceNotCommitted {
+                game_id: game.game_id.clone(),
+            })
+        }
+        (true, None) => {
+            return Err(ContractError::DiceRevealRequired {
+                game_id: game.game_id.clone(),
+            })
+        }
+        (true, Some(reveals)) => reveals,
+    };
+    dice::verify_reveals(&reveals)?;
+    for reveal in &reveals {
+        DICE_REVEALS.save(storage, (&game.game_id, reveal.turn), reveal)?;
+    }
+    Ok(())
+}
+
+fn execute_register_signing_key(
+    deps: DepsMut,
+    info: MessageInfo,
+    pubkey: Binary,
+) -> Result<Response, ContractError> {
+    validate_pubkey(&pubkey)?;
+    SIGNING_KEYS.save(
+        deps.storage,
+        &info.sender,
+        &SigningKey {
+            pubkey: pubkey.clone(),
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_signing_key")
+        .add_attribute("player", info.sender)
+        .add_attribute("pubkey", pubkey.to_base64()))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_submit_signed_result(
+    deps: DepsMut,
+    env: Env,
+    game_id: String,
+    winner: String,
+    result_type_str: String,
+    nonce: u64,
+    cube: Option<CubeReport>,
+    dice_reveals: Option<Vec<DiceReveal>>,
+    signature_a: Binary,
+    signature_b: Binary,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut game = load_open_game(deps.storage, &game_id)?;
+
+    let winner_addr = deps.api.addr_validate(&winner)?;
+    if winner_addr != game.player_a && winner_addr != game.player_b {
+        return Err(ContractError::InvalidWinner {});
+    }
+
+    // Nonces are per game, so results for other games can be signed in any
+    // order
+    let last = RESULT_NONCES
+        .may_load(deps.storage, &game_id)?
+        .unwrap_or_default();
+    if nonce <= last {
+        return Err(ContractError::StaleNonce { nonce, last });
+    }
+
+    let hash = signing_payload(
+        &env,
+        &game_id,
+        &winner_addr,
+        &result_type_str,
+        nonce,
+        cube.clone(),
+    )?
+    .1;
+    for (player, signature) in [
+        (&game.player_a, &signature_a),
+        (&game.player_b, &signature_b),
+    ] {
+        let key = load_signing_key(deps.storage, player)?;
+        verify_signature(deps.api, player, &key, &hash, signature)?;
+    }
+    RESULT_NONCES.save(deps.storage, &game_id, &nonce)?;
+
+    let result_type = parse_result_type(result_type_str)?;
+    game.cube = parse_cube(deps.as_ref(), &game, &winner_addr, &result_type, cube)?;
+    store_dice_reveals(deps.storage, &game, dice_reveals)?;
+    finalize_result(
+        deps.storage,
+        &env,
+        &config,
+        Response::new()
+            .add_attribute("action", "submit_signed_result")
+            .add_attribute("nonce", nonce.to_string()),
+        game,
+        winner_addr,
+        result_type,
+    )
+}
+
+/// Whether both players registered signing keys, so the server cannot
+/// report their results on its own
+fn co_signed(storage: &dyn cosmwasm_std::Storage, game: &Game) -> bool {
+    SIGNING_KEYS.has(storage, &game.player_a) && SIGNING_KEYS.has(storage, &game.player_b)
+}
+
+/// Accepts a SEC1 compressed (33 bytes) or uncompressed (65 bytes)
+/// secp256k1 key
+fn validate_pubkey(pubkey: &Binary) -> Result<(), ContractError> {
+    let valid = match pubkey.len() {
+        33 => pubkey[0] == 0x02 || pubkey[0] == 0x03,
+        65 => pubkey[0] == 0x04,
+        _ => false,
+    };
+    if !valid {
+        return Err(ContractError::InvalidSigningKey {});
+    }
+    Ok(())
+}
+
+fn load_signing_key(
+    storage: &dyn cosmwasm_std::Storage,
+    player: &Addr,
+) -> Result<SigningKey, ContractError> {
+    SIGNING_KEYS
+        .may_load(storage, player)?
+        .ok_or(ContractError::SigningKeyNotRegistered {
+            address: player.to_string(),
+        })
+}
+
+/// Check `player`'s `signature` over `hash` against their registered key
+fn verify_signature(
+    api: &dyn cosmwasm_std::Api,
+    player: &Addr,
+    key: &SigningKey,
+    hash: &[u8],
+    signature: &[u8],
+) -> Result<(), ContractError> {
+    // A malformed signature is just as invalid as a wrong one
+    let verified = api
+        .secp256k1_verify(hash, signature, &key.pubkey)
+        .unwrap_or(false);
+    if !verified {
+        return Err(ContractError::InvalidSignature {
+            signer: player.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// The canonical result payload both players sign, and its sha256. Binding
+/// the chain and contract stops a signature being replayed on another deployment.
+fn signing_payload(
+    env: &Env,
+    game_id: &str,
+    winner: &Addr,
+    result_type: &str,
+    nonce: u64,
+    cube: Option<CubeReport>,
+) -> StdResult<(Vec<u8>, [u8; 32])> {
+    let payload = to_json_vec(&SignedResultPayload {
+        chain_id: env.block.chain_id.clone(),
+        contract: env.contract.address.to_string(),
+        game_id: game_id.to_string(),
+        winner: winner.to_string(),
+        result_type: result_type.to_string(),
+        nonce,
+        cube,
+    })?;
+    let hash = Sha256::digest(&payload).into();
+    Ok((payload, hash))
+}
+
+/// The canonical abandonment payload a winner signs, and its sha256
+fn abandonment_signing_payload(
+    env: &Env,
+    game_id: &str,
+    abandoner: &Addr,
+) -> StdResult<(Vec<u8>, [u8; 32])> {
+    let payload = to_json_vec(&SignedAbandonmentPayload {
+        chain_id: env.block.chain_id.clone(),
+        contract: env.contract.address.to_string(),
+        game_id: game_id.to_string(),
+        abandoner: abandoner.to_string(),
+    })?;
+    let hash = Sha256::digest(&payload).into();
+    Ok((payload, hash))
+}
+
+/// The canonical move log payload both players sign, and its sha256
+fn move_log_signing_payload(
+    env: &Env,
+    game_id: &str,
+    log: &MoveLogCommitment,
+) -> StdResult<(Vec<u8>, [u8; 32])> {
+    let payload = to_json_vec(&SignedMoveLogPayload {
+        chain_id: env.block.chain_id.clone(),
+        contract: env.contract.address.to_string(),
+        game_id: game_id.to_string(),
+        root: log.root.clone(),
+        leaf_count: log.leaf_count,
+    })?;
+    let hash = Sha256::digest(&payload).into();
+    Ok((payload, hash))
+}
+
+/// Loads a game that is still waiting for its result
+fn load_open_game(
+    storage: &dyn cosmwasm_std::Storage,
+    game_id: &str,
+) -> Result<Game, ContractError> {
+    let game = GAMES
+        .may_load(storage, game_id)?
+        .ok_or(ContractError::GameNotFound {
+            game_id: game_id.to_string(),
+        })?;
+
+    // Game must be Created or InProgress
+    if game.status != GameStatus::Created && game.status != GameStatus::InProgress {
+        return Err(ContractError::InvalidGameStatus {
+            expected: "Created or InProgress".to_string(),
+            got: format!("{:?}", game.status),
+        });
+    }
+    Ok(game)
+}
+
+fn parse_result_type(result_type: String) -> Result<ResultType, ContractError> {
+    match result_type.as_str() {
+        "normal" => Ok(ResultType::Normal),
+        "gammon" => Ok(ResultType::Gammon),
+        "backgammon" => Ok(ResultType::Backgammon),
+        _ => Err(ContractError::InvalidResultType { result_type }),
+    }
+}
+
+fn result_multiplier(result_type: &ResultType) -> u32 {
+    match result_type {
+        ResultType::Normal => 1,
+        ResultType::Gammon => 2,
+        ResultType::Backgammon => 3,
+    }
+}
+
+/// Points a result is worth: gammons and backgammons times the final cube
+fn result_points(result_type: &ResultType, cube: Option<&CubeResult>) -> u32 {
+    result_multiplier(result_type) * cube.map_or(1, |c| c.value)
+}
+
+/// Validate a reported cube against the game and its result. The number of
+/// doubles has to account for the cube value, plus the final double if it
+/// was passed.
+fn parse_cube(
+    deps: Deps,
+    game: &Game,
+    winner: &Addr,
+    result_type: &ResultType,
+    cube: Option<CubeReport>,
+) -> Result<Option<CubeResult>, ContractError> {
+    let Some(cube) = cube else {
+        return Ok(None);
+    };
+    let invalid = |reason: &str| ContractError::InvalidCube {
+        reason: reason.to_string(),
+    };
+
+    if !cube.value.is_power_of_two() || cube.value > MAX_CUBE_VALUE {
+        return Err(invalid("value must be a power of two up to 64"));
+    }
+    let owner = cube
+        .owner
+        .map(|owner| deps.api.addr_validate(&owner))
+        .transpose()?;
+    if owner
+        .as_ref()
+        .is_some_and(|o| *o != game.player_a && *o != game.player_b)
+    {
+        return Err(invalid("owner must be a player in the game"));
+    }
+    if owner.is_some() != (cube.value > 1) {
+        return Err(invalid(
+            "a turned cube must have an owner, a centred one none",
+        ));
+    }
+
+    let doubles = cube.value.trailing_zeros() + u32::from(cube.passed);
+    if cube.doubles_a.checked_add(cube.doubles_b) != Some(doubles) {
+        return Err(invalid("doubles offered do not add up to the cube value"));
+    }
+    if cube.passed {
+        let winner_doubles = if *winner == game.player_a {
+            cube.doubles_a
+        } else {
+            cube.doubles_b
+        };
+        if winner_doubles == 0 || *result_type != ResultType::Normal {
+            return Err(invalid(
+                "a passed double is a single game won by the doubler",
+            ));
+        }
+    }
+    if doubles > 0 {
+        if let Some(match_id) = &game.match_id {
+            if MATCHES.load(deps.storage, match_id)?.crawford {
+                return Err(invalid("the Crawford game is played without the cube"));
+            }
+        }
+    }
+
+    Ok(Some(CubeResult {
+        value: cube.value,
+        owner,
+        doubles_a: cube.doubles_a,
+        doubles_b: cube.doubles_b,
+        passed: cube.passed,
+    }))
+}
+
+/// Records a reported result: pending if a challenge period is configured,
+/// otherwise final straight away.
+fn record_result(
+    storage: &mut dyn cosmwasm_std::Storage,
+    env: &Env,
+    config: &Config,
+    response: Response,
+    mut game: Game,
+    winner_addr: Addr,
+    result_type: ResultType,
+) -> Result<Response, ContractError> {
+    if config.challenge_period_seconds == 0 {
+        return finalize_result(
+            storage,
+            env,
+            config,
+            response,
+            game,
+            winner_addr,
+            result_type,
+        );
+    }
+
+    let response = response
+        .add_attribute("game_id", &game.game_id)
+        .add_attribute("winner", winner_addr.to_string())
+        .add_attribute("result_type", format!("{:?}", result_type));
+
+    game.points_won = result_points(&result_type, game.cube.as_ref());
+    hold_result(
+        storage,
+        env,
+        config,
+        response,
+        game,
+        winner_addr,
+        result_type,
+    )
+}
+
+/// Holds a reported result as PendingResult until its challenge period,
+/// fixed now so later config changes don't move it, runs out
+fn hold_result(
+    storage: &mut dyn cosmwasm_std::Storage,
+    env: &Env,
+    config: &Config,
+    response: Response,
+    mut game: Game,
+    winner_addr: Addr,
+    result_type: ResultType,
+) -> Result<Response, ContractError> {
+    let now = env.block.time.seconds();
+    let deadline = now + config.challenge_period_seconds;
+
+    game.status = GameStatus::PendingResult;
+    game.winner = Some(winner_addr);
+    game.result_type = Some(result_type);
+    game.result_reported_at = Some(now);
+    game.challenge_deadline = Some(deadline);
+    GAMES.save(storage, &game.game_id, &game)?;
+
+    Ok(response
+        .add_attribute("pending", "true")
+        .add_attribute("challenge_deadline", deadline.to_string()))
+}
+
+fn execute_challenge_result(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    game_id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut game = load_pending_game(deps.storage, &game_id)?;
+
+    if info.sender != game.player_a && info.sender != game.player_b {
+        return Err(ContractError::Unauthorized {});
+    }
+    if game.challenged_by.is_some() {
+        return Err(ContractError::AlreadyChallenged { game_id });
+    }
+    let deadline = challenge_deadline(&config, &game);
+    if env.block.time.seconds() >= deadline {
+        return Err(ContractError::ChallengePeriodEnded { game_id, deadline });
+    }
+
+    game.challenged_by = Some(info.sender.clone());
+    GAMES.save(deps.storage, &game_id, &game)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "challenge_result")
+        .add_attribute("game_id", game_id)
+        .add_attribute("challenger", info.sender))
+}
+
+fn execute_resolve_challenge(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    game_id: String,
+    winner: String,
+    result_type_str: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ROLES.ensure(deps.storage, &config.admin, &Role::Arbiter, &info.sender)?;
+
+    let game = load_pending_game(deps.storage, &game_id)?;
+    if game.challenged_by.is_none() {
+        return Err(ContractError::NotChallenged { game_id });
+    }
+
+    let winner_addr = deps.api.addr_validate(&winner)?;
+    if winner_addr != game.player_a && winner_addr != game.player_b {
+        return Err(ContractError::InvalidWinner {});
+    }
+    let result_type = parse_result_type(result_type_str)?;
+
+    let response = Response::new().add_attribute("action", "resolve_challenge");
+    match game.abandoned_by.clone() {
+        // An upheld abandonment is booked as one
+        Some(abandoner) if winner_addr != abandoner => finalize_abandonment(
+            deps.storage,
+            &env,
+            &config,
+            response,
+            game,
+            winner_addr,
+            abandoner,
+        ),
+        _ => {
+            let mut game = game;
+            game.abandoned_by = None;
+            finalize_result(
+                deps.storage,
+                &env,
+                &config,
+                response,
+                game,
+                winner_addr,
+                result_type,
+            )
+        }
+    }
+}
+
+fn execute_finalize_result(
+    deps: DepsMut,
+    env: Env,
+    game_id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let game = load_pending_game(deps.storage, &game_id)?;
+
+    if game.challenged_by.is_some() {
+        return Err(ContractError::AlreadyChallenged { game_id });
+    }
+    let deadline = challenge_deadline(&config, &game);
+    if env.block.time.seconds() < deadline {
+        return Err(ContractError::ChallengePeriodActive { game_id, deadline });
+    }
+
+    // A pending game always carries the reported result
+    let winner = game.winner.clone().ok_or(ContractError::InvalidWinner {})?;
+    let result_type = game.result_type.clone().unwrap_or(ResultType::Normal);
+    let response = Response::new().add_attribute("action", "finalize_result");
+    match game.abandoned_by.clone() {
+        Some(abandoner) => finalize_abandonment(
+            deps.storage,
+            &env,
+            &config,
+            response,
+            game,
+            winner,
+            abandoner,
+        ),
+        None => finalize_result(
+            deps.storage,
+            &env,
+            &config,
+            response,
+            game,
+            winner,
+            result_type,
+        ),
+    }
+}
+
+fn load_pending_game(
+    storage: &dyn cosmwasm_std::Storage,
+    game_id: &str,
+) -> Result<Game, ContractError> {
+    let game = GAMES
+        .may_load(storage, game_id)?
+        .ok_or(ContractError::GameNotFound {
+            game_id: game_id.to_string(),
+        })?;
+    if game.status != GameStatus::PendingResult {
+        return Err(ContractError::InvalidGameStatus {
+            expected: "PendingResult".to_string(),
+            got: format!("{:?}", game.status),
+        });
+    }
+    Ok(game)
+}
+
+/// End of the challenge period. Results held before the deadline was stored
+/// on the game fall back to the current config.
+fn challenge_deadline(config: &Config, game: &Game) -> u64 {
+    game.challenge_deadline
+        .unwrap_or_else(|| game.result_reported_at.unwrap_or(0) + config.challenge_period_seconds)
+}
+
+/// Makes a result final, then settles it (standalone games) or scores it
+/// into its match.
+fn finalize_result(
+    storage: &mut dyn cosmwasm_std::Storage,
+    env: &Env,
+    config: &Config,
+    response: Response,
+    mut game: Game,
+    winner_addr: Addr,
+    result_type: ResultType,
+) -> Result<Response, ContractError> {
+    // Determine multiplier for wager settlement
+    let multiplier = result_multiplier(&result_type);
+    game.points_won = result_points(&result_type, game.cube.as_ref());
+
+    let response = response
+        .add_attribute("game_id", &game.game_id)
+        .add_attribute("winner", winner_addr.to_string())
+        .add_attribute("result_type", format!("{:?}", result_type))
+        .add_attribute("points_won", game.points_won.to_string());
+
+    // Update game
+    game.status = GameStatus::Completed;
+    game.winner = Some(winner_addr.clone());
+    game.result_type = Some(result_type.clone());
+    game.completed_at = Some(env.block.time.seconds());
+    GAMES.save(storage, &game.game_id, &game)?;
+
+    // Determine loser
+    let loser_addr = if winner_addr == game.player_a {
+        game.player_b.clone()
+    } else {
+        game.player_a.clone()
+    };
+
+    // NOTE: Stats are saved before the escrow settlement message is dispatched.
+    // This is safe because CosmWasm executes all messages atomically — if the
+    // escrow settlement fails, the entire transaction (including stat updates) reverts.
+    let now = env.block.time.seconds();
+    record_game(storage, now, &winner_addr, &loser_addr)?;
+    record_score(storage, &game, &winner_addr, &loser_addr)?;
+    record_head_to_head(
+        storage,
+        now,
+        &winner_addr,
+        &loser_addr,
+        result_type != ResultType::Normal,
+    )?;
+    record_platform_stats(storage, now, |stats| {
+        *result_count(stats, &result_type) += 1
+    })?;
+
+    let response = match &game.match_id {
+        // A standalone game is settled and rated as a 1-point match
+        None => settle_match(
+            storage,
+            now,
+            config,
+            response,
+            &game.game_id,
+            &winner_addr,
+            &loser_addr,
+            1,
+            game.wager_amount,
+            multiplier,
+        )?,
+        Some(match_id) => score_match_game(
+            storage,
+            env,
+            config,
+            response,
+            match_id,
+            &winner_addr,
+            &loser_addr,
+            game.points_won,
+        )?,
+    };
+
+    award_achievements(
+        storage,
+        env,
+        config,
+        response,
+        &game.game_id,
+        [&winner_addr, &loser_addr],
+    )
+}
+
+/// Scores a finished game into its match. Settles the match once a player
+/// reaches the match length; otherwise opens the next game of the match.
+#[allow(clippy::too_many_arguments)]
+fn score_match_game(
+    storage: &mut dyn cosmwasm_std::Storage,
+    env: &Env,
+    config: &Config,
+    response: Response,
+    match_id: &str,
+    winner: &Addr,
+    loser: &Addr,
+    points: u32,
+) -> Result<Response, ContractError> {
+    let mut bg_match = MATCHES.load(storage, match_id)?;
+
+    if *winner == bg_match.player_a {
+        bg_match.score_a += points;
+    } else {
+        bg_match.score_b += points;
+    }
+
+    let response = response
+        .add_attribute("match_id", match_id)
+        .add_attribute("score_a", bg_match.score_a.to_string())
+        .add_attribute("score_b", bg_match.score_b.to_string());
+
+    if bg_match.score_a >= bg_match.match_length || bg_match.score_b >= bg_match.match_length {
+        bg_match.status = MatchStatus::Completed;
+        bg_match.winner = Some(winner.clone());
+        bg_match.crawford = false;
+        bg_match.completed_at = Some(env.block.time.seconds());
+        MATCHES.save(storage, match_id, &bg_match)?;
+
+        return settle_match(
+            storage,
+            env.block.time.seconds(),
+            config,
+            response.add_attribute("match_complete", "true"),
+            match_id,
+            winner,
+            loser,
+            bg_match.match_length,
+            bg_match.wager_amount,
+            1,
+        );
+    }
+
+    // The game after a player first reaches match point is the Crawford
+    // game, played without the cube. It happens at most once per match.
+    if bg_match.crawford {
+        bg_match.crawford = false;
+        bg_match.crawford_played = true;
+    } else if !bg_match.crawford_played {
+        let match_point = bg_match.match_length - 1;
+        bg_match.crawford = (bg_match.score_a == match_point) != (bg_match.score_b == match_point);
+    }
+
+    let next_game_id = format!("{}-{}", match_id, bg_match.game_ids.len() + 1);
+    bg_match.game_ids.push(next_game_id.clone());
+    MATCHES.save(storage, match_id, &bg_match)?;
+
+    // Deposits were made for the whole match, so later games start
+    // immediately. Their dice can still be declared committed with StartGame.
+    let mut next_game = new_game(
+        storage,
+        env,
+        next_game_id.clone(),
+        bg_match.player_a.clone(),
+        bg_match.player_b.clone(),
+        0,
+        Some(match_id.to_string()),
+    )?;
+    next_game.status = GameStatus::InProgress;
+    GAMES.save(storage, &next_game_id, &next_game)?;
+
+    Ok(response
+        .add_attribute("next_game_id", next_game_id)
+        .add_attribute("crawford", bg_match.crawford.to_string()))
+}
+
+/// Count a finished game in both players' stats
+fn record_game(
+    storage: &mut dyn cosmwasm_std::Storage,
+    now: u64,
+    winner: &Addr,
+    loser: &Addr,
+) -> Result<(), ContractError> {
+    let in_season = ACTIVE_SEASON.exists(storage);
+
+    let mut winner_stats = PLAYER_STATS.load(storage, winner)?;
+    winner_stats.games_played += 1;
+    winner_stats.games_won += 1;
+    winner_stats.record_result(true, now);
+    if in_season {
+        winner_stats.season_games_played += 1;
+        winner_stats.season_games_won += 1;
+    }
+    PLAYER_STATS.save(storage, winner, &winner_stats)?;
+
+    let mut loser_stats = PLAYER_STATS.load(storage, loser)?;
+    loser_stats.games_played += 1;
+    loser_stats.record_result(false, now);
+    if in_season {
+        loser_stats.season_games_played += 1;
+    }
+    PLAYER_STATS.save(storage, loser, &loser_stats)?;
+
+    for stats in [&winner_stats, &loser_stats] {
+        record_active_player(storage, now, stats)?;
+    }
+    Ok(())
+}
+
+/// Count a player who just finished a game among the platform's unique
+/// players, and among the day's if this is their first game that day
+fn record_active_player(
+    storage: &mut dyn cosmwasm_std::Storage,
+    now: u64,
+    stats: &PlayerStats,
+) -> StdResult<()> {
+    if stats.games_played == 1 {
+        let mut totals = PLATFORM_STATS.may_load(storage)?.unwrap_or_default();
+        totals.unique_players += 1;
+        PLATFORM_STATS.save(storage, &totals)?;
+    }
+
+    let day = now / SECONDS_PER_DAY;
+    if !DAILY_PLAYERS.has(storage, (day, &stats.address)) {
+        DAILY_PLAYERS.save(storage, (day, &stats.address), &Empty {})?;
+        let mut daily = DAILY_STATS.may_load(storage, day)?.unwrap_or_default();
+        daily.unique_players += 1;
+        DAILY_STATS.save(storage, day, &daily)?;
+    }
+    Ok(())
+}
+
+/// Apply `update` to the all-time platform stats and to the bucket for the
+/// UTC day containing `at`
+fn record_platform_stats(
+    storage: &mut dyn cosmwasm_std::Storage,
+    at: u64,
+    update: impl Fn(&mut PlatformStats),
+) -> StdResult<()> {
+    let mut totals = PLATFORM_STATS.may_load(storage)?.unwrap_or_default();
+    update(&mut totals);
+    PLATFORM_STATS.save(storage, &totals)?;
+
+    let day = at / SECONDS_PER_DAY;
+    let mut daily = DAILY_STATS.may_load(storage, day)?.unwrap_or_default();
+    update(&mut daily);
+    DAILY_STATS.save(storage, day, &daily)
+}
+
+/// The platform counter for games won with `result_type`
+fn result_count<'a>(stats: &'a mut PlatformStats, result_type: &ResultType) -> &'a mut u64 {
+    match result_type {
+        ResultType::Normal => &mut stats.normal_wins,
+        ResultType::Gammon => &mut stats.gammons,
+        ResultType::Backgammon => &mut stats.backgammons,
+    }
+}
+
+/// Apply `update` to the record between two players, in either order
+fn update_head_to_head(
+    storage: &mut dyn cosmwasm_std::Storage,
+    a: &Addr,
+    b: &Addr,
+    update: impl FnOnce(&mut HeadToHead),
+) -> StdResult<()> {
+    let (low, high) = if a <= b { (a, b) } else { (b, a) };
+    let mut record = HEAD_TO_HEAD
+        .may_load(storage, (low, high))?
+        .unwrap_or_else(|| HeadToHead::new(low.clone(), high.clone()));
+    update(&mut record);
+    HEAD_TO_HEAD.save(storage, (low, high), &record)
+}
+
+/// Count a finished game in the record between its winner and loser
+fn record_head_to_head(
+    storage: &mut dyn cosmwasm_std::Storage,
+    now: u64,
+    winner: &Addr,
+    loser: &Addr,
+    gammon: bool,
+) -> StdResult<()> {
+    update_head_to_head(storage, winner, loser, |record| {
+        record.games += 1;
+        record.last_played = now;
+        let (wins, gammons) = record.side_mut(winner);
+        *wins += 1;
+        if gammon {
+            *gammons += 1;
+        }
+    })
+}
+
+/// Record any achievements the players have newly reached with this game's
+/// result, minting a badge for each if an achievement NFT contract is set.
+/// A mint the NFT contract rejects leaves the achievement without a badge.
+fn award_achievements(
+    storage: &mut dyn cosmwasm_std::Storage,
+    env: &Env,
+    config: &Config,
+    mut response: Response,
+    game_id: &str,
+    players: [&Addr; 2],
+) -> Result<Response, ContractError> {
+    for player in players {
+        let stats = PLAYER_STATS.load(storage, player)?;
+        for achievement in &achievements::ALL {
+            let slug = achievements::slug(achievement);
+            if ACHIEVEMENTS.has(storage, (player, slug))
+                || !achievements::is_met(achievement, &stats)
+            {
+                continue;
+            }
+
+            let token_id = match &config.achievement_nft_contract {
+                Some(nft_contract) => {
+                    let token_id = achievements::token_id(achievement, player);
+                    response = response.add_submessage(
+                        SubMsg::reply_on_error(
+                            achievements::mint_msg(nft_contract, &token_id, player)?,
+                            MINT_BADGE_REPLY_ID,
+                        )
+                        .with_payload(to_json_vec(&(player, slug))?),
+                    );
+                    Some(token_id)
+                }
+                None => None,
+            };
+            ACHIEVEMENTS.save(
+                storage,
+                (player, slug),
+                &EarnedAchievement {
+                    achievement: achievement.clone(),
+                    earned_at: env.block.time.seconds(),
+                    game_id: game_id.to_string(),
+                    token_id,
+                },
+            )?;
+            response = response.add_attribute("achievement", format!("{slug}:{player}"));
+        }
+    }
+    Ok(response)
+}
+
+/// Revoke the achievements this game's result earned that the players no
+/// longer meet now a dispute has overturned it, burning their badges
+fn revoke_achievements(
+    storage: &mut dyn cosmwasm_std::Storage,
+    config: &Config,
+    mut response: Response,
+    game_id: &str,
+    players: [&Addr; 2],
+) -> Result<Response, ContractError> {
+    for player in players {
+        let stats = PLAYER_STATS.load(storage, player)?;
+        for achievement in &achievements::ALL {
+            let slug = achievements::slug(achievement);
+            let Some(earned) = ACHIEVEMENTS.may_load(storage, (player, slug))? else {
+                continue;
+            };
+            if earned.game_id != game_id || achievements::is_met(achievement, &stats) {
+                continue;
+            }
+
+            ACHIEVEMENTS.remove(storage, (player, slug));
+            if let (Some(nft_contract), Some(token_id)) =
+                (&config.achievement_nft_contract, &earned.token_id)
+            {
+                response = response.add_submessage(SubMsg::reply_on_error(
+                    achievements::burn_msg(nft_contract, token_id)?,
+                    BURN_BADGE_REPLY_ID,
+                ));
+            }
+            response = response.add_attribute("revoked_achievement", format!("{slug}:{player}"));
+        }
+    }
+    Ok(response)
+}
+
+/// Book a scored game's gammons, points and cube decisions in both players'
+/// stats. Abandoned games are not scored.
+fn record_score(
+    storage: &mut dyn cosmwasm_std::Storage,
+    game: &Game,
+    winner: &Addr,
+    loser: &Addr,
+) -> Result<(), ContractError> {
+    let mut winner_stats = PLAYER_STATS.load(storage, winner)?;
+    let mut loser_stats = PLAYER_STATS.load(storage, loser)?;
+
+    let result_type = game.result_type.clone().unwrap_or(ResultType::Normal);
+    book_score(
+        &mut winner_stats,
+        &mut loser_stats,
+        &result_type,
+        game.points_won,
+        false,
+    );
+
+    if let Some(cube) = &game.cube {
+        book_cube(
+            &mut winner_stats,
+            &mut loser_stats,
+            cube,
+            *winner == game.player_a,
+            false,
+        );
+
+        let passed = u32::from(cube.passed);
+        let offered = u64::from(cube.doubles_a + cube.doubles_b);
+        let passed = u64::from(passed);
+        record_platform_stats(storage, game.completed_at.unwrap_or_default(), |stats| {
+            stats.doubles_offered += offered;
+            stats.doubles_taken += offered - passed;
+            stats.doubles_passed += passed;
+        })?;
+    }
+
+    PLAYER_STATS.save(storage, winner, &winner_stats)?;
+    PLAYER_STATS.save(storage, loser, &loser_stats)?;
+    Ok(())
+}
+
+/// Add a game's cube decisions to the winner's and loser's stats, or take
+/// them back out with `undo`. Each double is offered by one player and taken
+/// or passed by the other; only the winner's last double can have been
+/// passed.
+fn book_cube(
+    winner: &mut PlayerStats,
+    loser: &mut PlayerStats,
+    cube: &CubeResult,
+    winner_is_a: bool,
+    undo: bool,
+) {
+    let (winner_doubles, loser_doubles) = if winner_is_a {
+        (cube.doubles_a, cube.doubles_b)
+    } else {
+        (cube.doubles_b, cube.doubles_a)
+    };
+    let passed = u32::from(cube.passed);
+    for (count, n) in [
+        (&mut winner.doubles_offered, winner_doubles),
+        (&mut winner.doubles_taken, loser_doubles),
+        (&mut loser.doubles_offered, loser_doubles),
+        (&mut loser.doubles_taken, winner_doubles - passed),
+        (&mut loser.doubles_passed, passed),
+    ] {
+        *count = if undo {
+            count.saturating_sub(n)
+        } else {
+            *count + n
+        };
+    }
+}
+
+/// Add a result's gammons and points to the winner's and loser's stats, or
+/// take them back out with `undo` when a dispute overturns the result
+fn book_score(
+    winner: &mut PlayerStats,
+    loser: &mut PlayerStats,
+    result_type: &ResultType,
+    points: u32,
+    undo: bool,
+) {
+    let apply = |count: &mut u32| {
+        *count = if undo {
+            count.saturating_sub(1)
+        } else {
+            *count + 1
+        }
+    };
+    match result_type {
+        ResultType::Normal => {}
+        ResultType::Gammon => {
+            apply(&mut winner.gammons_won);
+            apply(&mut loser.gammons_lost);
+        }
+        ResultType::Backgammon => {
+            apply(&mut winner.backgammons_won);
+            apply(&mut loser.backgammons_lost);
+        }
+    }
+
+    let points = u64::from(points);
+    if undo {
+        winner.points_won = winner.points_won.saturating_sub(points);
+        loser.points_lost = loser.points_lost.saturating_sub(points);
+    } else {
+        winner.points_won += points;
+        loser.points_lost += points;
+    }
+}
+
+/// Finish a match: apply the rating update, book the wager in both players'
+/// stats and settle the escrow held under `escrow_id`.
+#[allow(clippy::too_many_arguments)]
+fn settle_match(
+    storage: &mut dyn cosmwasm_std::Storage,
+    now: u64,
+    config: &Config,
+    response: Response,
+    escrow_id: &str,
+    winner: &Addr,
+    loser: &Addr,
+    match_length: u32,
+    wager_amount: u128,
+    multiplier: u32,
+) -> Result<Response, ContractError> {
+    let mut winner_stats = PLAYER_STATS.load(storage, winner)?;
+    let mut loser_stats = PLAYER_STATS.load(storage, loser)?;
+    let loser_rating_before = loser_stats.rating;
+
+    let (rating_gain, rating_loss) = rating::apply_match_result(
+        &config.rating_params,
+        &mut winner_stats,
+        &mut loser_stats,
+        match_length,
+    )?;
+    let peak = winner_stats.record_peak_rating(now);
+    // The loser's floor may have cut the loss short, so book what was applied
+    SETTLEMENTS.save(
+        storage,
+        escrow_id,
+        &Settlement {
+            winner: winner.clone(),
+            loser: loser.clone(),
+            match_length,
+            wager_amount,
+            rating_gain,
+            rating_loss: loser_rating_before - loser_stats.rating,
+            payout: 0,
+            rake: 0,
+            cube_value: 0,
+            winner_stake: 0,
+            loser_stake: 0,
+            owed: 0,
+            peak,
+        },
+    )?;
+
+    // What was actually won is booked from the escrow's reply
+    winner_stats.total_wagered += wager_amount;
+    PLAYER_STATS.save(storage, winner, &winner_stats)?;
+
+    loser_stats.total_wagered += wager_amount;
+    PLAYER_STATS.save(storage, loser, &loser_stats)?;
+
+    if wager_amount > 0 {
+        record_platform_stats(storage, now, |stats| stats.wager_volume += wager_amount * 2)?;
+        update_head_to_head(storage, winner, loser, |record| {
+            record.total_wagered += wager_amount * 2
+        })?;
+    }
+
+    let mut response = response
+        .add_attribute("rating_gain", rating_gain.to_string())
+        .add_attribute("rating_loss", rating_loss.to_string());
+
+    // Settle escrow if configured
+    if let Some(escrow_contract) = &config.escrow_contract {
+        if wager_amount > 0 {
+            let settle_msg = if multiplier > 1 {
+                EscrowExecuteMsg::SettleWithMultiplier {
+                    game_id: escrow_id.to_string(),
+                    winner: winner.to_string(),
+                    multiplier,
+                }
+            } else {
+                EscrowExecuteMsg::Settle {
+                    game_id: escrow_id.to_string(),
+                    winner: winner.to_string(),
+                }
+            };
+            let msg = WasmMsg::Execute {
+                contract_addr: escrow_contract.to_string(),
+                msg: to_json_binary(&settle_msg)?,
+                funds: vec![],
+            };
+            response = response.add_submessage(
+                SubMsg::reply_on_success(msg, SETTLE_ESCROW_REPLY_ID)
+                    .with_payload(escrow_id.as_bytes().to_vec()),
+            );
+        }
+    }
+
+    Ok(response)
+}
+
+fn execute_report_abandonment(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    game_id: String,
+    abandoner: String,
+    dice_reveals: Option<Vec<DiceReveal>>,
+    winner_signature: Option<Binary>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ROLES.ensure(deps.storage, &config.admin, &Role::Reporter, &info.sender)?;
+
+    let mut game = GAMES
+        .may_load(deps.storage, &game_id)?
+        .ok_or(ContractError::GameNotFound {
+            game_id: game_id.clone(),
+        })?;
+
+    if game.status != GameStatus::Created && game.status != GameStatus::InProgress {
+        return Err(ContractError::InvalidGameStatus {
+            expected: "Created or InProgress".to_string(),
+            got: format!("{:?}", game.status),
+        });
+    }
+
+    let abandoner_addr = deps.api.addr_validate(&abandoner)?;
+    if abandoner_addr != game.player_a && abandoner_addr != game.player_b {
+        return Err(ContractError::InvalidWinner {}); // reuse error - abandoner must be a player
+    }
+    store_dice_reveals(deps.storage, &game, dice_reveals)?;
+
+    // The non-abandoner wins
+    let winner_addr = if abandoner_addr == game.player_a {
+        game.player_b.clone()
+    } else {
+        game.player_a.clone()
+    };
+
+    // Between co-signing players the server's word is not enough: the
+    // winner confirms the abandonment
+    if co_signed(deps.storage, &game) {
+        let signature = winner_signature.ok_or(ContractError::CoSignatureRequired {
+            game_id: game_id.clone(),
+        })?;
+        let hash = abandonment_signing_payload(&env, &game_id, &abandoner_addr)?.1;
+        let key = load_signing_key(deps.storage, &winner_addr)?;
+        verify_signature(deps.api, &winner_addr, &key, &hash, &signature)?;
+    }
+
+    let response = Response::new().add_attribute("action", "report_abandonment");
+
+    // Like any other result, an abandonment can be challenged before it counts
+    if config.challenge_period_seconds > 0 {
+        let response = response
+            .add_attribute("game_id", &game_id)
+            .add_attribute("abandoner", abandoner_addr.to_string())
+            .add_attribute("winner", winner_addr.to_string());
+        game.abandoned_by = Some(abandoner_addr);
+        return hold_result(
+            deps.storage,
+            &env,
+            &config,
+            response,
+            game,
+            winner_addr,
+            ResultType::Normal,
+        );
+    }
+
+    finalize_abandonment(
+        deps.storage,
+        &env,
+        &config,
+        response,
+        game,
+        winner_addr,
+        abandoner_addr,
+    )
+}
+
+/// Books an abandonment: the abandoner forfeits the game and, for a match
+/// game, the whole match.
+fn finalize_abandonment(
+    storage: &mut dyn cosmwasm_std::Storage,
+    env: &Env,
+    config: &Config,
+    response: Response,
+    mut game: Game,
+    winner_addr: Addr,
+    abandoner_addr: Addr,
+) -> Result<Response, ContractError> {
+    let game_id = game.game_id.clone();
+    let now = env.block.time.seconds();
+
+    game.status = GameStatus::Abandoned;
+    game.winner = Some(winner_addr.clone());
+    game.result_type = Some(ResultType::Normal);
+    game.abandoned_by = None;
+    game.completed_at = Some(now);
+    GAMES.save(storage, &game_id, &game)?;
+
+    record_game(storage, now, &winner_addr, &abandoner_addr)?;
+    PLAYER_STATS.update(storage, &abandoner_addr, |stats| -> StdResult<_> {
+        let mut stats = stats.ok_or_else(|| StdError::not_found("PlayerStats"))?;
+        stats.abandonments += 1;
+        Ok(stats)
+    })?;
+    record_head_to_head(storage, now, &winner_addr, &abandoner_addr, false)?;
+    record_platform_stats(storage, now, |stats| stats.abandonments += 1)?;
+
+    let response = response
+        .add_attribute("game_id", &game_id)
+        .add_attribute("abandoner", abandoner_addr.to_string())
+        .add_attribute("winner", winner_addr.to_string());
+
+    // Abandoning a game forfeits the whole match, so the abandoner is rated
+    // as having lost it and the winner gets the pot
+    let (response, escrow_id, match_length, wager_amount) =
+        forfeit_match(storage, now, response, &game, &winner_addr)?;
+
+    let response = settle_match(
+        storage,
+        now,
+        config,
+        response,
+        &escrow_id,
+        &winner_addr,
+        &abandoner_addr,
+        match_length,
+        wager_amount,
+        1,
+    )?;
+
+    award_achievements(
+        storage,
+        env,
+        config,
+        response,
+        &game_id,
+        [&winner_addr, &abandoner_addr],
+    )
+}
+
+/// Ends the match a forfeited game belongs to, won by `winner`. Returns the
+/// escrow ID, match length and wager the forfeit is settled at, which are
+/// the game's own for a standalone game.
+fn forfeit_match(
+    storage: &mut dyn cosmwasm_std::Storage,
+    now: u64,
+    mut response: Response,
+    game: &Game,
+    winner: &Addr,
+) -> StdResult<(Response, String, u32, u128)> {
+    let Some(match_id) = &game.match_id else {
+        return Ok((response, game.game_id.clone(), 1, game.wager_amount));
+    };
+    let mut bg_match = MATCHES.load(storage, match_id)?;
+    bg_match.status = MatchStatus::Completed;
+    bg_match.winner = Some(winner.clone());
+    bg_match.crawford = false;
+    bg_match.completed_at = Some(now);
+    MATCHES.save(storage, match_id, &bg_match)?;
+    response = response
+        .add_attribute("match_id", match_id)
+        .add_attribute("match_complete", "true");
+    Ok((
+        response,
+        match_id.clone(),
+        bg_match.match_length,
+        bg_match.wager_amount,
+    ))
+}
+
+/// Books a game whose escrow was forfeited on a passed double: the doubler
+/// wins it, worth the cube the double was passed at, and with it the match,
+/// since the escrow has paid out the whole pot.
+#[allow(clippy::too_many_arguments)]
+fn finalize_forfeit(
+    storage: &mut dyn cosmwasm_std::Storage,
+    env: &Env,
+    config: &Config,
+    response: Response,
+    mut game: Game,
+    winner_addr: Addr,
+    loser_addr: Addr,
+    cube_value: u32,
+) -> Result<Response, ContractError> {
+    let game_id = game.game_id.clone();
+    let now = env.block.time.seconds();
+
+    game.status = GameStatus::Completed;
+    game.winner = Some(winner_addr.clone());
+    game.result_type = Some(ResultType::Normal);
+    game.points_won = cube_value.max(1);
+    game.abandoned_by = None;
+    game.completed_at = Some(now);
+    GAMES.save(storage, &game_id, &game)?;
+
+    record_game(storage, now, &winner_addr, &loser_addr)?;
+    record_score(storage, &game, &winner_addr, &loser_addr)?;
+    record_head_to_head(storage, now, &winner_addr, &loser_addr, false)?;
+    record_platform_stats(storage, now, |stats| {
+        *result_count(stats, &ResultType::Normal) += 1
+    })?;
+
+    let response = response
+        .add_attribute("game_id", &game_id)
+        .add_attribute("winner", winner_addr.to_string())
+        .add_attribute("points_won", game.points_won.to_string());
+    let (response, escrow_id, match_length, wager_amount) =
+        forfeit_match(storage, now, response, &game, &winner_addr)?;
+
+    // The escrow has already paid out, so there is nothing to settle there
+    let settled_config = Config {
+        escrow_contract: None,
+        ..config.clone()
+    };
+    let response = settle_match(
+        storage,
+        now,
+        &settled_config,
+        response,
+        &escrow_id,
+        &winner_addr,
+        &loser_addr,
+        match_length,
+        wager_amount,
+        1,
+    )?;
+
+    award_achievements(
+        storage,
+        env,
+        config,
+        response,
+        &game_id,
+        [&winner_addr, &loser_addr],
+    )
+}
+
+fn execute_dispute_result(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    game_id: String,
+    turns: Vec<LoggedTurn>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut game = GAMES
+        .may_load(deps.storage, &game_id)?
+        .ok_or(ContractError::GameNotFound {
+            game_id: game_id.clone(),
+        })?;
+
+    if info.sender != game.player_a && info.sender != game.player_b {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let not_allowed = |reason: &str| ContractError::DisputeNotAllowed {
+        game_id: game_id.clone(),
+        reason: reason.to_string(),
+    };
+    if game.status != GameStatus::Completed {
+        return Err(not_allowed("only completed games can be disputed"));
+    }
+    // Past the deadline the result, and the escrow's payout, are final
+    if config.dispute_period_seconds > 0
+        && env.block.time.seconds()
+            > game.completed_at.unwrap_or_default() + config.dispute_period_seconds
+    {
+        return Err(not_allowed("the dispute period has ended"));
+    }
+    if game.match_id.is_some() {
+        return Err(not_allowed("match games cannot be disputed"));
+    }
+    if DISPUTES.has(deps.storage, &game_id) {
+        return Err(not_allowed("a dispute was already filed"));
+    }
+    let log = game
+        .move_log
+        .clone()
+        .ok_or_else(|| not_allowed("no move log was committed"))?;
+    // The server alone could commit to any log; the players must have
+    // vouched for this one
+    if !game.move_log_signed {
+        return Err(not_allowed("the move log was not signed by both players"));
+    }
+    let reported_winner = game
+        .winner
+        .clone()
+        .ok_or_else(|| not_allowed("no winner was reported"))?;
+    let reported_result_type = game.result_type.clone().unwrap_or(ResultType::Normal);
+
+    // The submitted turns must be exactly the log the server committed to
+    let leaves = turns
+        .iter()
+        .map(to_json_vec)
+        .collect::<StdResult<Vec<_>>>()?;
+    let root = merkle::root(&leaves);
+    if leaves.len() != log.leaf_count as usize
+        || root.as_ref().map(|r| r.as_slice()) != Some(log.root.as_slice())
+    {
+        return Err(ContractError::InvalidMoveLog {
+            reason: "turns do not match the committed root".to_string(),
+        });
+    }
+
+    // and, if the dice were committed, use the revealed rolls
+    if game.dice_committed {
+        for turn in &turns {
+            let rolled = DICE_REVEALS
+                .may_load(deps.storage, (&game_id, turn.turn))?
+                .map(|r| dice::derive_dice(&r.server_seed, &r.client_seed, turn.turn));
+            if rolled != Some(turn.dice) {
+                return Err(ContractError::InvalidMoveLog {
+                    reason: format!(
+                        "dice for turn {} do not match the revealed seeds",
+                        turn.turn
+                    ),
+                });
+            }
+        }
+    }
+
+    let verdict =
+        rules::replay(&turns).map_err(|reason| ContractError::InvalidMoveLog { reason })?;
+    let (winner, loser) = match verdict.winner {
+        rules::Player::White => (game.player_a.clone(), game.player_b.clone()),
+        rules::Player::Black => (game.player_b.clone(), game.player_a.clone()),
+    };
+    let overturned = winner != reported_winner || verdict.result_type != reported_result_type;
+
+    let (illegal_turn, reason) = verdict.illegal_turn.unzip();
+    DISPUTES.save(
+        deps.storage,
+        &game_id,
+        &Dispute {
+            game_id: game_id.clone(),
+            disputer: info.sender.clone(),
+            reported_winner: reported_winner.clone(),
+            reported_result_type: reported_result_type.clone(),
+            winner: winner.clone(),
+            result_type: verdict.result_type.clone(),
+            overturned,
+            illegal_turn,
+            reason,
+            filed_at: env.block.time.seconds(),
+        },
+    )?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "dispute_result")
+        .add_attribute("game_id", &game_id)
+        .add_attribute("winner", winner.to_string())
+        .add_attribute("result_type", format!("{:?}", verdict.result_type))
+        .add_attribute("overturned", overturned.to_string());
+
+    if !overturned {
+        return Ok(response);
+    }
+
+    // The replay decided the game by play, so a double the report claims was
+    // passed did not end it: the pass is voided along with the double
+    let reported_cube = game.cube.clone();
+    let voided_pass = match game.cube.as_mut() {
+        Some(cube) if cube.passed => {
+            cube.passed = false;
+            let doubles = if reported_winner == game.player_a {
+                &mut cube.doubles_a
+            } else {
+                &mut cube.doubles_b
+            };
+            *doubles = doubles.saturating_sub(1);
+            true
+        }
+        _ => false,
+    };
+
+    let reported_points = game.points_won;
+    game.winner = Some(winner.clone());
+    game.points_won = result_points(&verdict.result_type, game.cube.as_ref());
+    game.result_type = Some(verdict.result_type.clone());
+    GAMES.save(deps.storage, &game_id, &game)?;
+
+    // Move the game to its replayed result type in the day it completed
+    if verdict.result_type != reported_result_type || voided_pass {
+        record_platform_stats(
+            deps.storage,
+            game.completed_at.unwrap_or_default(),
+            |stats| {
+                let reported = result_count(stats, &reported_result_type);
+                *reported = reported.saturating_sub(1);
+                *result_count(stats, &verdict.result_type) += 1;
+                if voided_pass {
+                    stats.doubles_offered = stats.doubles_offered.saturating_sub(1);
+                    stats.doubles_passed = stats.doubles_passed.saturating_sub(1);
+                }
+            },
+        )?;
+    }
+
+    // Move the game's win and gammon over to the replayed result
+    update_head_to_head(deps.storage, &winner, &loser, |record| {
+        let (wins, gammons) = record.side_mut(&reported_winner);
+        *wins = wins.saturating_sub(1);
+        if reported_result_type != ResultType::Normal {
+            *gammons = gammons.saturating_sub(1);
+        }
+        let (wins, gammons) = record.side_mut(&winner);
+        *wins += 1;
+        if verdict.result_type != ResultType::Normal {
+            *gammons += 1;
+        }
+    })?;
+
+    // Swap the reported result's gammons, points and cube decisions for the
+    // replayed one's
+    let mut winner_stats = PLAYER_STATS.load(deps.storage, &winner)?;
+    let mut loser_stats = PLAYER_STATS.load(deps.storage, &loser)?;
+    let (reported_winner_stats, reported_loser_stats) = if winner == reported_winner {
+        (&mut winner_stats, &mut loser_stats)
+    } else {
+        (&mut loser_stats, &mut winner_stats)
+    };
+    book_score(
+        reported_winner_stats,
+        reported_loser_stats,
+        &reported_result_type,
+        reported_points,
+        true,
+    );
+    if let Some(cube) = &reported_cube {
+        book_cube(
+            reported_winner_stats,
+            reported_loser_stats,
+            cube,
+            reported_winner == game.player_a,
+            true,
+        );
+    }
+    book_score(
+        &mut winner_stats,
+        &mut loser_stats,
+        &verdict.result_type,
+        game.points_won,
+        false,
+    );
+    if let Some(cube) = &game.cube {
+        book_cube(
+            &mut winner_stats,
+            &mut loser_stats,
+            cube,
+            winner == game.player_a,
+            false,
+        );
+    }
+
+    if winner != reported_winner {
+        winner_stats.games_won += 1;
+        loser_stats.games_won = loser_stats.games_won.saturating_sub(1);
+        // Season counters only hold this season's games
+        if completed_in_current_season(deps.storage, &game)? {
+            winner_stats.season_games_won += 1;
+            loser_stats.season_games_won = loser_stats.season_games_won.saturating_sub(1);
+        }
+    }
+    PLAYER_STATS.save(deps.storage, &winner, &winner_stats)?;
+    PLAYER_STATS.save(deps.storage, &loser, &loser_stats)?;
+
+    let now = env.block.time.seconds();
+    let multiplier = result_multiplier(&verdict.result_type);
+    if winner != reported_winner {
+        response = resettle_match(deps.storage, now, &config, response, &game_id, multiplier)?;
+
+        // Streaks and recent form depend on the order results came in, so
+        // replay both players' games with the corrected winner
+        for player in [&winner, &loser] {
+            let mut stats = PLAYER_STATS.load(deps.storage, player)?;
+            stats.best_win_streak = 0;
+            backfill_player_stats(deps.storage, &mut stats, now, None, usize::MAX)?;
+            PLAYER_STATS.save(deps.storage, player, &stats)?;
+        }
+    } else {
+        response = resettle_multiplier(deps.storage, &config, response, &game_id, multiplier)?;
+    }
+
+    // Re-evaluate the achievements against the replayed result
+    let response =
+        revoke_achievements(deps.storage, &config, response, &game_id, [&winner, &loser])?;
+    award_achievements(
+        deps.storage,
+        &env,
+        &config,
+        response,
+        &game_id,
+        [&winner, &loser],
+    )
+}
+
+/// Reverse what settle_match booked for `escrow_id`, book it again with the
+/// winner and loser swapped, and ask the escrow to re-settle at `multiplier`.
+fn resettle_match(
+    storage: &mut dyn cosmwasm_std::Storage,
+    now: u64,
+    config: &Config,
+    response: Response,
+    escrow_id: &str,
+    multiplier: u32,
+) -> Result<Response, ContractError> {
+    let previous = SETTLEMENTS.load(storage, escrow_id)?;
+    let mut winner_stats = PLAYER_STATS.load(storage, &previous.loser)?;
+    let mut loser_stats = PLAYER_STATS.load(storage, &previous.winner)?;
+
+    // Undo the original booking, including any peak the win set. The payout already sent stays with who
+    // received it; the stakes, and whatever the new winner is owed, are
+    // booked again from the escrow's reply to the resettlement.
+    loser_stats.rating = loser_stats.rating.saturating_sub(previous.rating_gain);
+    loser_stats.total_staked = loser_stats
+        .total_staked
+        .saturating_sub(previous.winner_stake);
+    winner_stats.total_staked = winner_stats
+        .total_staked
+        .saturating_sub(previous.loser_stake);
+    loser_stats.experience = loser_stats.experience.saturating_sub(previous.match_length);
+    winner_stats.rating = winner_stats.rating.saturating_add(previous.rating_loss);
+    winner_stats.experience = winner_stats
+        .experience
+        .saturating_sub(previous.match_length);
+    if let Some(peak) = &previous.peak {
+        loser_stats.revert_peak_rating(peak);
+        loser_stats.record_peak_rating(now);
+    }
+
+    let loser_rating_before = loser_stats.rating;
+    let (rating_gain, _) = rating::apply_match_result(
+        &config.rating_params,
+        &mut winner_stats,
+        &mut loser_stats,
+        previous.match_length,
+    )?;
+    let peak = winner_stats.record_peak_rating(now);
+    PLAYER_STATS.save(storage, &winner_stats.address.clone(), &winner_stats)?;
+    PLAYER_STATS.save(storage, &loser_stats.address.clone(), &loser_stats)?;
+
+    SETTLEMENTS.save(
+        storage,
+        escrow_id,
+        &Settlement {
+            winner: previous.loser.clone(),
+            loser: previous.winner.clone(),
+            match_length: previous.match_length,
+            wager_amount: previous.wager_amount,
+            rating_gain,
+            rating_loss: loser_rating_before - loser_stats.rating,
+            payout: 0,
+            rake: 0,
+            cube_value: 0,
+            winner_stake: 0,
+            loser_stake: 0,
+            owed: 0,
+            peak,
+        },
+    )?;
+
+    let mut response = response
+        .add_attribute("rating_gain", rating_gain.to_string())
+        .add_attribute(
+            "rating_loss",
+            (loser_rating_before - loser_stats.rating).to_string(),
+        );
+
+    if let Some(escrow_contract) = &config.escrow_contract {
+        if previous.wager_amount > 0 {
+            let msg = WasmMsg::Execute {
+                contract_addr: escrow_contract.to_string(),
+                msg: to_json_binary(&EscrowExecuteMsg::Resettle {
+                    game_id: escrow_id.to_string(),
+                    winner: previous.loser.to_string(),
+                    multiplier,
+                })?,
+                funds: vec![],
+            };
+            response = response.add_submessage(
+                SubMsg::reply_on_success(msg, SETTLE_ESCROW_REPLY_ID)
+                    .with_payload(escrow_id.as_bytes().to_vec()),
+            );
+        }
+    }
+
+    Ok(response)
+}
+
+/// Ask the escrow to re-settle `escrow_id` for the same winner at
+/// `multiplier`. The payout already sent stands; the stakes, which follow
+/// the multiplier, are booked again from the escrow's reply.
+fn resettle_multiplier(
+    storage: &mut dyn cosmwasm_std::Storage,
+    config: &Config,
+    response: Response,
+    escrow_id: &str,
+    multiplier: u32,
+) -> Result<Response, ContractError> {
+    let mut settlement = SETTLEMENTS.load(storage, escrow_id)?;
+    let Some(escrow_contract) = &config.escrow_contract else {
+        return Ok(response);
+    };
+    if settlement.wager_amount == 0 {
+        return Ok(response);
+    }
+
+    let mut winner_stats = PLAYER_STATS.load(storage, &settlement.winner)?;
+    let mut loser_stats = PLAYER_STATS.load(storage, &settlement.loser)?;
+    winner_stats.total_staked = winner_stats
+        .total_staked
+        .saturating_sub(settlement.winner_stake);
+    loser_stats.total_staked = loser_stats
+        .total_staked
+        .saturating_sub(settlement.loser_stake);
+    PLAYER_STATS.save(storage, &settlement.winner, &winner_stats)?;
+    PLAYER_STATS.save(storage, &settlement.loser, &loser_stats)?;
+
+    settlement.winner_stake = 0;
+    settlement.loser_stake = 0;
+    SETTLEMENTS.save(storage, escrow_id, &settlement)?;
+
+    let msg = WasmMsg::Execute {
+        contract_addr: escrow_contract.to_string(),
+        msg: to_json_binary(&EscrowExecuteMsg::Resettle {
+            game_id: escrow_id.to_string(),
+            winner: settlement.winner.to_string(),
+            multiplier,
+        })?,
+        funds: vec![],
+    };
+    Ok(response.add_submessage(
+        SubMsg::reply_on_success(msg, SETTLE_ESCROW_REPLY_ID)
+            .with_payload(escrow_id.as_bytes().to_vec()),
+    ))
+}
+
+fn execute_start_season(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    reset_percent: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ROLES.ensure(deps.storage, &config.admin, &Role::Operator, &info.sender)?;
+    if let Some(season_id) = ACTIVE_SEASON.may_load(deps.storage)? {
+        return Err(ContractError::SeasonActive { season_id });
+    }
+    if let Some(ending) = SEASON_ENDING.may_load(deps.storage)? {
+        return Err(ContractError::SeasonEnding {
+            season_id: ending.season_id,
+        });
+    }
+    let reset_percent = reset_percent.unwrap_or(DEFAULT_SEASON_RESET_PERCENT);
+    if reset_percent > 100 {
+        return Err(ContractError::InvalidResetPercent {
+            percent: reset_percent,
+        });
+    }
+
+    let season_id = SEASON_COUNTER.may_load(deps.storage)?.unwrap_or(0) + 1;
+    SEASON_COUNTER.save(deps.storage, &season_id)?;
+    SEASONS.save(
+        deps.storage,
+        season_id,
+        &Season {
+            season_id,
+            name: name.clone(),
+            started_at: env.block.time.seconds(),
+            ended_at: None,
+            reset_percent,
+            player_count: 0,
+        },
+    )?;
+    ACTIVE_SEASON.save(deps.storage, &season_id)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "start_season")
+        .add_attribute("season_id", season_id.to_string())
+        .add_attribute("name", name))
+}
+
+/// Archives everyone who played this season, ranked by rating, then
+/// soft-resets every rating and clears the season counters. Lifetime stats
+/// are left untouched. The first call closes the season, so later results
+/// count toward none; the work is then spread over calls of `limit` players.
+fn execute_end_season(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ROLES.ensure(deps.storage, &config.admin, &Role::Operator, &info.sender)?;
+    let ending = match SEASON_ENDING.may_load(deps.storage)? {
+        Some(ending) => ending,
+        None => {
+            let season_id = ACTIVE_SEASON
+                .may_load(deps.storage)?
+                .ok_or(ContractError::NoActiveSeason {})?;
+            let mut season = SEASONS.load(deps.storage, season_id)?;
+            season.ended_at = Some(env.block.time.seconds());
+            SEASONS.save(deps.storage, season_id, &season)?;
+            ACTIVE_SEASON.remove(deps.storage);
+            SeasonEnding {
+                season_id,
+                stage: SeasonEndStage::Players { start_after: None },
+            }
+        }
+    };
+    let season_id = ending.season_id;
+    let mut season = SEASONS.load(deps.storage, season_id)?;
+    let limit = limit
+        .unwrap_or(DEFAULT_SEASON_END_LIMIT)
+        .clamp(1, MAX_SEASON_END_LIMIT) as usize;
+
+    // Spend the batch across stages until it runs out or the work is done
+    let mut processed = 0;
+    let mut next = Some(ending.stage);
+    while let Some(stage) = next.take() {
+        if processed == limit {
+            next = Some(stage);
+            break;
+        }
+        let (count, following) =
+            end_season_batch(deps.storage, &config, &mut season, stage, limit - processed)?;
+        processed += count;
+        next = following;
+    }
+
+    let mut response = Response::new()
+        .add_attribute("action", "end_season")
+        .add_attribute("season_id", season_id.to_string())
+        .add_attribute("processed", processed.to_string())
+        .add_attribute("complete", next.is_none().to_string());
+    match next {
+        Some(stage) => SEASON_ENDING.save(deps.storage, &SeasonEnding { season_id, stage })?,
+        None => {
+            SEASON_ENDING.remove(deps.storage);
+            response = response.add_attribute("player_count", season.player_count.to_string());
+        }
+    }
+    Ok(response)
+}
+
+/// Whether a game finished during the running season
+fn completed_in_current_season(
+    storage: &dyn cosmwasm_std::Storage,
+    game: &Game,
+) -> StdResult<bool> {
+    let Some(season_id) = ACTIVE_SEASON.may_load(storage)? else {
+        return Ok(false);
+    };
+    let season = SEASONS.load(storage, season_id)?;
+    Ok(game
+        .completed_at
+        .is_some_and(|completed_at| completed_at >= season.started_at))
+}
+
+/// Process up to `limit` players of a season end stage, returning how many
+/// were processed and the stage to continue from, if any work remains
+fn end_season_batch(
+    storage: &mut dyn cosmwasm_std::Storage,
+    config: &Config,
+    season: &mut Season,
+    stage: SeasonEndStage,
+    limit: usize,
+) -> StdResult<(usize, Option<SeasonEndStage>)> {
+    let season_id = season.season_id;
+    match stage {
+        // Players are walked by address, since resetting ratings reorders
+        // the rating index under the walk
+        SeasonEndStage::Players { start_after } => {
+            let players = PLAYER_STATS
+                .range(
+                    storage,
+                    start_after.as_ref().map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .map(|item| item.map(|(_, stats)| stats))
+                .collect::<StdResult<Vec<PlayerStats>>>()?;
+            let next = match players.last() {
+                Some(last) if players.len() == limit => SeasonEndStage::Players {
+                    start_after: Some(last.address.clone()),
+                },
+                _ => SeasonEndStage::Standings { ranked: 0 },
+            };
+            let count = players.len();
+            for mut stats in players {
+                let final_rating = stats.rating;
+                stats.rating =
+                    rating::soft_reset(final_rating, DEFAULT_RATING, season.reset_percent)
+                        .max(config.rating_params.min_rating);
+
+                if stats.season_games_played > 0 {
+                    SEASON_ENTRIES.save(
+                        storage,
+                        (season_id, final_rating, &stats.address),
+                        &SeasonStanding {
+                            season_id,
+                            rank: 0,
+                            address: stats.address.clone(),
+                            rating: final_rating,
+                            reset_rating: stats.rating,
+                            games_played: stats.season_games_played,
+                            games_won: stats.season_games_won,
+                        },
+                    )?;
+                }
+
+                stats.season_games_played = 0;
+                stats.season_games_won = 0;
+                PLAYER_STATS.save(storage, &stats.address.clone(), &stats)?;
+            }
+            Ok((count, Some(next)))
+        }
+        // Archived entries come out highest rating first; each is ranked
+        // and removed, so the next batch starts from the top again
+        SeasonEndStage::Standings { mut ranked } => {
+            let entries = SEASON_ENTRIES
+                .sub_prefix(season_id)
+                .range(storage, None, None, Order::Descending)
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+            for ((rating, address), mut standing) in entries.iter().cloned() {
+                ranked += 1;
+                standing.rank = ranked;
+                SEASON_STANDINGS.save(storage, (season_id, ranked), &standing)?;
+                SEASON_ENTRIES.remove(storage, (season_id, rating, &address));
+            }
+            if entries.len() == limit {
+                return Ok((entries.len(), Some(SeasonEndStage::Standings { ranked })));
+            }
+            season.player_count = ranked;
+            SEASONS.save(storage, season_id, season)?;
+            Ok((entries.len(), None))
+        }
+    }
+}
+
+/// The operation a message belongs to, if a Pauser can halt it
+fn pausable_operation(msg: &ExecuteMsg) -> Option<Operation> {
+    match msg {
+        ExecuteMsg::CreateGame { .. }
+        | ExecuteMsg::AcceptGame { .. }
+        | ExecuteMsg::AcceptChallenge { .. }
+        | ExecuteMsg::CreateMatch { .. } => Some(Operation::CreateGame),
+        ExecuteMsg::ReportResult { .. }
+        | ExecuteMsg::ReportAbandonment { .. }
+        | ExecuteMsg::ResolveChallenge { .. }
+        | ExecuteMsg::FinalizeResult { .. }
+        | ExecuteMsg::SubmitSignedResult { .. }
+        | ExecuteMsg::DisputeResult { .. } => Some(Operation::Settlement),
+        _ => None,
+    }
+}
+
+fn ensure_not_paused(
+    storage: &dyn cosmwasm_std::Storage,
+    operation: &Operation,
+) -> Result<(), ContractError> {
+    let key = format!("{:?}", operation);
+    if PAUSED.has(storage, key.clone()) {
+        return Err(ContractError::Paused { operation: key });
+    }
+    Ok(())
+}
+
+fn execute_set_paused(
+    deps: DepsMut,
+    info: MessageInfo,
+    operations: Vec<Operation>,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ROLES.ensure(deps.storage, &config.admin, &Role::Pauser, &info.sender)?;
+
+    let mut names = Vec::with_capacity(operations.len());
+    for operation in &operations {
+        let key = format!("{:?}", operation);
+        if paused {
+            PAUSED.save(deps.storage, key.clone(), &Empty {})?;
+        } else {
+            PAUSED.remove(deps.storage, key.clone());
+        }
+        names.push(key);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", if paused { "pause" } else { "unpause" })
+        .add_attribute("operations", names.join(",")))
+}
+
+fn execute_propose_new_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+    expires_in: Option<u64>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    let expires_in = expires_in.unwrap_or(DEFAULT_ADMIN_TRANSFER_EXPIRY);
+    if expires_in == 0 || expires_in > MAX_ADMIN_TRANSFER_EXPIRY {
+        return Err(ContractError::InvalidAdminTransferExpiry {
+            seconds: expires_in,
+            max: MAX_ADMIN_TRANSFER_EXPIRY,
+        });
+    }
+
+    let pending = PendingAdmin {
+        address: deps.api.addr_validate(&address)?,
+        expires_at: env.block.time.seconds() + expires_in,
+    };
+    PENDING_ADMIN.save(deps.storage, &pending)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose_new_admin")
+        .add_attribute("pending_admin", pending.address)
+        .add_attribute("expires_at", pending.expires_at.to_string()))
+}
+
+fn execute_accept_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let pending = PENDING_ADMIN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingAdmin {})?;
+    if info.sender != pending.address {
+        return Err(ContractError::Unauthorized {});
+    }
+    if env.block.time.seconds() >= pending.expires_at {
+        return Err(ContractError::AdminTransferExpired {
+            expires_at: pending.expires_at,
+        });
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+    let previous = std::mem::replace(&mut config.admin, pending.address);
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_admin")
+        .add_attribute("previous_admin", previous)
+        .add_attribute("admin", config.admin))
+}
+
+fn execute_cancel_admin_transfer(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    if !PENDING_ADMIN.exists(deps.storage) {
+        return Err(ContractError::NoPendingAdmin {});
+    }
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new().add_attribute("action", "cancel_admin_transfer"))
+}
+
+fn execute_renounce_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Grants could never be revoked afterwards, so none may outlive the admin
+    if !ROLES.is_empty(deps.storage) {
+        return Err(ContractError::RolesRemain {});
+    }
+
+    // The contract never sends messages to itself, so this leaves no one
+    // able to act as admin
+    config.admin = env.contract.address;
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "renounce_admin")
+        .add_attribute("previous_admin", info.sender))
+}
+
+fn execute_grant_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ROLES.execute_grant(deps, &config.admin, &info.sender, &role, &address)?)
+}
+
+fn execute_revoke_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ROLES.execute_revoke(deps, &config.admin, &info.sender, &role, &address)?)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    escrow_contract: Option<String>,
+    rating_params: Option<RatingParams>,
+    challenge_period_seconds: Option<u64>,
+    game_ttl_seconds: Option<u64>,
+    dispute_period_seconds: Option<u64>,
+    achievement_nft_contract: Option<String>,
+    server_address: Option<String>,
+    arbiter: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    ROLES.ensure(
+        deps.storage,
+        &config.admin,
+        &Role::ConfigAdmin,
+        &info.sender,
+    )?;
+
+    // The pre-roles fields replaced a single address, so they replace every
+    // holder of their role; rotating a key through them must not leave the
+    // old one authorized. Only the admin can change roles.
+    let mut response = Response::new().add_attribute("action", "update_config");
+    for (role, addr) in [(Role::Reporter, server_address), (Role::Arbiter, arbiter)] {
+        if let Some(addr) = addr {
+            if info.sender != config.admin {
+                return Err(ContractError::Unauthorized {});
+            }
+            for replaced in ROLES.replace(deps.storage, &role, &deps.api.addr_validate(&addr)?)? {
+                response = response.add_attribute("revoked", format!("{role:?}:{replaced}"));
+            }
+        }
+    }
+
+    if let Some(addr) = escrow_contract {
+        config.escrow_contract = Some(deps.api.addr_validate(&addr)?);
+    }
+    if let Some(params) = rating_params {
+        validate_rating_params(&params)?;
+        config.rating_params = params;
+    }
+    if let Some(seconds) = challenge_period_seconds {
+        config.challenge_period_seconds = seconds;
+    }
+    if let Some(seconds) = game_ttl_seconds {
+        config.game_ttl_seconds = seconds;
+    }
+    if let Some(seconds) = dispute_period_seconds {
+        config.dispute_period_seconds = seconds;
+    }
+    if let Some(addr) = achievement_nft_contract {
+        config.achievement_nft_contract = Some(deps.api.addr_validate(&addr)?);
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+    Ok(response)
+}
+
+fn validate_rating_params(params: &RatingParams) -> Result<(), ContractError> {
+    if params.base_factor == 0 {
+        return Err(ContractError::InvalidRatingParams {
+            reason: "base_factor must be positive".to_string(),
+        });
+    }
+    if params.max_provisional_factor == 0 {
+        return Err(ContractError::InvalidRatingParams {
+            reason: "max_provisional_factor must be at least 1".to_string(),
+        });
+    }
+    if params.min_rating > DEFAULT_RATING {
+        return Err(ContractError::InvalidRatingParams {
+            reason: "min_rating cannot exceed the default rating".to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn validate_move_log(log: &MoveLogCommitment) -> Result<(), ContractError> {
+    if log.root.len() != 32 {
+        return Err(ContractError::InvalidMoveLog {
+            reason: "root must be a 32-byte sha256 digest".to_string(),
+        });
+    }
+    if log.leaf_count == 0 {
+        return Err(ContractError::InvalidMoveLog {
+            reason: "log cannot be empty".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Ensure player stats exist (initialize if new player)
+fn ensure_player_stats(
+    storage: &mut dyn cosmwasm_std::Storage,
+    addr: &Addr,
+) -> Result<(), ContractError> {
+    if !PLAYER_STATS.has(storage, addr) {
+        PLAYER_STATS.save(
+            storage,
+            addr,
+            &PlayerStats::new(addr.clone(), DEFAULT_RATING),
+        )?;
+    }
+    Ok(())
+}
+
+#[entry_point]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::Roles {} => to_json_binary(&query_roles(deps)?),
+        QueryMsg::PendingAdmin {} => to_json_binary(&query_pending_admin(deps)?),
+        QueryMsg::PauseStatus {} => to_json_binary(&query_pause_status(deps)?),
+        QueryMsg::Game { game_id } => to_json_binary(&query_game(deps, game_id)?),
+        QueryMsg::GameByServerMatch { server_match_id } => {
+            let game_id = SERVER_MATCH_GAMES.load(deps.storage, &server_match_id)?;
+            to_json_binary(&query_game(deps, game_id)?)
+        }
+        QueryMsg::GameDetails { game_id } => to_json_binary(&query_game_details(deps, game_id)?),
+        QueryMsg::Match { match_id } => to_json_binary(&query_match(deps, match_id)?),
+        QueryMsg::VerifyRoll { game_id, turn } => {
+            to_json_binary(&query_verify_roll(deps, game_id, turn)?)
+        }
+        QueryMsg::VerifyMoveProof {
+            game_id,
+            index,
+            leaf,
+            proof,
+        } => to_json_binary(&query_verify_move_proof(deps, game_id, index, leaf, proof)?),
+        QueryMsg::ResultSigningPayload {
+            game_id,
+            winner,
+            result_type,
+            nonce,
+            cube,
+        } => to_json_binary(&query_result_signing_payload(
+            deps,
+            env,
+            game_id,
+            winner,
+            result_type,
+            nonce,
+            cube,
+        )?),
+        QueryMsg::AbandonmentSigningPayload { game_id, abandoner } => to_json_binary(
+            &query_abandonment_signing_payload(deps, env, game_id, abandoner)?,
+        ),
+        QueryMsg::MoveLogSigningPayload {
+            game_id,
+            root,
+            leaf_count,
+        } => to_json_binary(&query_move_log_signing_payload(
+            env, game_id, root, leaf_count,
+        )?),
+        QueryMsg::Dispute { game_id } => to_json_binary(&query_dispute(deps, game_id)?),
+        QueryMsg::PlayerStats { address } => to_json_binary(&query_player_stats(deps, address)?),
+        QueryMsg::Stats {} => to_json_binary(&query_stats(deps)?),
+        QueryMsg::DailyStats {
+            start_time,
+            end_time,
+            limit,
+        } => to_json_binary(&query_daily_stats(deps, start_time, end_time, limit)?),
+        QueryMsg::Leaderboard {
+            start_after,
+            limit,
+            min_games,
+        } => to_json_binary(&query_leaderboard(deps, start_after, limit, min_games)?),
+        QueryMsg::Challenge { challenge_id } => {
+            to_json_binary(&query_challenge(deps, env, challenge_id)?)
+        }
+        QueryMsg::OpenChallenges { start_after, limit } => {
+            to_json_binary(&query_open_challenges(deps, env, start_after, limit)?)
+        }
+        QueryMsg::InviteSigningPayload {
+            challenge_id,
+            acceptor,
+        } => to_json_binary(&query_invite_signing_payload(
+            deps,
+            env,
+            challenge_id,
+            acceptor,
+        )?),
+        QueryMsg::Proposal { proposal_id } => {
+            to_json_binary(&query_proposal(deps, env, proposal_id)?)
+        }
+        QueryMsg::PendingProposals {
+            address,
+            start_after,
+            limit,
+        } => to_json_binary(&query_pending_proposals(
+            deps,
+            env,
+            address,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::Season { season_id } => to_json_binary(&query_season(deps, season_id)?),
+        QueryMsg::CurrentSeason {} => to_json_binary(&query_current_season(deps)?),
+        QueryMsg::SeasonStandings {
+            season_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query_season_standings(
+            deps,
+            season_id,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::PlayerSeasons {
+            address,
+            start_after,
+            limit,
+        } => to_json_binary(&query_player_seasons(deps, address, start_after, limit)?),
+        QueryMsg::PlayerAchievements { address } => {
+            to_json_binary(&query_player_achievements(deps, address)?)
+        }
+        QueryMsg::PlayerRank { address } => to_json_binary(&query_player_rank(deps, address)?),
+        QueryMsg::HeadToHead { a, b } => to_json_binary(&query_head_to_head(deps, a, b)?),
+        QueryMsg::Rivals { address, limit } => to_json_binary(&query_rivals(deps, address, limit)?),
+        QueryMsg::PlayerGames {
+            address,
+            start_after,
+            limit,
+            status,
+            start_time,
+            end_time,
+        } => to_json_binary(&query_player_games(
+            deps,
+            address,
+            start_after,
+            limit,
+            status,
+            start_time,
+            end_time,
+        )?),
+    }
+}
+
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        admin: config.admin,
+        escrow_contract: config.escrow_contract,
+        usdc_denom: config.usdc_denom,
+        rating_params: config.rating_params,
+        challenge_period_seconds: config.challenge_period_seconds,
+        game_ttl_seconds: config.game_ttl_seconds,
+        dispute_period_seconds: config.dispute_period_seconds,
+        achievement_nft_contract: config.achievement_nft_contract,
+        server_address: ROLES
+            .members(deps.storage, &Role::Reporter)?
+            .into_iter()
+            .next(),
+        arbiter: ROLES
+            .members(deps.storage, &Role::Arbiter)?
+            .into_iter()
+            .next(),
+    })
+}
+
+fn query_pause_status(deps: Deps) -> StdResult<PauseStatusResponse> {
+    let paused = [Operation::CreateGame, Operation::Settlement]
+        .into_iter()
+        .filter(|operation| PAUSED.has(deps.storage, format!("{:?}", operation)))
+        .collect();
+    Ok(PauseStatusResponse { paused })
+}
+
+fn query_pending_admin(deps: Deps) -> StdResult<PendingAdminResponse> {
+    Ok(PendingAdminResponse {
+        admin: CONFIG.load(deps.storage)?.admin,
+        pending: PENDING_ADMIN.may_load(deps.storage)?,
+    })
+}
+
+fn query_roles(deps: Deps) -> StdResult<RolesResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let roles = [
+        Role::ConfigAdmin,
+        Role::Operator,
+        Role::Reporter,
+        Role::Arbiter,
+        Role::Pauser,
+    ]
+    .into_iter()
+    .map(|role| {
+        let members = ROLES.members(deps.storage, &role)?;
+        Ok(RoleMembers { role, members })
+    })
+    .collect::<StdResult<Vec<_>>>()?;
+    Ok(RolesResponse {
+        admin: config.admin,
+        roles,
+    })
+}
+
+fn query_game(deps: Deps, game_id: String) -> StdResult<GameResponse> {
+    let game = GAMES.load(deps.storage, &game_id)?;
+    Ok(game_response(game))
+}
+
+fn query_game_details(deps: Deps, game_id: String) -> StdResult<GameDetailsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let game = GAMES.load(deps.storage, &game_id)?;
+
+    // Match games share the match's escrow and carry no wager of their own
+    let (escrow_id, wager_amount) = match &game.match_id {
+        Some(match_id) => (
+            match_id.clone(),
+            MATCHES.load(deps.storage, match_id)?.wager_amount,
+        ),
+        None => (game.game_id.clone(), game.wager_amount),
+    };
+    // A game created before the escrow contract was set has no escrow there
+    let escrow = match &config.escrow_contract {
+        Some(escrow_contract) if wager_amount > 0 => {
+            query_escrow(&deps.querier, escrow_contract, &escrow_id)?.map(|escrow| EscrowDetails {
+                escrow_id,
+                status: escrow.status,
+                player_a_deposited: escrow.player_a_deposited,
+                player_b_deposited: escrow.player_b_deposited,
+                cube_value: escrow.cube_value,
+                pending_double: escrow.pending_double,
+                projected_payout: escrow.projected_payout,
+                projected_rake: escrow.projected_rake,
+            })
+        }
+        _ => None,
+    };
+
+    Ok(GameDetailsResponse {
+        game: game_response(game),
+        escrow,
+    })
+}
+
+fn game_response(game: Game) -> GameResponse {
+    GameResponse {
+        game_id: game.game_id,
+        player_a: game.player_a,
+        player_b: game.player_b,
+        wager_amount: game.wager_amount,
+        status: format!("{:?}", game.status),
+        winner: game.winner,
+        result_type: game.result_type.map(|rt| format!("{:?}", rt)),
+        created_at: game.created_at,
+        completed_at: game.completed_at,
+        move_count: game.move_count,
+        match_id: game.match_id,
+        dice_committed: game.dice_committed,
+        move_log: game.move_log,
+        move_log_signed: game.move_log_signed,
+        result_reported_at: game.result_reported_at,
+        challenge_deadline: game.challenge_deadline,
+        abandoned_by: game.abandoned_by,
+        challenged_by: game.challenged_by,
+        cube: game.cube,
+        points_won: game.points_won,
+        server_match_id: game.server_match_id,
+    }
+}
+
+fn query_verify_roll(deps: Deps, game_id: String, turn: u32) -> StdResult<VerifyRollResponse> {
+    let reveal = DICE_REVEALS.load(deps.storage, (&game_id, turn))?;
+    Ok(VerifyRollResponse {
+        dice: dice::derive_dice(&reveal.server_seed, &reveal.client_seed, turn),
+        verified: dice::commit_hash(&reveal.server_seed) == reveal.commit_hash,
+        game_id,
+        turn,
+        server_seed: reveal.server_seed,
+        client_seed: reveal.client_seed,
+        commit_hash: reveal.commit_hash,
+    })
+}
+
+fn query_verify_move_proof(
+    deps: Deps,
+    game_id: String,
+    index: u32,
+    leaf: Binary,
+    proof: Vec<HexBinary>,
+) -> StdResult<VerifyMoveProofResponse> {
+    let game = GAMES.load(deps.storage, &game_id)?;
+    let valid = game
+        .move_log
+        .is_some_and(|log| merkle::verify_proof(&log.root, log.leaf_count, index, &leaf, &proof));
+    Ok(VerifyMoveProofResponse {
+        game_id,
+        index,
+        valid,
+    })
+}
+
+fn query_result_signing_payload(
+    deps: Deps,
+    env: Env,
+    game_id: String,
+    winner: String,
+    result_type: String,
+    nonce: u64,
+    cube: Option<CubeReport>,
+) -> StdResult<ResultSigningPayloadResponse> {
+    let winner = deps.api.addr_validate(&winner)?;
+    let (payload, hash) = signing_payload(&env, &game_id, &winner, &result_type, nonce, cube)?;
+    Ok(ResultSigningPayloadResponse {
+        payload: Binary::from(payload),
+        hash: HexBinary::from(hash.as_slice()),
+    })
+}
+
+fn query_abandonment_signing_payload(
+    deps: Deps,
+    env: Env,
+    game_id: String,
+    abandoner: String,
+) -> StdResult<ResultSigningPayloadResponse> {
+    let abandoner = deps.api.addr_validate(&abandoner)?;
+    let (payload, hash) = abandonment_signing_payload(&env, &game_id, &abandoner)?;
+    Ok(ResultSigningPayloadResponse {
+        payload: Binary::from(payload),
+        hash: HexBinary::from(hash.as_slice()),
+    })
+}
+
+fn query_move_log_signing_payload(
+    env: Env,
+    game_id: String,
+    root: HexBinary,
+    leaf_count: u32,
+) -> StdResult<ResultSigningPayloadResponse> {
+    let log = MoveLogCommitment { root, leaf_count };
+    let (payload, hash) = move_log_signing_payload(&env, &game_id, &log)?;
+    Ok(ResultSigningPayloadResponse {
+        payload: Binary::from(payload),
+        hash: HexBinary::from(hash.as_slice()),
+    })
+}
+
+fn query_dispute(deps: Deps, game_id: String) -> StdResult<DisputeResponse> {
+    let dispute = DISPUTES.load(deps.storage, &game_id)?;
+    Ok(DisputeResponse {
+        game_id: dispute.game_id,
+        disputer: dispute.disputer,
+        reported_winner: dispute.reported_winner,
+        reported_result_type: format!("{:?}", dispute.reported_result_type),
+        winner: dispute.winner,
+        result_type: format!("{:?}", dispute.result_type),
+        overturned: dispute.overturned,
+        illegal_turn: dispute.illegal_turn,
+        reason: dispute.reason,
+        filed_at: dispute.filed_at,
+    })
+}
+
+fn query_match(deps: Deps, match_id: String) -> StdResult<MatchResponse> {
+    let bg_match = MATCHES.load(deps.storage, &match_id)?;
+    Ok(MatchResponse {
+        match_id: bg_match.match_id,
+        player_a: bg_match.player_a,
+        player_b: bg_match.player_b,
+        match_length: bg_match.match_length,
+        wager_amount: bg_match.wager_amount,
+        score_a: bg_match.score_a,
+        score_b: bg_match.score_b,
+        crawford: bg_match.crawford,
+        crawford_played: bg_match.crawford_played,
+        status: format!("{:?}", bg_match.status),
+        game_ids: bg_match.game_ids,
+        winner: bg_match.winner,
+        created_at: bg_match.created_at,
+        completed_at: bg_match.completed_at,
+    })
+}
+
+fn query_player_stats(deps: Deps, address: String) -> StdResult<PlayerStatsResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let stats = PLAYER_STATS
+        .may_load(deps.storage, &addr)?
+        .unwrap_or_else(|| PlayerStats::new(addr, DEFAULT_RATING));
+    Ok(player_stats_response(stats))
+}
+
+fn player_stats_response(stats: PlayerStats) -> PlayerStatsResponse {
+    PlayerStatsResponse {
+        address: stats.address,
+        games_played: stats.games_played,
+        games_won: stats.games_won,
+        rating: stats.rating,
+        total_wagered: stats.total_wagered,
+        total_won: stats.total_won,
+        experience: stats.experience,
+        season_games_played: stats.season_games_played,
+        season_games_won: stats.season_games_won,
+        gammons_won: stats.gammons_won,
+        gammons_lost: stats.gammons_lost,
+        backgammons_won: stats.backgammons_won,
+        backgammons_lost: stats.backgammons_lost,
+        points_won: stats.points_won,
+        points_lost: stats.points_lost,
+        doubles_offered: stats.doubles_offered,
+        doubles_taken: stats.doubles_taken,
+        doubles_passed: stats.doubles_passed,
+        win_streak: stats.win_streak,
+        best_win_streak: stats.best_win_streak,
+        abandonments: stats.abandonments,
+        peak_rating: stats.peak_rating,
+        peak_rating_at: stats.peak_rating_at,
+        last_played: stats.last_played,
+        recent_results: stats.recent_results,
+        recent_games: stats.recent_games,
+        total_staked: stats.total_staked,
+        rake_paid: stats.rake_paid,
+        total_owed: stats.total_owed,
+        net_winnings: stats.total_won as i128 - stats.total_staked as i128,
+    }
+}
+
+/// Walks the rating index from the top. The index key is (rating, address),
+/// so the cursor player's current entry is an exact exclusive bound.
+fn query_leaderboard(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    min_games: Option<u32>,
+) -> StdResult<LeaderboardResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let max = match start_after {
+        Some(address) => {
+            let addr = deps.api.addr_validate(&address)?;
+            let cursor = PLAYER_STATS.load(deps.storage, &addr)?;
+            Some(Bound::exclusive((cursor.rating, addr)))
+        }
+        None => None,
+    };
+    let min_games = min_games.unwrap_or(0);
+
+    // The min_games filter can skip any number of entries, so a page stops
+    // after MAX_LEADERBOARD_SCAN of them and hands back where it got to
+    let mut players = vec![];
+    let mut last_scanned = None;
+    let mut scanned = 0;
+    for item in PLAYER_STATS
+        .idx
+        .rating
+        .range(deps.storage, None, max, Order::Descending)
+    {
+        let (_, stats) = item?;
+        scanned += 1;
+        last_scanned = Some(stats.address.clone());
+        if stats.games_played >= min_games {
+            players.push(player_stats_response(stats));
+        }
+        if players.len() == limit || scanned == MAX_LEADERBOARD_SCAN {
+            break;
+        }
+    }
+    let next_start_after = (players.len() == limit || scanned == MAX_LEADERBOARD_SCAN)
+        .then_some(last_scanned)
+        .flatten();
+
+    Ok(LeaderboardResponse {
+        players,
+        next_start_after,
+    })
+}
+
+fn query_player_achievements(deps: Deps, address: String) -> StdResult<PlayerAchievementsResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let achievements = achievements::ALL
+        .iter()
+        .filter_map(|a| {
+            ACHIEVEMENTS
+                .may_load(deps.storage, (&addr, achievements::slug(a)))
+                .transpose()
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(PlayerAchievementsResponse {
+        address: addr,
+        achievements,
+    })
+}
+
+/// Rank is one more than the number of index entries above the player's.
+/// Addresses that have never been in a game aren't on the board.
+fn query_player_rank(deps: Deps, address: String) -> StdResult<PlayerRankResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let Some(stats) = PLAYER_STATS.may_load(deps.storage, &addr)? else {
+        return Ok(PlayerRankResponse {
+            address: addr,
+            rating: DEFAULT_RATING,
+            rank: None,
+        });
+    };
+
+    let above = PLAYER_STATS
+        .idx
+        .rating_counts
+        .count_above(deps.storage, stats.rating)?;
+
+    Ok(PlayerRankResponse {
+        address: addr,
+        rating: stats.rating,
+        rank: Some(above + 1),
+    })
+}
+
+/// Orient a stored pair record to `player`'s side
+fn head_to_head_response(record: HeadToHead, player: &Addr) -> HeadToHeadResponse {
+    let last_played = (record.games > 0).then_some(record.last_played);
+    if *player == record.player_a {
+        HeadToHeadResponse {
+            player: record.player_a,
+            opponent: record.player_b,
+            games: record.games,
+            wins: record.wins_a,
+            losses: record.wins_b,
+            gammons_won: record.gammons_a,
+            gammons_lost: record.gammons_b,
+            total_wagered: record.total_wagered,
+            last_played,
+        }
+    } else {
+        HeadToHeadResponse {
+            player: record.player_b,
+            opponent: record.player_a,
+            games: record.games,
+            wins: record.wins_b,
+            losses: record.wins_a,
+            gammons_won: record.gammons_b,
+            gammons_lost: record.gammons_a,
+            total_wagered: record.total_wagered,
+            last_played,
+        }
+    }
+}
+
+fn query_head_to_head(deps: Deps, a: String, b: String) -> StdResult<HeadToHeadResponse> {
+    let a = deps.api.addr_validate(&a)?;
+    let b = deps.api.addr_validate(&b)?;
+    let (low, high) = if a <= b { (&a, &b) } else { (&b, &a) };
+    let record = HEAD_TO_HEAD
+        .may_load(deps.storage, (low, high))?
+        .unwrap_or_else(|| HeadToHead::new(low.clone(), high.clone()));
+    Ok(head_to_head_response(record, &a))
+}
+
+/// Merges the player_a and player_b indexes, both ordered by games played,
+/// the same way `query_player_games` merges a player's game history.
+fn query_rivals(deps: Deps, address: String, limit: Option<u32>) -> StdResult<RivalsResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let range = |index: &MultiIndex<'static, (Addr, u64), HeadToHead, (Addr, Addr)>| {
+        index
+            .sub_prefix(addr.clone())
+            .range(deps.storage, None, None, Order::Descending)
+            .peekable()
+    };
+    let mut as_a = range(&HEAD_TO_HEAD.idx.player_a);
+    let mut as_b = range(&HEAD_TO_HEAD.idx.player_b);
+
+    let mut rivals = Vec::with_capacity(limit);
+    while rivals.len() < limit {
+        // Take whichever side holds the more-played opponent
+        let take_a = match (as_a.peek(), as_b.peek()) {
+            (None, None) => break,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (Some(Err(_)), _) => true,
+            (_, Some(Err(_))) => false,
+            (Some(Ok((_, a))), Some(Ok((_, b)))) => a.games >= b.games,
+        };
+        let (_, record) = if take_a { as_a.next() } else { as_b.next() }.unwrap()?;
+        rivals.push(head_to_head_response(record, &addr));
+    }
+
+    Ok(RivalsResponse { rivals })
+}
+
+fn challenge_response(challenge: Challenge, now: u64) -> ChallengeResponse {
+    let status = if challenge.status == ChallengeStatus::Open && now >= challenge.expires_at {
+        "Expired".to_string()
+    } else {
+        format!("{:?}", challenge.status)
+    };
+    ChallengeResponse {
+        challenge_id: challenge.challenge_id,
+        creator: challenge.creator,
+        wager_amount: challenge.wager_amount,
+        min_rating: challenge.min_rating,
+        max_rating: challenge.max_rating,
+        private: challenge.invite_pubkey.is_some(),
+        status,
+        created_at: challenge.created_at,
+        expires_at: challenge.expires_at,
+        accepted_by: challenge.accepted_by,
+        game_id: challenge.game_id,
+    }
+}
+
+fn query_challenge(deps: Deps, env: Env, challenge_id: String) -> StdResult<ChallengeResponse> {
+    Ok(challenge_response(
+        CHALLENGES.load(deps.storage, &challenge_id)?,
+        env.block.time.seconds(),
+    ))
+}
+
+/// Walks the open challenges from the soonest expiry still ahead, so
+/// expired ones are never visited. The cursor challenge's (expiry, ID) key
+/// is an exact exclusive bound.
+fn query_open_challenges(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ChallengesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let now = env.block.time.seconds();
+    let cursor = match start_after {
+        Some(id) => CHALLENGES
+            .may_load(deps.storage, &id)?
+            .filter(|c| c.expires_at > now)
+            .map(|c| (c.expires_at, id)),
+        None => None,
+    };
+    let min = match cursor {
+        Some(key) => Bound::exclusive(key),
+        None => Bound::inclusive((now + 1, String::new())),
+    };
+    let challenges = CHALLENGES
+        .idx
+        .status
+        .sub_prefix(format!("{:?}", ChallengeStatus::Open))
+        .range(deps.storage, Some(min), None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, challenge)| challenge_response(challenge, now)))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ChallengesResponse { challenges })
+}
+
+fn query_invite_signing_payload(
+    deps: Deps,
+    env: Env,
+    challenge_id: String,
+    acceptor: String,
+) -> StdResult<ResultSigningPayloadResponse> {
+    let acceptor = deps.api.addr_validate(&acceptor)?;
+    let (payload, hash) = invite_signing_payload(&env, &challenge_id, &acceptor)?;
+    Ok(ResultSigningPayloadResponse {
+        payload: Binary::from(payload),
+        hash: HexBinary::from(hash.as_slice()),
+    })
+}
+
+fn proposal_response(proposal: Proposal, now: u64) -> ProposalResponse {
+    let status = if proposal.status == ProposalStatus::Pending && now >= proposal.expires_at {
+        "Expired".to_string()
+    } else {
+        format!("{:?}", proposal.status)
+    };
+    ProposalResponse {
+        proposal_id: proposal.proposal_id,
+        proposer: proposal.proposer,
+        opponent: proposal.opponent,
+        wager_amount: proposal.wager_amount,
+        status,
+        created_at: proposal.created_at,
+        expires_at: proposal.expires_at,
+        game_id: proposal.game_id,
+    }
+}
+
+fn query_proposal(deps: Deps, env: Env, proposal_id: String) -> StdResult<ProposalResponse> {
+    Ok(proposal_response(
+        PROPOSALS.load(deps.storage, &proposal_id)?,
+        env.block.time.seconds(),
+    ))
+}
+
+fn query_pending_proposals(
+    deps: Deps,
+    env: Env,
+    address: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ProposalsResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let now = env.block.time.seconds();
+    // As with open challenges: start at the soonest expiry still ahead, or
+    // just past the cursor proposal's (expiry, ID) key
+    let cursor = match start_after {
+        Some(id) => PROPOSALS
+            .may_load(deps.storage, &id)?
+            .filter(|p| p.expires_at > now)
+            .map(|p| (p.expires_at, id)),
+        None => None,
+    };
+    let min = match cursor {
+        Some(key) => Bound::exclusive(key),
+        None => Bound::inclusive((now + 1, String::new())),
+    };
+    let proposals = PROPOSALS
+        .idx
+        .opponent
+        .sub_prefix((addr, format!("{:?}", ProposalStatus::Pending)))
+        .range(deps.storage, Some(min), None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, proposal)| proposal_response(proposal, now)))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ProposalsResponse { proposals })
+}
+
+fn season_response(season: Season) -> SeasonResponse {
+    SeasonResponse {
+        season_id: season.season_id,
+        name: season.name,
+        started_at: season.started_at,
+        ended_at: season.ended_at,
+        reset_percent: season.reset_percent,
+        player_count: season.player_count,
+    }
+}
+
+fn query_season(deps: Deps, season_id: u32) -> StdResult<SeasonResponse> {
+    Ok(season_response(SEASONS.load(deps.storage, season_id)?))
+}
+
+fn query_current_season(deps: Deps) -> StdResult<CurrentSeasonResponse> {
+    let season = match ACTIVE_SEASON.may_load(deps.storage)? {
+        Some(season_id) => Some(season_response(SEASONS.load(deps.storage, season_id)?)),
+        None => None,
+    };
+    Ok(CurrentSeasonResponse { season })
+}
+
+fn query_season_standings(
+    deps: Deps,
+    season_id: u32,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<SeasonStandingsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let standings = SEASON_STANDINGS
+        .prefix(season_id)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(_, standing)| standing))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(SeasonStandingsResponse { standings })
+}
+
+fn query_player_seasons(
+    deps: Deps,
+    address: String,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<SeasonStandingsResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    // Entries are ordered by (season_id, rank), and (season_id, u32::MAX)
+    // sorts after every rank in that season
+    let min = start_after.map(|season_id| Bound::exclusive((season_id, u32::MAX)));
+    let standings = SEASON_STANDINGS
+        .idx
+        .player
+        .prefix(addr)
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, standing)| standing))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(SeasonStandingsResponse { standings })
+}
+
+fn query_stats(deps: Deps) -> StdResult<StatsResponse> {
+    Ok(StatsResponse {
+        total_games: TOTAL_GAMES.load(deps.storage)?,
+        totals: PLATFORM_STATS.may_load(deps.storage)?.unwrap_or_default(),
+    })
+}
+
+fn query_daily_stats(
+    deps: Deps,
+    start_time: Option<u64>,
+    end_time: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<DailyStatsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = start_time.map(|t| Bound::inclusive(t / SECONDS_PER_DAY));
+    let max = end_time.map(|t| Bound::inclusive(t / SECONDS_PER_DAY));
+
+    let days = DAILY_STATS
+        .range(deps.storage, min, max, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(day, stats)| DailyStats {
+                day_start: day * SECONDS_PER_DAY,
+                stats,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(DailyStatsResponse { days })
+}
+
+/// Pages through a player's games newest-first using the player_a and
+/// player_b indexes, or their status-keyed variants when filtering by
+/// status. Both index ranges are ordered by (created_at, game_id), so
+/// merging them yields the player's history in order without a full scan.
+fn query_player_games(
+    deps: Deps,
+    address: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    status: Option<String>,
+    start_time: Option<u64>,
+    end_time: Option<u64>,
+) -> StdResult<GamesListResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    // Upper bound is the tighter of the cursor and end_time. (t + 1, "") sorts
+    // before every key created at t + 1, so it excludes them as a bound.
+    let mut upper: Option<(u64, String)> = end_time.map(|t| (t.saturating_add(1), String::new()));
+    if let Some(game_id) = start_after {
+        let cursor = GAMES.load(deps.storage, &game_id)?;
+        let key = (cursor.created_at, cursor.game_id);
+        if upper.as_ref().is_none_or(|u| key < *u) {
+            upper = Some(key);
+        }
+    }
+    let lower = start_time.map(|t| (t, String::new()));
+
+    let (prefix_a, prefix_b) = match status {
+        Some(status) => (
+            GAMES
+                .idx
+                .player_a_status
+                .sub_prefix((addr.clone(), status.clone())),
+            GAMES.idx.player_b_status.sub_prefix((addr, status)),
+        ),
+        None => (
+            GAMES.idx.player_a.sub_prefix(addr.clone()),
+            GAMES.idx.player_b.sub_prefix(addr),
+        ),
+    };
+    let range = |prefix: &IndexPrefix<String, Game, (u64, String)>| {
+        prefix
+            .range(
+                deps.storage,
+                lower.clone().map(Bound::inclusive),
+                upper.clone().map(Bound::exclusive),
+                Order::Descending,
+            )
+            .peekable()
+    };
+    let mut as_a = range(&prefix_a);
+    let mut as_b = range(&prefix_b);
+
+    let mut games = Vec::with_capacity(limit);
+    while games.len() < limit {
+        // Take whichever side holds the newer game
+        let take_a = match (as_a.peek(), as_b.peek()) {
+            (None, None) => break,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (Some(Err(_)), _) => true,
+            (_, Some(Err(_))) => false,
+            (Some(Ok((id_a, a))), Some(Ok((id_b, b)))) => match a.created_at.cmp(&b.created_at) {
+                Ordering::Equal => id_a >= id_b,
+                ord => ord == Ordering::Greater,
+            },
+        };
+        let (_, game) = if take_a { as_a.next() } else { as_b.next() }.unwrap()?;
+        games.push(game_response(game));
+    }
+
+    Ok(GamesListResponse { games })
+}
+
+#[entry_point]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        SETTLE_ESCROW_REPLY_ID => record_escrow_payout(deps, msg),
+        MINT_BADGE_REPLY_ID => {
+            // The achievement stays earned, just without a badge
+            let (player, slug): (Addr, String) = from_json(&msg.payload)?;
+            if let Some(mut earned) = ACHIEVEMENTS.may_load(deps.storage, (&player, &slug))? {
+                earned.token_id = None;
+                ACHIEVEMENTS.save(deps.storage, (&player, &slug), &earned)?;
+            }
+            Ok(Response::new()
+                .add_attribute("action", "mint_badge_failed")
+                .add_attribute("achievement", format!("{slug}:{player}")))
+        }
+        BURN_BADGE_REPLY_ID => Ok(Response::new().add_attribute("action", "burn_badge_failed")),
+        id => Err(StdError::generic_err(format!("unknown reply id {id}")).into()),
+    }
+}
+
+/// Book what the escrow actually paid for a settlement, as reported in the
+/// attributes of its `settle` or `resettle` response
+fn record_escrow_payout(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let escrow_id = String::from_utf8_lossy(&msg.payload).to_string();
+    let config = CONFIG.load(deps.storage)?;
+    let escrow_contract = config
+        .escrow_contract
+        .map(|a| a.to_string())
+        .unwrap_or_default();
+    let events = msg
+        .result
+        .into_result()
+        .map_err(|reason| ContractError::InvalidEscrowReply { reason })?
+        .events;
+    let event = events
+        .iter()
+        .find(|e| {
+            e.ty == "wasm"
+                && e.attributes
+                    .iter()
+                    .any(|a| a.key == "_contract_address" && a.value == escrow_contract)
+        })
+        .ok_or_else(|| ContractError::InvalidEscrowReply {
+            reason: "no event from the escrow contract".to_string(),
+        })?;
+    let figure = |key: &str| -> Result<u128, ContractError> {
+        event
+            .attributes
+            .iter()
+            .find(|a| a.key == key)
+            .and_then(|a| a.value.parse().ok())
+            .ok_or_else(|| ContractError::InvalidEscrowReply {
+                reason: format!("missing or invalid {key}"),
+            })
+    };
+
+    // A resettlement pays and rakes nothing, so what an earlier settlement
+    // of this escrow paid stands
+    let mut settlement = SETTLEMENTS.load(deps.storage, &escrow_id)?;
+    let payout = figure("payout")?;
+    let rake = figure("rake")?;
+    settlement.payout += payout;
+    settlement.rake += rake;
+    settlement.cube_value =
+        figure("cube_value")?
+            .try_into()
+            .map_err(|_| ContractError::InvalidEscrowReply {
+                reason: "cube_value out of range".to_string(),
+            })?;
+    // The escrow collected the doubled stakes itself, so its cube is the one
+    // a standalone game is scored at, whatever cube, if any, the server
+    // reported. Failing here instead would leave the escrow unable to ever
+    // settle. Abandoned games are not scored, and match escrows hold no
+    // cube of their own.
+    let mut response = Response::new().add_attribute("action", "record_payout");
+    if let Some(mut game) = GAMES.may_load(deps.storage, &escrow_id)? {
+        let reported = game.cube.as_ref().map_or(1, |cube| cube.value);
+        if game.status == GameStatus::Completed && reported != settlement.cube_value {
+            correct_cube(deps.storage, &mut game, &settlement)?;
+            response = response.add_attribute(
+                "cube_corrected",
+                format!("{reported}:{}", settlement.cube_value),
+            );
+        }
+    }
+    settlement.winner_stake = figure("winner_stake")?;
+    settlement.loser_stake = figure("loser_stake")?;
+    // Only a resettlement after the pot was paid out leaves anything owed
+    if event.attributes.iter().any(|a| a.key == "owed") {
+        settlement.owed = figure("owed")?;
+    }
+    SETTLEMENTS.save(deps.storage, &escrow_id, &settlement)?;
+
+    let mut winner_stats = PLAYER_STATS.load(deps.storage, &settlement.winner)?;
+    winner_stats.total_won += payout;
+    winner_stats.rake_paid += rake;
+    winner_stats.total_owed += settlement.owed;
+    winner_stats.total_staked += settlement.winner_stake;
+    PLAYER_STATS.save(deps.storage, &settlement.winner, &winner_stats)?;
+
+    let mut loser_stats = PLAYER_STATS.load(deps.storage, &settlement.loser)?;
+    loser_stats.total_staked += settlement.loser_stake;
+    PLAYER_STATS.save(deps.storage, &settlement.loser, &loser_stats)?;
+
+    Ok(response
+        .add_attribute("escrow_id", escrow_id)
+        .add_attribute("payout", settlement.payout.to_string())
+        .add_attribute("rake", settlement.rake.to_string())
+        .add_attribute("owed", settlement.owed.to_string()))
+}
+
+/// Re-score a completed standalone game at the cube its escrow settled at.
+/// The cube decisions the server reported are kept; only the value, and
+/// the points it is worth, change.
+fn correct_cube(
+    storage: &mut dyn cosmwasm_std::Storage,
+    game: &mut Game,
+    settlement: &Settlement,
+) -> Result<(), ContractError> {
+    let mut winner_stats = PLAYER_STATS.load(storage, &settlement.winner)?;
+    let mut loser_stats = PLAYER_STATS.load(storage, &settlement.loser)?;
+    let result_type = game.result_type.clone().unwrap_or(ResultType::Normal);
+
+    book_score(
+        &mut winner_stats,
+        &mut loser_stats,
+        &result_type,
+        game.points_won,
+        true,
+    );
+    game.cube = Some(match game.cube.take() {
+        Some(cube) => CubeResult {
+            value: settlement.cube_value,
+            ..cube
+        },
+        None => CubeResult {
+            value: settlement.cube_value,
+            owner: None,
+            doubles_a: 0,
+            doubles_b: 0,
+            passed: false,
+        },
+    });
+    game.points_won = result_points(&result_type, game.cube.as_ref());
+    book_score(
+        &mut winner_stats,
+        &mut loser_stats,
+        &result_type,
+        game.points_won,
+        false,
+    );
+
+    GAMES.save(storage, &game.game_id, game)?;
+    PLAYER_STATS.save(storage, &settlement.winner, &winner_stats)?;
+    PLAYER_STATS.save(storage, &settlement.loser, &loser_stats)?;
+    Ok(())
+}
+
+#[entry_point]
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stage = match MIGRATION.may_load(deps.storage)? {
+        Some(stage) => stage,
+        None => {
+            // Contracts deployed before versioning have no stored version
+            let stored = cw2::CONTRACT.may_load(deps.storage)?;
+            if let Some(stored) = &stored {
+                if stored.contract != CONTRACT_NAME {
+                    return Err(ContractError::InvalidMigration {
+                        reason: format!("cannot migrate from {}", stored.contract),
+                    });
+                }
+                match version_parts(&stored.version).cmp(&version_parts(CONTRACT_VERSION)) {
+                    Ordering::Less => {}
+                    Ordering::Equal => {
+                        return Ok(Response::new()
+                            .add_attribute("action", "migrate")
+                            .add_attribute("migration_complete", "true"));
+                    }
+                    Ordering::Greater => {
+                        return Err(ContractError::InvalidMigration {
+                            reason: format!("cannot downgrade from {}", stored.version),
+                        });
+                    }
+                }
+            }
+            migrate_roles(deps.storage)?;
+            MigrationStage::Games {
+                start_after: None,
+                backfill_stats: stored.is_none_or(|stored| {
+                    version_parts(&stored.version) < version_parts(STATS_BACKFILL_VERSION)
+                }),
+            }
+        }
+    };
+    let limit = msg
+        .limit
+        .unwrap_or(DEFAULT_MIGRATION_LIMIT)
+        .min(MAX_MIGRATION_LIMIT) as usize;
+
+    // Spend the batch across stages until it runs out or the work is done
+    let mut migrated = 0;
+    let mut next = Some(stage);
+    while let Some(stage) = next.take() {
+        if migrated == limit {
+            next = Some(stage);
+            break;
+        }
+        let (count, following) = migrate_batch(
+            deps.storage,
+            env.block.time.seconds(),
+            stage,
+            limit - migrated,
+        )?;
+        migrated += count;
+        next = following;
+    }
+
+    let complete = next.is_none();
+    match &next {
+        Some(next) => MIGRATION.save(deps.storage, next)?,
+        None => {
+            MIGRATION.remove(deps.storage);
+            cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("migrated", migrated.to_string())
+        .add_attribute("migration_complete", complete.to_string()))
+}
+
+/// Re-save up to `limit` entries of a migration stage, returning how many
+/// were saved and the stage to continue from, if any work remains
+fn migrate_batch(
+    storage: &mut dyn cosmwasm_std::Storage,
+    now: u64,
+    stage: MigrationStage,
+    limit: usize,
+) -> StdResult<(usize, Option<MigrationStage>)> {
+    match stage {
+        // Games stored before GAMES became an IndexedMap have no index
+        // entries; re-saving each one populates them
+        MigrationStage::Games {
+            start_after,
+            backfill_stats,
+        } => {
+            let games = GAMES
+                .range(
+                    storage,
+                    start_after.as_deref().map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .map(|item| item.map(|(_, game)| game))
+                .collect::<StdResult<Vec<Game>>>()?;
+            for game in &games {
+                GAMES.save(storage, &game.game_id, game)?;
+            }
+            let next = match games.last() {
+                Some(last) if games.len() == limit => MigrationStage::Games {
+                    start_after: Some(last.game_id.clone()),
+                    backfill_stats,
+                },
+                _ => MigrationStage::Players {
+                    start_after: None,
+                    backfill_stats,
+                    backfilling: None,
+                },
+            };
+            Ok((games.len(), Some(next)))
+        }
+        // Likewise for player stats and the rating index, rebuilding stats
+        // the entries predate on the way. Games are all indexed by now, and
+        // each game replayed counts towards the batch, so a player with a
+        // long history is spread over as many batches as it takes.
+        MigrationStage::Players {
+            mut start_after,
+            backfill_stats,
+            mut backfilling,
+        } => {
+            let mut count = 0;
+            loop {
+                if count == limit {
+                    let next = MigrationStage::Players {
+                        start_after,
+                        backfill_stats,
+                        backfilling,
+                    };
+                    return Ok((count, Some(next)));
+                }
+                if let Some(cursor) = backfilling.take() {
+                    let mut stats = PLAYER_STATS.load(storage, &cursor.player)?;
+                    let (read, games_after) = backfill_player_stats(
+                        storage,
+                        &mut stats,
+                        now,
+                        cursor.games_after,
+                        limit - count,
+                    )?;
+                    PLAYER_STATS.save(storage, &cursor.player, &stats)?;
+                    count += read;
+                    match games_after {
+                        Some(games_after) => {
+                            backfilling = Some(BackfillCursor {
+                                player: cursor.player,
+                                games_after: Some(games_after),
+                            })
+                        }
+                        None => start_after = Some(cursor.player),
+                    }
+                    continue;
+                }
+
+                let Some((_, stats)) = PLAYER_STATS
+                    .range(
+                        storage,
+                        start_after.as_ref().map(Bound::exclusive),
+                        None,
+                        Order::Ascending,
+                    )
+                    .next()
+                    .transpose()?
+                else {
+                    return Ok((count, None));
+                };
+                count += 1;
+                if backfill_stats {
+                    backfilling = Some(BackfillCursor {
+                        player: stats.address,
+                        games_after: None,
+                    });
+                } else {
+                    PLAYER_STATS.save(storage, &stats.address, &stats)?;
+                    start_after = Some(stats.address);
+                }
+            }
+        }
+    }
+}
+
+/// Numeric components of a version string, for ordering versions
+fn version_parts(version: &str) -> Vec<u64> {
+    version
+        .split(['.', '-', '+'])
+        .map_while(|part| part.parse().ok())
+        .collect()
+}
+
+/// The single server and arbiter addresses become role holders
+fn migrate_roles(storage: &mut dyn cosmwasm_std::Storage) -> StdResult<()> {
+    let mut config = CONFIG.load(storage)?;
+    for (role, addr) in [
+        (Role::Reporter, config.server_address.take()),
+        (Role::Arbiter, config.arbiter.take()),
+    ] {
+        if let Some(addr) = addr {
+            ROLES.grant(storage, &role, &addr)?;
+        }
+    }
+    CONFIG.save(storage, &config)
+}
+
+/// Rebuild a player's streaks, abandonments and recent form by replaying
+/// their finished games in the order they were created, starting over when
+/// `games_after` is None. Reads at most `limit` games and returns how many
+/// it read and, if it had to stop there, the cursor to resume after.
+/// Ratings before now aren't stored, so a missing peak starts from the
+/// current rating.
+fn backfill_player_stats(
+    storage: &dyn cosmwasm_std::Storage,
+    stats: &mut PlayerStats,
+    now: u64,
+    games_after: Option<(u64, String)>,
+    limit: usize,
+) -> StdResult<(usize, Option<(u64, String)>)> {
+    if games_after.is_none() {
+        stats.win_streak = 0;
+        stats.abandonments = 0;
+        stats.recent_results = 0;
+        stats.recent_games = 0;
+        stats.record_peak_rating(now);
+    }
+
+    // Both index ranges are ordered by (created_at, game_id); merge them
+    let player = stats.address.clone();
+    let prefix_a = GAMES.idx.player_a.sub_prefix(player.clone());
+    let prefix_b = GAMES.idx.player_b.sub_prefix(player.clone());
+    let range = |prefix: &IndexPrefix<String, Game, (u64, String)>| {
+        prefix
+            .range(
+                storage,
+                games_after.clone().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .peekable()
+    };
+    let mut as_a = range(&prefix_a);
+    let mut as_b = range(&prefix_b);
+
+    let mut read = 0;
+    let mut last = None;
+    while read < limit {
+        // Take whichever side holds the older game
+        let take_a = match (as_a.peek(), as_b.peek()) {
+            (None, None) => break,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (Some(Err(_)), _) => true,
+            (_, Some(Err(_))) => false,
+            (Some(Ok((id_a, a))), Some(Ok((id_b, b)))) => match a.created_at.cmp(&b.created_at) {
+                Ordering::Equal => id_a <= id_b,
+                ord => ord == Ordering::Less,
+            },
+        };
+        let (game_id, game) = if take_a { as_a.next() } else { as_b.next() }.unwrap()?;
+        read += 1;
+        last = Some((game.created_at, game_id));
+
+        if !matches!(game.status, GameStatus::Completed | GameStatus::Abandoned) {
+            continue;
+        }
+        let (Some(winner), Some(at)) = (&game.winner, game.completed_at) else {
+            continue;
+        };
+        stats.record_result(player == *winner, at);
+        if game.status == GameStatus::Abandoned && player != *winner {
+            stats.abandonments += 1;
+        }
+    }
+    Ok((read, last.filter(|_| read == limit)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{
+        message_info, mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{from_json, OwnedDeps, ReplyOn, Timestamp};
+
+    fn addr(name: &str) -> Addr {
+        MockApi::default().addr_make(name)
+    }
+
+    fn env_at(seconds: u64) -> Env {
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(seconds);
+        env
+    }
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            escrow_contract: None,
+            server_address: Some(addr("server").to_string()),
+            usdc_denom: "uusdc".to_string(),
+            rating_params: None,
+            challenge_period_seconds: None,
+            arbiter: None,
+            game_ttl_seconds: None,
+            dispute_period_seconds: None,
+            achievement_nft_contract: None,
+        };
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("admin"), &[]),
+            msg,
+        )
+        .unwrap();
+        deps
+    }
+
+    fn create_game(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        player_a: &str,
+        player_b: &str,
+        at: u64,
+    ) -> String {
+        let res = execute(
+            deps.as_mut(),
+            env_at(at),
+            message_info(&addr("server"), &[]),
+            ExecuteMsg::CreateGame {
+                player_a: addr(player_a).to_string(),
+                player_b: addr(player_b).to_string(),
+                wager_amount: 0,
+                game_id: None,
+                idempotency_key: None,
+                server_match_id: None,
+            },
+        )
+        .unwrap();
+        res.attributes
+            .iter()
+            .find(|a| a.key == "game_id")
+            .unwrap()
+            .value
+            .clone()
+    }
+
+    fn report(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        game_id: &str,
+        winner: &str,
+    ) {
+        report_as(deps, game_id, winner, "normal");
+    }
+
+    fn player_games(
+        deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        player: &str,
+        start_after: Option<String>,
+        limit: u32,
+        status: Option<&str>,
+        start_time: Option<u64>,
+        end_time: Option<u64>,
+    ) -> Vec<String> {
+        let res: GamesListResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::PlayerGames {
+                    address: addr(player).to_string(),
+                    start_after,
+                    limit: Some(limit),
+                    status: status.map(|s| s.to_string()),
+                    start_time,
+                    end_time,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        res.games.into_iter().map(|g| g.game_id).collect()
+    }
+
+    #[test]
+    fn test_player_games_pages_with_cursor() {
+        let mut deps = setup();
+        // alice alternates seats; bob's game with carol must not show up
+        let g1 = create_game(&mut deps, "alice", "bob", 100);
+        let g2 = create_game(&mut deps, "bob", "alice", 200);
+        let _g3 = create_game(&mut deps, "bob", "carol", 300);
+        let g4 = create_game(&mut deps, "alice", "carol", 400);
+        let g5 = create_game(&mut deps, "carol", "alice", 500);
+
+        let page1 = player_games(&deps, "alice", None, 2, None, None, None);
+        assert_eq!(page1, vec![g5.clone(), g4.clone()]);
+
+        let page2 = player_games(&deps, "alice", Some(g4), 2, None, None, None);
+        assert_eq!(page2, vec![g2.clone(), g1.clone()]);
+
+        let page3 = player_games(&deps, "alice", Some(g1), 2, None, None, None);
+        assert!(page3.is_empty());
+    }
+
+    #[test]
+    fn test_player_games_filters() {
+        let mut deps = setup();
+        let g1 = create_game(&mut deps, "alice", "bob", 100);
+        let g2 = create_game(&mut deps, "bob", "alice", 200);
+        let g3 = create_game(&mut deps, "alice", "carol", 300);
+        report(&mut deps, &g1, "alice");
+        report(&mut deps, &g3, "carol");
+
+        let completed = player_games(&deps, "alice", None, 10, Some("Completed"), None, None);
+        assert_eq!(completed, vec![g3.clone(), g1.clone()]);
+
+        let created = player_games(&deps, "alice", None, 10, Some("Created"), None, None);
+        assert_eq!(created, vec![g2.clone()]);
+
+        let window = player_games(&deps, "alice", None, 10, None, Some(150), Some(300));
+        assert_eq!(window, vec![g3.clone(), g2.clone()]);
+
+        // Cursor and end_time combine; the tighter bound wins
+        let window = player_games(
+            &deps,
+            "alice",
+            Some(g3.clone()),
+            10,
+            None,
+            None,
+            Some(1_000),
+        );
+        assert_eq!(window, vec![g2, g1.clone()]);
+
+        // Status pages skip straight past games in other statuses
+        let page = player_games(&deps, "alice", Some(g3), 1, Some("Completed"), None, None);
+        assert_eq!(page, vec![g1]);
+    }
+
+    #[test]
+    fn test_migrate_is_versioned_and_batched() {
+        let mut deps = setup();
+        let g1 = create_game(&mut deps, "alice", "bob", 100);
+        create_game(&mut deps, "bob", "carol", 200);
+        create_game(&mut deps, "carol", "alice", 300);
+        report(&mut deps, &g1, "alice");
+
+        // A contract deployed before versioning has no stored version
+        cw2::CONTRACT.remove(deps.as_mut().storage);
+        let mut batches = vec![];
+        loop {
+            let res = migrate(deps.as_mut(), mock_env(), MigrateMsg { limit: Some(2) }).unwrap();
+            batches.push(attr(&res, "migrated").unwrap());
+            if attr(&res, "migration_complete").unwrap() == "true" {
+                break;
+            }
+        }
+        // Three games, then three players and the two games each replays
+        // to backfill their stats, two entries at a time
+        assert_eq!(batches, vec!["2", "2", "2", "2", "2", "2", "0"]);
+        assert_eq!(
+            cw2::get_contract_version(&deps.storage).unwrap().version,
+            CONTRACT_VERSION
+        );
+        assert!(MIGRATION.may_load(&deps.storage).unwrap().is_none());
+
+        // Nothing to do once the stored version is current
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg { limit: None }).unwrap();
+        assert_eq!(attr(&res, "migrated"), None);
+        assert_eq!(attr(&res, "migration_complete"), Some("true".to_string()));
+
+        cw2::set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "99.0.0").unwrap();
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg { limit: None }).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidMigration { .. }));
+    }
+
+    #[test]
+    fn test_report_result_applies_fibs_rating() {
+        let mut deps = setup();
+        let game_id = create_game(&mut deps, "alice", "bob", 100);
+
+        // alice is an established 2100 player, bob a newcomer
+        let mut alice = PLAYER_STATS.load(&deps.storage, &addr("alice")).unwrap();
+        alice.rating = 210_000;
+        alice.experience = 1_000;
+        PLAYER_STATS
+            .save(&mut deps.storage, &addr("alice"), &alice)
+            .unwrap();
+
+        report(&mut deps, &game_id, "bob");
+
+        // P(upset) ~= 0.666: bob (K = 5) gains 13.32, alice (K = 1) loses 2.66
+        let alice = PLAYER_STATS.load(&deps.storage, &addr("alice")).unwrap();
+        let bob = PLAYER_STATS.load(&deps.storage, &addr("bob")).unwrap();
+        assert_eq!(alice.rating, 210_000 - 266);
+        assert_eq!(bob.rating, DEFAULT_RATING + 1_332);
+        assert_eq!(bob.experience, 1);
+    }
+
+    #[test]
+    fn test_update_rating_params() {
+        let mut deps = setup();
+        let params = RatingParams {
+            base_factor: 8,
+            ..RatingParams::default()
+        };
+        let msg = ExecuteMsg::UpdateConfig {
+            escrow_contract: None,
+            rating_params: Some(params.clone()),
+            challenge_period_seconds: None,
+            game_ttl_seconds: None,
+            dispute_period_seconds: None,
+            achievement_nft_contract: None,
+            server_address: None,
+            arbiter: None,
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            msg.clone(),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("admin"), &[]),
+            msg,
+        )
+        .unwrap();
+        assert_eq!(CONFIG.load(&deps.storage).unwrap().rating_params, params);
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("admin"), &[]),
+            ExecuteMsg::UpdateConfig {
+                escrow_contract: None,
+                rating_params: Some(RatingParams {
+                    base_factor: 0,
+                    ..RatingParams::default()
+                }),
+                challenge_period_seconds: None,
+                game_ttl_seconds: None,
+                dispute_period_seconds: None,
+                achievement_nft_contract: None,
+                server_address: None,
+                arbiter: None,
+            },
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::InvalidRatingParams { .. }
+        ));
+    }
+
+    /// The escrow's reply to a settlement of two `stake` deposits
+    #[allow(deprecated)]
+    fn settle_reply(escrow_id: &str, payout: u128, rake: u128, stake: u128, cube: u32) -> Reply {
+        let event = cosmwasm_std::Event::new("wasm")
+            .add_attribute("_contract_address", addr("escrow").to_string())
+            .add_attribute("action", "settle")
+            .add_attribute("payout", payout.to_string())
+            .add_attribute("rake", rake.to_string())
+            .add_attribute("cube_value", cube.to_string())
+            .add_attribute("winner_stake", stake.to_string())
+            .add_attribute("loser_stake", stake.to_string());
+        Reply {
+            id: SETTLE_ESCROW_REPLY_ID,
+            payload: Binary::from(escrow_id.as_bytes()),
+            gas_used: 0,
+            result: cosmwasm_std::SubMsgResult::Ok(cosmwasm_std::SubMsgResponse {
+                events: vec![event],
+                data: None,
+                msg_responses: vec![],
+            }),
+        }
+    }
+
+    fn report_as(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        game_id: &str,
+        winner: &str,
+        result_type: &str,
+    ) -> Response {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            ExecuteMsg::ReportResult {
+                game_id: game_id.to_string(),
+                winner: addr(winner).to_string(),
+                result_type: result_type.to_string(),
+                move_count: 40,
+                dice_reveals: None,
+                move_log: None,
+                move_log_signatures: None,
+                cube: None,
+            },
+        )
+        .unwrap()
+    }
+
+    fn attr(res: &Response, key: &str) -> Option<String> {
+        res.attributes
+            .iter()
+            .find(|a| a.key == key)
+            .map(|a| a.value.clone())
+    }
+
+    #[test]
+    fn test_match_scores_games_and_settles_once() {
+        let mut deps = setup();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("admin"), &[]),
+            ExecuteMsg::UpdateConfig {
+                escrow_contract: Some(addr("escrow").to_string()),
+                rating_params: None,
+                challenge_period_seconds: None,
+                game_ttl_seconds: None,
+                dispute_period_seconds: None,
+                achievement_nft_contract: None,
+                server_address: None,
+                arbiter: None,
+            },
+        )
+        .unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            ExecuteMsg::CreateMatch {
+                player_a: addr("alice").to_string(),
+                player_b: addr("bob").to_string(),
+                match_length: 3,
+                wager_amount: 5_000_000,
+            },
+        )
+        .unwrap();
+        // One escrow for the whole match
+        assert_eq!(res.messages.len(), 1);
+        let match_id = attr(&res, "match_id").unwrap();
+        let game_1 = attr(&res, "game_id").unwrap();
+
+        // alice wins a gammon: 2-0, one away from the 3-point target
+        let res = report_as(&mut deps, &game_1, "alice", "gammon");
+        assert!(res.messages.is_empty());
+        assert_eq!(attr(&res, "crawford").unwrap(), "true");
+        let game_2 = attr(&res, "next_game_id").unwrap();
+
+        // Ratings do not move mid-match
+        let alice = PLAYER_STATS.load(&deps.storage, &addr("alice")).unwrap();
+        assert_eq!(alice.rating, DEFAULT_RATING);
+        assert_eq!(alice.games_won, 1);
+
+        // bob wins the Crawford game: 2-1, post-Crawford
+        let res = report_as(&mut deps, &game_2, "bob", "normal");
+        assert_eq!(attr(&res, "crawford").unwrap(), "false");
+        let game_3 = attr(&res, "next_game_id").unwrap();
+        let bg_match = MATCHES.load(&deps.storage, &match_id).unwrap();
+        assert!(bg_match.crawford_played);
+        // Deposits cover the whole match, so later games start immediately
+        let next_game = GAMES.load(&deps.storage, &game_3).unwrap();
+        assert_eq!(next_game.status, GameStatus::InProgress);
+        assert_eq!(next_game.match_id, Some(match_id.clone()));
+
+        // alice closes it out 3-1
+        let res = report_as(&mut deps, &game_3, "alice", "normal");
+        assert_eq!(attr(&res, "match_complete").unwrap(), "true");
+        assert!(attr(&res, "next_game_id").is_none());
+        assert_eq!(res.messages.len(), 1);
+
+        let bg_match = MATCHES.load(&deps.storage, &match_id).unwrap();
+        assert_eq!(bg_match.status, MatchStatus::Completed);
+        assert_eq!(bg_match.winner, Some(addr("alice")));
+        assert_eq!((bg_match.score_a, bg_match.score_b), (3, 1));
+        assert_eq!(bg_match.game_ids, vec![game_1, game_2, game_3]);
+
+        // Rated once, as a 3-point match between two new players
+        let alice = PLAYER_STATS.load(&deps.storage, &addr("alice")).unwrap();
+        let bob = PLAYER_STATS.load(&deps.storage, &addr("bob")).unwrap();
+        assert!(alice.rating > DEFAULT_RATING);
+        assert_eq!(alice.experience, 3);
+        assert_eq!(bob.games_played, 3);
+        assert_eq!(alice.total_wagered, 5_000_000);
+    }
+
+    #[test]
+    fn test_abandoning_a_match_game_forfeits_the_match() {
+        let mut deps = setup();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            ExecuteMsg::CreateMatch {
+                player_a: addr("alice").to_string(),
+                player_b: addr("bob").to_string(),
+                match_length: 5,
+                wager_amount: 0,
+            },
+        )
+        .unwrap();
+        let match_id = attr(&res, "match_id").unwrap();
+        let game_1 = attr(&res, "game_id").unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            ExecuteMsg::ReportAbandonment {
+                game_id: game_1,
+                abandoner: addr("alice").to_string(),
+                dice_reveals: None,
+                winner_signature: None,
+            },
+        )
+        .unwrap();
+
+        let bg_match = MATCHES.load(&deps.storage, &match_id).unwrap();
+        assert_eq!(bg_match.status, MatchStatus::Completed);
+        assert_eq!(bg_match.winner, Some(addr("bob")));
+        let bob = PLAYER_STATS.load(&deps.storage, &addr("bob")).unwrap();
+        assert_eq!(bob.experience, 5);
+    }
+
+    #[test]
+    fn test_dice_commit_reveal_and_verify_roll() {
+        let mut deps = setup();
+        let game_id = create_game(&mut deps, "alice", "bob", 100);
+        let server = message_info(&addr("server"), &[]);
+
+        // Each turn has its own seed, committed to before the roll
+        let seeds: Vec<String> = (1..=3)
+            .map(|turn| format!("{turn:02}").repeat(32))
+            .collect();
+        let reveals: Vec<DiceReveal> = seeds
+            .iter()
+            .enumerate()
+            .map(|(i, seed)| DiceReveal {
+                turn: i as u32 + 1,
+                commit_hash: dice::commit_hash(seed),
+                server_seed: seed.clone(),
+                client_seed: addr("alice").to_string(),
+            })
+            .collect();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            server.clone(),
+            ExecuteMsg::StartGame {
+                game_id: game_id.clone(),
+                commit_dice: Some(true),
+            },
+        )
+        .unwrap();
+
+        let report = |reveals: Option<Vec<DiceReveal>>| ExecuteMsg::ReportResult {
+            game_id: game_id.clone(),
+            winner: addr("alice").to_string(),
+            result_type: "normal".to_string(),
+            move_count: 3,
+            dice_reveals: reveals,
+            move_log: None,
+            move_log_signatures: None,
+            cube: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), server.clone(), report(None));
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::DiceRevealRequired { .. }
+        ));
+
+        let mut forged = reveals.clone();
+        forged[1].server_seed = "cd".repeat(32);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            server.clone(),
+            report(Some(forged)),
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::InvalidDiceReveal { turn: 2, .. }
+        ));
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            server.clone(),
+            report(Some(reveals.clone())),
+        )
+        .unwrap();
+
+        let res: VerifyRollResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::VerifyRoll {
+                    game_id: game_id.clone(),
+                    turn: 2,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(res.verified);
+        assert_eq!(res.server_seed, seeds[1]);
+        assert_eq!(res.commit_hash, dice::commit_hash(&seeds[1]));
+        assert_eq!(
+            res.dice,
+            dice::derive_dice(&seeds[1], addr("alice").as_str(), 2)
+        );
+
+        // Reveals for a game without committed dice prove nothing
+        let uncommitted = create_game(&mut deps, "alice", "bob", 200);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            server.clone(),
+            ExecuteMsg::ReportResult {
+                game_id: uncommitted,
+                winner: addr("alice").to_string(),
+                result_type: "normal".to_string(),
+                move_count: 3,
+                dice_reveals: Some(reveals.clone()),
+                move_log: None,
+                move_log_signatures: None,
+                cube: None,
+            },
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::DiceNotCommitted { .. }
+        ));
+
+        // An abandoned committed game keeps the turns it rolled
+        let abandoned = create_game(&mut deps, "alice", "bob", 300);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            server.clone(),
+            ExecuteMsg::StartGame {
+                game_id: abandoned.clone(),
+                commit_dice: Some(true),
+            },
+        )
+        .unwrap();
+        let abandon = |dice_reveals| ExecuteMsg::ReportAbandonment {
+            game_id: abandoned.clone(),
+            abandoner: addr("bob").to_string(),
+            dice_reveals,
+            winner_signature: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), server.clone(), abandon(None));
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::DiceRevealRequired { .. }
+        ));
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            server,
+            abandon(Some(reveals[..2].to_vec())),
+        )
+        .unwrap();
+        let res = query_verify_roll(deps.as_ref(), abandoned.clone(), 2).unwrap();
+        assert!(res.verified);
+        assert!(query_verify_roll(deps.as_ref(), abandoned, 3).is_err());
+    }
+
+    #[test]
+    fn test_move_log_root_and_verify_move_proof() {
+        let mut deps = setup();
+        let game_id = create_game(&mut deps, "alice", "bob", 100);
+
+        // Two-leaf log: the proof for each leaf is the other leaf's hash
+        let leaves: [&[u8]; 2] = [b"roll 3-1", b"8/5 6/5"];
+        let root = HexBinary::from(merkle::root(&leaves).unwrap().as_slice());
+        let sibling = HexBinary::from(merkle::leaf_hash(leaves[0]).as_slice());
+
+        let report = |root: HexBinary| ExecuteMsg::ReportResult {
+            game_id: game_id.clone(),
+            winner: addr("alice").to_string(),
+            result_type: "normal".to_string(),
+            move_count: 1,
+            dice_reveals: None,
+            move_log: Some(MoveLogCommitment {
+                root,
+                leaf_count: 2,
+            }),
+            move_log_signatures: None,
+            cube: None,
+        };
+        let server = message_info(&addr("server"), &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            server.clone(),
+            report(HexBinary::from(b"short")),
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::InvalidMoveLog { .. }
+        ));
+        execute(deps.as_mut(), mock_env(), server, report(root.clone())).unwrap();
+
+        let game = query_game(deps.as_ref(), game_id.clone()).unwrap();
+        assert_eq!(game.move_log.unwrap().root, root);
+
+        let verify = |leaf: &[u8]| -> bool {
+            let res: VerifyMoveProofResponse = from_json(
+                query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::VerifyMoveProof {
+                        game_id: game_id.clone(),
+                        index: 1,
+                        leaf: Binary::from(leaf),
+                        proof: vec![sibling.clone()],
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            res.valid
+        };
+        assert!(verify(leaves[1]));
+        assert!(!verify(b"8/4 6/5"));
+    }
+
+    #[test]
+    fn test_dispute_overturns_result_and_resettles() {
+        use k256::ecdsa::{signature::hazmat::PrehashSigner, Signature, SigningKey};
+
+        let mut deps = setup();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("admin"), &[]),
+            ExecuteMsg::UpdateConfig {
+                escrow_contract: Some(addr("escrow").to_string()),
+                rating_params: None,
+                challenge_period_seconds: None,
+                game_ttl_seconds: None,
+                dispute_period_seconds: None,
+                achievement_nft_contract: None,
+                server_address: None,
+                arbiter: None,
+            },
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            ExecuteMsg::CreateGame {
+                player_a: addr("alice").to_string(),
+                player_b: addr("bob").to_string(),
+                wager_amount: 1_000_000,
+                game_id: None,
+                idempotency_key: None,
+                server_match_id: None,
+            },
+        )
+        .unwrap();
+        let game_id = attr(&res, "game_id").unwrap();
+
+        // Black (bob) moves onto white's made 5 point on turn 2, so alice
+        // should win, but the server reports bob as the winner
+        let mv = |from, to, die| CheckerMove { from, to, die };
+        let turns = vec![
+            LoggedTurn {
+                turn: 1,
+                dice: [3, 1],
+                moves: vec![mv(8, 5, 3), mv(6, 5, 1)],
+            },
+            LoggedTurn {
+                turn: 2,
+                dice: [4, 2],
+                moves: vec![mv(1, 5, 4), mv(12, 14, 2)],
+            },
+        ];
+        let leaves: Vec<Vec<u8>> = turns.iter().map(|t| to_json_vec(t).unwrap()).collect();
+        let log = MoveLogCommitment {
+            root: HexBinary::from(merkle::root(&leaves).unwrap().as_slice()),
+            leaf_count: 2,
+        };
+
+        // Both players register keys and sign the log before it is reported
+        let keys = [
+            SigningKey::from_slice(&[1u8; 32]).unwrap(),
+            SigningKey::from_slice(&[2u8; 32]).unwrap(),
+        ];
+        for (name, key) in [("alice", &keys[0]), ("bob", &keys[1])] {
+            let pubkey = key.verifying_key().to_encoded_point(true);
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr(name), &[]),
+                ExecuteMsg::RegisterSigningKey {
+                    pubkey: Binary::from(pubkey.as_bytes()),
+                },
+            )
+            .unwrap();
+        }
+        let sign = |deps: &OwnedDeps<_, _, _>, game_id: &str, key: &SigningKey| {
+            let payload: ResultSigningPayloadResponse = from_json(
+                query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::MoveLogSigningPayload {
+                        game_id: game_id.to_string(),
+                        root: log.root.clone(),
+                        leaf_count: log.leaf_count,
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            let sig: Signature = key.sign_prehash(payload.hash.as_slice()).unwrap();
+            Binary::from(sig.to_bytes().as_slice())
+        };
+        let signatures = |deps: &OwnedDeps<_, _, _>, game_id: &str| MoveLogSignatures {
+            signature_a: sign(deps, game_id, &keys[0]),
+            signature_b: sign(deps, game_id, &keys[1]),
+        };
+        let report = |game_id: &str,
+                      winner: &str,
+                      result_type: &str,
+                      signatures: Option<MoveLogSignatures>| {
+            ExecuteMsg::ReportResult {
+                game_id: game_id.to_string(),
+                winner: addr(winner).to_string(),
+                result_type: result_type.to_string(),
+                move_count: 4,
+                dice_reveals: None,
+                move_log: Some(log.clone()),
+                move_log_signatures: signatures,
+                cube: None,
+            }
+        };
+
+        // The log needs bob's signature too
+        let signed = signatures(&deps, &game_id);
+        let forged = MoveLogSignatures {
+            signature_b: signed.signature_a.clone(),
+            ..signed.clone()
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            report(&game_id, "bob", "normal", Some(forged)),
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::InvalidSignature {
+                signer: addr("bob").to_string()
+            }
+        );
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            report(&game_id, "bob", "normal", Some(signed)),
+        )
+        .unwrap();
+        reply(
+            deps.as_mut(),
+            mock_env(),
+            settle_reply(&game_id, 1_950_000, 50_000, 1_000_000, 1),
+        )
+        .unwrap();
+        let game = query_game(deps.as_ref(), game_id.clone()).unwrap();
+        assert!(game.move_log_signed);
+
+        let dispute = |game_id: &str, turns: Vec<LoggedTurn>| ExecuteMsg::DisputeResult {
+            game_id: game_id.to_string(),
+            turns,
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("carol"), &[]),
+            dispute(&game_id, turns.clone()),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+
+        // A log that differs from the committed one is rejected
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("alice"), &[]),
+            dispute(&game_id, turns[..1].to_vec()),
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::InvalidMoveLog { .. }
+        ));
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("alice"), &[]),
+            dispute(&game_id, turns.clone()),
+        )
+        .unwrap();
+        assert_eq!(attr(&res, "overturned").unwrap(), "true");
+        assert_eq!(attr(&res, "winner"), Some(addr("alice").to_string()));
+        // Bob's first win goes to alice
+        assert_eq!(
+            attr(&res, "revoked_achievement"),
+            Some(format!("first-win:{}", addr("bob")))
+        );
+        assert_eq!(
+            attr(&res, "achievement"),
+            Some(format!("first-win:{}", addr("alice")))
+        );
+        // The escrow is asked to re-settle to alice
+        assert_eq!(res.messages.len(), 1);
+
+        let game = query_game(deps.as_ref(), game_id.clone()).unwrap();
+        assert_eq!(game.winner, Some(addr("alice")));
+
+        // Ratings and wins are swapped back as if alice had won
+        let alice = query_player_stats(deps.as_ref(), addr("alice").to_string()).unwrap();
+        let bob = query_player_stats(deps.as_ref(), addr("bob").to_string()).unwrap();
+        assert_eq!((alice.games_won, bob.games_won), (1, 0));
+        assert!(alice.rating > DEFAULT_RATING && bob.rating < DEFAULT_RATING);
+        assert_eq!((alice.experience, bob.experience), (1, 1));
+        assert_eq!((alice.total_staked, bob.total_staked), (0, 0));
+        // and so are the streaks, recent form and the peak bob's win set
+        assert_eq!((alice.win_streak, alice.best_win_streak), (1, 1));
+        assert_eq!((bob.win_streak, bob.best_win_streak), (0, 0));
+        assert_eq!((alice.recent_results, bob.recent_results), (1, 0));
+        assert_eq!((alice.recent_games, bob.recent_games), (1, 1));
+        assert_eq!(alice.peak_rating, alice.rating);
+        assert_eq!(
+            (bob.peak_rating, bob.peak_rating_at),
+            (DEFAULT_RATING, None)
+        );
+
+        // The escrow already paid bob, so its re-settlement pays nothing and
+        // books bob's payout as owed to alice
+        let mut resettled = settle_reply(&game_id, 0, 0, 1_000_000, 1);
+        if let cosmwasm_std::SubMsgResult::Ok(response) = &mut resettled.result {
+            response.events[0] = response.events[0].clone().add_attribute("owed", "1950000");
+        }
+        reply(deps.as_mut(), mock_env(), resettled).unwrap();
+        let alice = query_player_stats(deps.as_ref(), addr("alice").to_string()).unwrap();
+        let bob = query_player_stats(deps.as_ref(), addr("bob").to_string()).unwrap();
+        assert_eq!((alice.total_won, bob.total_won), (0, 1_950_000));
+        assert_eq!((alice.rake_paid, bob.rake_paid), (0, 50_000));
+        assert_eq!((alice.total_owed, bob.total_owed), (1_950_000, 0));
+        assert_eq!(
+            (alice.total_staked, bob.total_staked),
+            (1_000_000, 1_000_000)
+        );
+        assert_eq!(
+            (alice.net_winnings, bob.net_winnings),
+            (-1_000_000, 950_000)
+        );
+
+        let recorded: DisputeResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Dispute {
+                    game_id: game_id.clone(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(recorded.reported_winner, addr("bob"));
+        assert_eq!(recorded.illegal_turn, Some(2));
+
+        // Only one dispute per game
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("bob"), &[]),
+            dispute(&game_id, turns.clone()),
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::DisputeNotAllowed { .. }
+        ));
+
+        // The right winner with the wrong result type is overturned too, and
+        // the escrow is sent the corrected multiplier
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            ExecuteMsg::CreateGame {
+                player_a: addr("alice").to_string(),
+                player_b: addr("bob").to_string(),
+                wager_amount: 1_000_000,
+                game_id: None,
+                idempotency_key: None,
+                server_match_id: None,
+            },
+        )
+        .unwrap();
+        let game_id = attr(&res, "game_id").unwrap();
+        let signed = signatures(&deps, &game_id);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            report(&game_id, "alice", "gammon", Some(signed)),
+        )
+        .unwrap();
+        reply(
+            deps.as_mut(),
+            mock_env(),
+            settle_reply(&game_id, 1_950_000, 50_000, 2_000_000, 1),
+        )
+        .unwrap();
+
+        // Disputes close once the configured period after completion ends
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("admin"), &[]),
+            ExecuteMsg::UpdateConfig {
+                escrow_contract: None,
+                rating_params: None,
+                challenge_period_seconds: None,
+                game_ttl_seconds: None,
+                dispute_period_seconds: Some(600),
+                achievement_nft_contract: None,
+                server_address: None,
+                arbiter: None,
+            },
+        )
+        .unwrap();
+        let late = env_at(mock_env().block.time.seconds() + 601);
+        let res = execute(
+            deps.as_mut(),
+            late,
+            message_info(&addr("bob"), &[]),
+            dispute(&game_id, turns.clone()),
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::DisputeNotAllowed { .. }
+        ));
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("bob"), &[]),
+            dispute(&game_id, turns.clone()),
+        )
+        .unwrap();
+        assert_eq!(attr(&res, "overturned").unwrap(), "true");
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].id, SETTLE_ESCROW_REPLY_ID);
+        assert!(
+            matches!(&res.messages[0].msg, CosmosMsg::Wasm(WasmMsg::Execute { msg, .. })
+            if *msg == to_json_binary(&EscrowExecuteMsg::Resettle {
+                game_id: game_id.clone(),
+                winner: addr("alice").to_string(),
+                multiplier: 1,
+            })
+            .unwrap())
+        );
+        let game = query_game(deps.as_ref(), game_id.clone()).unwrap();
+        assert_eq!(game.winner, Some(addr("alice")));
+        assert_eq!(game.points_won, 1);
+
+        // The single-stake re-settlement replaces the gammon's doubled
+        // stakes, and the payout alice was already sent stands
+        let staked = |deps: &OwnedDeps<_, _, _>| {
+            let alice = query_player_stats(deps.as_ref(), addr("alice").to_string()).unwrap();
+            let bob = query_player_stats(deps.as_ref(), addr("bob").to_string()).unwrap();
+            (alice.total_staked, bob.total_staked, alice.total_won)
+        };
+        let (alice_staked, bob_staked, alice_won) = staked(&deps);
+        reply(
+            deps.as_mut(),
+            mock_env(),
+            settle_reply(&game_id, 0, 0, 1_000_000, 1),
+        )
+        .unwrap();
+        assert_eq!(
+            staked(&deps),
+            (alice_staked + 1_000_000, bob_staked + 1_000_000, alice_won)
+        );
+        let settlement = SETTLEMENTS.load(&deps.storage, &game_id).unwrap();
+        assert_eq!((settlement.payout, settlement.rake), (1_950_000, 50_000));
+        assert_eq!(
+            (settlement.winner_stake, settlement.loser_stake),
+            (1_000_000, 1_000_000)
+        );
+
+        // Bob claims alice passed his double; the escrow settling at a
+        // different cube than the reported one re-scores the game at its cube
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            ExecuteMsg::CreateGame {
+                player_a: addr("alice").to_string(),
+                player_b: addr("bob").to_string(),
+                wager_amount: 1_000_000,
+                game_id: None,
+                idempotency_key: None,
+                server_match_id: None,
+            },
+        )
+        .unwrap();
+        let game_id = attr(&res, "game_id").unwrap();
+        let signed = signatures(&deps, &game_id);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            {
+                let mut msg = report(&game_id, "bob", "normal", Some(signed));
+                if let ExecuteMsg::ReportResult { cube, .. } = &mut msg {
+                    *cube = Some(CubeReport {
+                        value: 1,
+                        owner: None,
+                        doubles_a: 0,
+                        doubles_b: 1,
+                        passed: true,
+                    });
+                }
+                msg
+            },
+        )
+        .unwrap();
+        let res = reply(
+            deps.as_mut(),
+            mock_env(),
+            settle_reply(&game_id, 3_900_000, 100_000, 2_000_000, 2),
+        )
+        .unwrap();
+        assert_eq!(attr(&res, "cube_corrected").unwrap(), "1:2");
+        let game = query_game(deps.as_ref(), game_id.clone()).unwrap();
+        assert_eq!((game.cube.unwrap().value, game.points_won), (2, 2));
+        let bob = query_player_stats(deps.as_ref(), addr("bob").to_string()).unwrap();
+        assert_eq!(bob.points_won, 2);
+        let stats = |deps: &OwnedDeps<_, _, _>, player: &str| {
+            let stats = query_player_stats(deps.as_ref(), addr(player).to_string()).unwrap();
+            (stats.doubles_offered, stats.doubles_passed)
+        };
+        assert_eq!(
+            (stats(&deps, "bob"), stats(&deps, "alice")),
+            ((1, 0), (0, 1))
+        );
+
+        // The replay decides the game on bob's illegal move, so the pass it
+        // claimed never ended it and is voided with bob's double
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("alice"), &[]),
+            dispute(&game_id, turns.clone()),
+        )
+        .unwrap();
+        assert_eq!(attr(&res, "overturned").unwrap(), "true");
+        let game = query_game(deps.as_ref(), game_id).unwrap();
+        assert_eq!(game.winner, Some(addr("alice")));
+        let cube = game.cube.unwrap();
+        assert!(!cube.passed);
+        assert_eq!((cube.doubles_a, cube.doubles_b), (0, 0));
+        assert_eq!(
+            (stats(&deps, "bob"), stats(&deps, "alice")),
+            ((0, 0), (0, 0))
+        );
+
+        // A log only the server committed to cannot settle a dispute
+        let game_id = create_game(&mut deps, "alice", "carol", mock_env().block.time.seconds());
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            report(&game_id, "carol", "normal", None),
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("alice"), &[]),
+            dispute(&game_id, turns),
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::DisputeNotAllowed { .. }
+        ));
+    }
+
+    #[test]
+    fn test_escrow_cube_settles_abandoned_and_held_games() {
+        let mut deps = setup();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("admin"), &[]),
+            ExecuteMsg::UpdateConfig {
+                escrow_contract: Some(addr("escrow").to_string()),
+                rating_params: None,
+                challenge_period_seconds: Some(600),
+                game_ttl_seconds: None,
+                dispute_period_seconds: None,
+                achievement_nft_contract: None,
+                server_address: None,
+                arbiter: None,
+            },
+        )
+        .unwrap();
+        let create = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>| {
+            let res = execute(
+                deps.as_mut(),
+                env_at(100),
+                message_info(&addr("server"), &[]),
+                ExecuteMsg::CreateGame {
+                    player_a: addr("alice").to_string(),
+                    player_b: addr("bob").to_string(),
+                    wager_amount: 1_000_000,
+                    game_id: None,
+                    idempotency_key: None,
+                    server_match_id: None,
+                },
+            )
+            .unwrap();
+            attr(&res, "game_id").unwrap()
+        };
+        let abandoned = create(&mut deps);
+        let held = create(&mut deps);
+
+        // bob abandons after alice's double was taken in the escrow; the
+        // server reports no cube for an abandonment
+        execute(
+            deps.as_mut(),
+            env_at(1_000),
+            message_info(&addr("server"), &[]),
+            ExecuteMsg::ReportAbandonment {
+                game_id: abandoned.clone(),
+                abandoner: addr("bob").to_string(),
+                dice_reveals: None,
+                winner_signature: None,
+            },
+        )
+        .unwrap();
+        // nor does it for a gammon won at a cube of 2
+        execute(
+            deps.as_mut(),
+            env_at(1_000),
+            message_info(&addr("server"), &[]),
+            ExecuteMsg::ReportResult {
+                game_id: held.clone(),
+                winner: addr("alice").to_string(),
+                result_type: "gammon".to_string(),
+                move_count: 40,
+                dice_reveals: None,
+                move_log: None,
+                move_log_signatures: None,
+                cube: None,
+            },
+        )
+        .unwrap();
+
+        for game_id in [&abandoned, &held] {
+            let res = execute(
+                deps.as_mut(),
+                env_at(1_600),
+                message_info(&addr("anyone"), &[]),
+                ExecuteMsg::FinalizeResult {
+                    game_id: game_id.clone(),
+                },
+            )
+            .unwrap();
+            assert_eq!(res.messages[0].id, SETTLE_ESCROW_REPLY_ID);
+            reply(
+                deps.as_mut(),
+                mock_env(),
+                settle_reply(game_id, 3_900_000, 100_000, 2_000_000, 2),
+            )
+            .unwrap();
+            let settlement = SETTLEMENTS.load(&deps.storage, game_id).unwrap();
+            assert_eq!(settlement.cube_value, 2);
+        }
+
+        // The abandonment settles without being scored
+        let game = query_game(deps.as_ref(), abandoned).unwrap();
+        assert_eq!((game.status.as_str(), game.points_won), ("Abandoned", 0));
+        // The held gammon is scored at the escrow's cube
+        let game = query_game(deps.as_ref(), held).unwrap();
+        assert_eq!((game.cube.unwrap().value, game.points_won), (2, 4));
+        let alice = query_player_stats(deps.as_ref(), addr("alice").to_string()).unwrap();
+        assert_eq!((alice.points_won, alice.gammons_won), (4, 1));
+        assert_eq!(alice.total_won, 7_800_000);
+    }
+
+    #[test]
+    fn test_escrow_forfeit_is_booked() {
+        let mut deps = setup();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("admin"), &[]),
+            ExecuteMsg::UpdateConfig {
+                escrow_contract: Some(addr("escrow").to_string()),
+                rating_params: None,
+                challenge_period_seconds: None,
+                game_ttl_seconds: None,
+                dispute_period_seconds: None,
+                achievement_nft_contract: None,
+                server_address: None,
+                arbiter: None,
+            },
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            ExecuteMsg::CreateGame {
+                player_a: addr("alice").to_string(),
+                player_b: addr("bob").to_string(),
+                wager_amount: 500_000,
+                game_id: None,
+                idempotency_key: None,
+                server_match_id: None,
+            },
+        )
+        .unwrap();
+        let game_id = attr(&res, "game_id").unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("escrow"), &[]),
+            ExecuteMsg::EscrowActivated {
+                game_id: game_id.clone(),
+            },
+        )
+        .unwrap();
+
+        // bob passes alice's redouble to 4 at a cube of 2
+        let forfeited = ExecuteMsg::EscrowForfeited {
+            game_id: game_id.clone(),
+            winner: addr("alice").to_string(),
+            loser: addr("bob").to_string(),
+            payout: 1_900_000,
+            rake: 100_000,
+            winner_stake: 1_000_000,
+            loser_stake: 1_000_000,
+            cube_value: 2,
+        };
+
+        // Only the escrow can report a forfeit
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            forfeited.clone(),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("escrow"), &[]),
+            forfeited,
+        )
+        .unwrap();
+        // The escrow has paid out already and is not asked to settle
+        assert!(res
+            .messages
+            .iter()
+            .all(|sub| sub.id != SETTLE_ESCROW_REPLY_ID));
+        let alice = query_player_stats(deps.as_ref(), addr("alice").to_string()).unwrap();
+        let bob = query_player_stats(deps.as_ref(), addr("bob").to_string()).unwrap();
+        assert_eq!((alice.total_won, alice.rake_paid), (1_900_000, 100_000));
+        assert_eq!(
+            (alice.net_winnings, bob.net_winnings),
+            (900_000, -1_000_000)
+        );
+
+        // The game is complete, won by the doubler at the passed cube, and
+        // rated like any other result
+        let game = query_game(deps.as_ref(), game_id.clone()).unwrap();
+        assert_eq!(game.status, "Completed");
+        assert_eq!((game.winner, game.points_won), (Some(addr("alice")), 2));
+        assert_eq!(
+            (alice.games_played, alice.games_won, alice.points_won),
+            (1, 1, 2)
+        );
+        assert_eq!((bob.games_played, bob.win_streak), (1, 0));
+        assert!(alice.rating > DEFAULT_RATING && bob.rating < DEFAULT_RATING);
+        let settlement = SETTLEMENTS.load(&deps.storage, &game_id).unwrap();
+        assert_eq!((settlement.payout, settlement.cube_value), (1_900_000, 2));
+
+        // so the server's report of it cannot settle the escrow again
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            ExecuteMsg::ReportResult {
+                game_id,
+                winner: addr("alice").to_string(),
+                result_type: "normal".to_string(),
+                move_count: 30,
+                dice_reveals: None,
+                move_log: None,
+                move_log_signatures: None,
+                cube: None,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidGameStatus { .. }));
+    }
+
+    #[test]
+    fn test_submit_co_signed_result() {
+        use k256::ecdsa::{signature::hazmat::PrehashSigner, Signature, SigningKey};
+
+        let mut deps = setup();
+        let game_id = create_game(&mut deps, "alice", "bob", 100);
+
+        let keys = [
+            SigningKey::from_slice(&[1u8; 32]).unwrap(),
+            SigningKey::from_slice(&[2u8; 32]).unwrap(),
+        ];
+        for (name, key) in [("alice", &keys[0]), ("bob", &keys[1])] {
+            let pubkey = key.verifying_key().to_encoded_point(true);
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr(name), &[]),
+                ExecuteMsg::RegisterSigningKey {
+                    pubkey: Binary::from(pubkey.as_bytes()),
+                },
+            )
+            .unwrap();
+        }
+
+        let payload: ResultSigningPayloadResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ResultSigningPayload {
+                    game_id: game_id.clone(),
+                    winner: addr("bob").to_string(),
+                    result_type: "gammon".to_string(),
+                    nonce: 1,
+                    cube: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let sign = |key: &SigningKey| {
+            let sig: Signature = key.sign_prehash(payload.hash.as_slice()).unwrap();
+            Binary::from(sig.to_bytes().as_slice())
+        };
+        let submit = |signature_b: Binary| ExecuteMsg::SubmitSignedResult {
+            game_id: game_id.clone(),
+            winner: addr("bob").to_string(),
+            result_type: "gammon".to_string(),
+            nonce: 1,
+            cube: None,
+            dice_reveals: None,
+            signature_a: sign(&keys[0]),
+            signature_b,
+        };
+
+        // Alice's signature in bob's place does not verify
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("carol"), &[]),
+            submit(sign(&keys[0])),
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::InvalidSignature {
+                signer: addr("bob").to_string()
+            }
+        );
+
+        // Anyone may submit a properly co-signed result
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("carol"), &[]),
+            submit(sign(&keys[1])),
+        )
+        .unwrap();
+        assert_eq!(attr(&res, "action").unwrap(), "submit_signed_result");
+        let game = query_game(deps.as_ref(), game_id.clone()).unwrap();
+        assert_eq!(game.status, "Completed");
+        assert_eq!(game.winner, Some(addr("bob")));
+        assert_eq!(game.result_type.as_deref(), Some("Gammon"));
+
+        // The server cannot report a co-signed game on its own word
+        let other = create_game(&mut deps, "alice", "bob", 200);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            ExecuteMsg::ReportResult {
+                game_id: other.clone(),
+                winner: addr("bob").to_string(),
+                result_type: "normal".to_string(),
+                move_count: 30,
+                dice_reveals: None,
+                move_log: None,
+                move_log_signatures: None,
+                cube: None,
+            },
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::CoSignatureRequired {
+                game_id: other.clone()
+            }
+        );
+
+        // Nor an abandonment the winner did not confirm
+        let abandon = |winner_signature| ExecuteMsg::ReportAbandonment {
+            game_id: other.clone(),
+            abandoner: addr("alice").to_string(),
+            dice_reveals: None,
+            winner_signature,
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            abandon(None),
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::CoSignatureRequired {
+                game_id: other.clone()
+            }
+        );
+        let payload: ResultSigningPayloadResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::AbandonmentSigningPayload {
+                    game_id: other.clone(),
+                    abandoner: addr("alice").to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let sign_hash = |key: &SigningKey, hash: &HexBinary| {
+            let sig: Signature = key.sign_prehash(hash.as_slice()).unwrap();
+            Binary::from(sig.to_bytes().as_slice())
+        };
+        // Signed by the abandoner instead of the winner
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            abandon(Some(sign_hash(&keys[0], &payload.hash))),
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::InvalidSignature {
+                signer: addr("bob").to_string()
+            }
+        );
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            abandon(Some(sign_hash(&keys[1], &payload.hash))),
+        )
+        .unwrap();
+        let game = query_game(deps.as_ref(), other).unwrap();
+        assert_eq!(game.status, "Abandoned");
+        assert_eq!(game.winner, Some(addr("bob")));
+
+        // Nonces are per game, so another game starts again from 1
+        let third = create_game(&mut deps, "alice", "bob", 300);
+        let payload: ResultSigningPayloadResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ResultSigningPayload {
+                    game_id: third.clone(),
+                    winner: addr("alice").to_string(),
+                    result_type: "normal".to_string(),
+                    nonce: 1,
+                    cube: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("carol"), &[]),
+            ExecuteMsg::SubmitSignedResult {
+                game_id: third.clone(),
+                winner: addr("alice").to_string(),
+                result_type: "normal".to_string(),
+                nonce: 1,
+                cube: None,
+                dice_reveals: None,
+                signature_a: sign_hash(&keys[0], &payload.hash),
+                signature_b: sign_hash(&keys[1], &payload.hash),
+            },
+        )
+        .unwrap();
+        let game = query_game(deps.as_ref(), third).unwrap();
+        assert_eq!(game.winner, Some(addr("alice")));
+    }
+
+    #[test]
+    fn test_challenge_period_holds_results() {
+        let mut deps = setup();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("admin"), &[]),
+            ExecuteMsg::UpdateConfig {
+                escrow_contract: None,
+                rating_params: None,
+                challenge_period_seconds: Some(600),
+                game_ttl_seconds: None,
+                dispute_period_seconds: None,
+                achievement_nft_contract: None,
+                server_address: None,
+                arbiter: None,
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("admin"), &[]),
+            ExecuteMsg::GrantRole {
+                role: Role::Arbiter,
+                address: addr("arbiter").to_string(),
+            },
+        )
+        .unwrap();
+        let challenged = create_game(&mut deps, "alice", "bob", 100);
+        let quiet = create_game(&mut deps, "alice", "bob", 200);
+
+        let report_at = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, game_id: &str| {
+            execute(
+                deps.as_mut(),
+                env_at(1_000),
+                message_info(&addr("server"), &[]),
+                ExecuteMsg::ReportResult {
+                    game_id: game_id.to_string(),
+                    winner: addr("bob").to_string(),
+                    result_type: "normal".to_string(),
+                    move_count: 30,
+                    dice_reveals: None,
+                    move_log: None,
+                    move_log_signatures: None,
+                    cube: None,
+                },
+            )
+            .unwrap()
+        };
+        let res = report_at(&mut deps, &challenged);
+        assert_eq!(attr(&res, "challenge_deadline").unwrap(), "1600");
+        report_at(&mut deps, &quiet);
+
+        // Nothing is booked while the result is pending
+        let bob = query_player_stats(deps.as_ref(), addr("bob").to_string()).unwrap();
+        assert_eq!(bob.games_played, 0);
+        let game = query_game(deps.as_ref(), challenged.clone()).unwrap();
+        assert_eq!(game.status, "PendingResult");
+
+        let run = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                   at: u64,
+                   sender: &str,
+                   msg: ExecuteMsg| {
+            execute(
+                deps.as_mut(),
+                env_at(at),
+                message_info(&addr(sender), &[]),
+                msg,
+            )
+        };
+
+        // Alice challenges in time; the window closing doesn't finalize it
+        run(
+            &mut deps,
+            1_599,
+            "alice",
+            ExecuteMsg::ChallengeResult {
+                game_id: challenged.clone(),
+            },
+        )
+        .unwrap();
+        let res = run(
+            &mut deps,
+            1_700,
+            "carol",
+            ExecuteMsg::FinalizeResult {
+                game_id: challenged.clone(),
+            },
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::AlreadyChallenged { .. }
+        ));
+
+        // Only the admin or arbiter resolves it
+        let resolve = || ExecuteMsg::ResolveChallenge {
+            game_id: challenged.clone(),
+            winner: addr("alice").to_string(),
+            result_type: "gammon".to_string(),
+        };
+        let res = run(&mut deps, 1_700, "bob", resolve());
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+        run(&mut deps, 1_700, "arbiter", resolve()).unwrap();
+        let game = query_game(deps.as_ref(), challenged).unwrap();
+        assert_eq!(game.status, "Completed");
+        assert_eq!(game.winner, Some(addr("alice")));
+
+        // The unchallenged game can't be challenged late or finalized early
+        let res = run(
+            &mut deps,
+            1_600,
+            "alice",
+            ExecuteMsg::ChallengeResult {
+                game_id: quiet.clone(),
+            },
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::ChallengePeriodEnded {
+                deadline: 1_600,
+                ..
+            }
+        ));
+        let finalize = || ExecuteMsg::FinalizeResult {
+            game_id: quiet.clone(),
+        };
+        let res = run(&mut deps, 1_599, "carol", finalize());
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::ChallengePeriodActive { .. }
+        ));
+        run(&mut deps, 1_600, "carol", finalize()).unwrap();
+
+        let alice = query_player_stats(deps.as_ref(), addr("alice").to_string()).unwrap();
+        let bob = query_player_stats(deps.as_ref(), addr("bob").to_string()).unwrap();
+        assert_eq!((alice.games_won, bob.games_won), (1, 1));
+        assert_eq!(bob.games_played, 2);
+
+        // Abandonments are held too, and the deadline set at report time
+        // survives a config change
+        let abandoned = create_game(&mut deps, "alice", "bob", 300);
+        let res = run(
+            &mut deps,
+            2_000,
+            "server",
+            ExecuteMsg::ReportAbandonment {
+                game_id: abandoned.clone(),
+                abandoner: addr("alice").to_string(),
+                dice_reveals: None,
+                winner_signature: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(attr(&res, "challenge_deadline").unwrap(), "2600");
+        let game = query_game(deps.as_ref(), abandoned.clone()).unwrap();
+        assert_eq!(game.status, "PendingResult");
+        assert_eq!(game.abandoned_by, Some(addr("alice")));
+        assert_eq!(game.challenge_deadline, Some(2_600));
+        run(
+            &mut deps,
+            2_000,
+            "admin",
+            ExecuteMsg::UpdateConfig {
+                escrow_contract: None,
+                rating_params: None,
+                challenge_period_seconds: Some(60),
+                game_ttl_seconds: None,
+                dispute_period_seconds: None,
+                achievement_nft_contract: None,
+                server_address: None,
+                arbiter: None,
+            },
+        )
+        .unwrap();
+        let finalize = || ExecuteMsg::FinalizeResult {
+            game_id: abandoned.clone(),
+        };
+        let res = run(&mut deps, 2_100, "carol", finalize());
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::ChallengePeriodActive {
+                deadline: 2_600,
+                ..
+            }
+        ));
+        run(&mut deps, 2_600, "carol", finalize()).unwrap();
+        let game = query_game(deps.as_ref(), abandoned).unwrap();
+        assert_eq!(game.status, "Abandoned");
+        assert_eq!(game.winner, Some(addr("bob")));
+        let alice = query_player_stats(deps.as_ref(), addr("alice").to_string()).unwrap();
+        assert_eq!(alice.abandonments, 1);
+    }
+
+    #[test]
+    fn test_leaderboard_and_rank_follow_rating_index() {
+        let mut deps = setup();
+        let game = create_game(&mut deps, "alice", "bob", 100);
+        report(&mut deps, &game, "alice");
+        let game = create_game(&mut deps, "carol", "alice", 200);
+        report(&mut deps, &game, "carol");
+        let game = create_game(&mut deps, "dave", "bob", 300);
+        report(&mut deps, &game, "dave");
+        // Never finished a game
+        create_game(&mut deps, "erin", "frank", 400);
+
+        let leaderboard = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                           start_after: Option<Addr>,
+                           min_games: Option<u32>| {
+            let res: LeaderboardResponse = from_json(
+                query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::Leaderboard {
+                        start_after: start_after.map(|a| a.to_string()),
+                        limit: Some(2),
+                        min_games,
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            (res.players, res.next_start_after)
+        };
+
+        // Expected order: rating descending, ties on address descending
+        let mut expected: Vec<(u32, Addr)> = ["alice", "bob", "carol", "dave", "erin", "frank"]
+            .iter()
+            .map(|p| {
+                let stats = PLAYER_STATS.load(&deps.storage, &addr(p)).unwrap();
+                (stats.rating, stats.address)
+            })
+            .collect();
+        expected.sort_by(|a, b| b.cmp(a));
+
+        let mut paged = vec![];
+        let mut cursor = None;
+        loop {
+            let (page, next) = leaderboard(&deps, cursor, None);
+            paged.extend(page.into_iter().map(|p| (p.rating, p.address)));
+            cursor = match next {
+                Some(next) => Some(next),
+                None => break,
+            };
+        }
+        assert_eq!(paged, expected);
+        assert!(paged[0].0 > paged[5].0);
+
+        // min_games drops erin and frank
+        let mut filtered = vec![];
+        let mut cursor = None;
+        loop {
+            let (page, next) = leaderboard(&deps, cursor, Some(1));
+            filtered.extend(page.into_iter().map(|p| p.address));
+            cursor = match next {
+                Some(next) => Some(next),
+                None => break,
+            };
+        }
+        assert_eq!(filtered.len(), 4);
+        assert!(!filtered.contains(&addr("erin")) && !filtered.contains(&addr("frank")));
+
+        // Tied players share the rank below everyone rated above them
+        for (rating, address) in &expected {
+            let above = expected.iter().filter(|(r, _)| r > rating).count();
+            let res: PlayerRankResponse = from_json(
+                query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::PlayerRank {
+                        address: address.to_string(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            assert_eq!(res.rank, Some(above as u64 + 1));
+            assert_eq!(res.rating, *rating);
+        }
+
+        // However far down the leaderboard a player is
+        for i in 0..1_500u32 {
+            let stats = PlayerStats::new(addr(&format!("p{i}")), 200_000 + i * 7);
+            PLAYER_STATS
+                .save(deps.as_mut().storage, &stats.address.clone(), &stats)
+                .unwrap();
+        }
+        let bottom = expected.last().unwrap();
+        let res = query_player_rank(deps.as_ref(), bottom.1.to_string()).unwrap();
+        let above = expected.iter().filter(|(r, _)| *r > bottom.0).count();
+        assert_eq!(res.rank, Some(1_500 + above as u64 + 1));
+        let res = query_player_rank(deps.as_ref(), addr("p1000").to_string()).unwrap();
+        assert_eq!(res.rank, Some(500));
+        let res: PlayerRankResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::PlayerRank {
+                    address: addr("nobody").to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(res.rank, None);
+    }
+
+    #[test]
+    fn test_season_archives_standings_and_soft_resets() {
+        let mut deps = setup();
+        let admin = |msg: ExecuteMsg, deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>| {
+            execute(
+                deps.as_mut(),
+                env_at(50),
+                message_info(&addr("admin"), &[]),
+                msg,
+            )
+        };
+
+        // Off-season games count toward lifetime stats only
+        let game = create_game(&mut deps, "carol", "dave", 10);
+        report(&mut deps, &game, "carol");
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("alice"), &[]),
+            ExecuteMsg::StartSeason {
+                name: "Spring".to_string(),
+                reset_percent: None,
+            },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+        let start = |name: &str| ExecuteMsg::StartSeason {
+            name: name.to_string(),
+            reset_percent: Some(50),
+        };
+        admin(start("Spring"), &mut deps).unwrap();
+        assert!(matches!(
+            admin(start("Summer"), &mut deps).unwrap_err(),
+            ContractError::SeasonActive { season_id: 1 }
+        ));
+
+        let game = create_game(&mut deps, "alice", "bob", 100);
+        report(&mut deps, &game, "alice");
+        let game = create_game(&mut deps, "alice", "bob", 200);
+        report(&mut deps, &game, "alice");
+
+        let alice = PLAYER_STATS.load(&deps.storage, &addr("alice")).unwrap();
+        let bob = PLAYER_STATS.load(&deps.storage, &addr("bob")).unwrap();
+        assert_eq!(alice.season_games_played, 2);
+        let carol = PLAYER_STATS.load(&deps.storage, &addr("carol")).unwrap();
+        assert_eq!((carol.games_played, carol.season_games_played), (1, 0));
+
+        // Four players to reset, then two to rank, one per call
+        let mut batches = vec![];
+        loop {
+            let res = admin(ExecuteMsg::EndSeason { limit: Some(1) }, &mut deps).unwrap();
+            batches.push(attr(&res, "processed").unwrap());
+            if attr(&res, "complete").unwrap() == "true" {
+                assert_eq!(attr(&res, "player_count").unwrap(), "2");
+                break;
+            }
+            // No new season until this one is fully ended
+            assert!(matches!(
+                admin(start("Summer"), &mut deps).unwrap_err(),
+                ContractError::SeasonEnding { season_id: 1 }
+            ));
+        }
+        assert_eq!(batches, vec!["1", "1", "1", "1", "1", "1", "0"]);
+        assert_eq!(
+            admin(ExecuteMsg::EndSeason { limit: None }, &mut deps).unwrap_err(),
+            ContractError::NoActiveSeason {}
+        );
+
+        let standings: SeasonStandingsResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::SeasonStandings {
+                    season_id: 1,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        // Carol and Dave sat the season out
+        let placings: Vec<_> = standings
+            .standings
+            .iter()
+            .map(|s| (s.rank, s.address.clone(), s.games_won))
+            .collect();
+        assert_eq!(placings, vec![(1, addr("alice"), 2), (2, addr("bob"), 0)]);
+        assert_eq!(standings.standings[0].rating, alice.rating);
+        assert_eq!(standings.standings[1].rating, bob.rating);
+
+        // Ratings are halfway back to the default; lifetime stats are kept
+        let alice_after = PLAYER_STATS.load(&deps.storage, &addr("alice")).unwrap();
+        assert_eq!(
+            alice_after.rating,
+            alice.rating - (alice.rating - DEFAULT_RATING) / 2
+        );
+        assert_eq!(alice_after.games_won, 2);
+        assert_eq!(alice_after.season_games_played, 0);
+        let carol_after = PLAYER_STATS.load(&deps.storage, &addr("carol")).unwrap();
+        assert!(carol_after.rating < carol.rating);
+
+        let season: SeasonResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::Season { season_id: 1 }).unwrap())
+                .unwrap();
+        assert_eq!(season.ended_at, Some(50));
+        assert_eq!(season.player_count, 2);
+
+        // A second season, then Bob's history across both
+        admin(start("Summer"), &mut deps).unwrap();
+        let current: CurrentSeasonResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::CurrentSeason {}).unwrap())
+                .unwrap();
+        assert_eq!(current.season.unwrap().season_id, 2);
+        let game = create_game(&mut deps, "alice", "bob", 300);
+        report(&mut deps, &game, "bob");
+        let res = admin(ExecuteMsg::EndSeason { limit: None }, &mut deps).unwrap();
+        assert_eq!(attr(&res, "complete").unwrap(), "true");
+
+        let history = |start_after: Option<u32>| -> Vec<(u32, u32)> {
+            let res: SeasonStandingsResponse = from_json(
+                query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::PlayerSeasons {
+                        address: addr("bob").to_string(),
+                        start_after,
+                        limit: None,
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            res.standings
+                .iter()
+                .map(|s| (s.season_id, s.games_won))
+                .collect()
+        };
+        assert_eq!(history(None), vec![(1, 0), (2, 1)]);
+        assert_eq!(history(Some(1)), vec![(2, 1)]);
+    }
+
+    #[test]
+    fn test_open_and_private_challenges() {
+        use k256::ecdsa::{signature::hazmat::PrehashSigner, Signature, SigningKey};
+
+        let mut deps = setup();
+        let run_at = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                      sender: &str,
+                      at: u64,
+                      msg: ExecuteMsg| {
+            execute(
+                deps.as_mut(),
+                env_at(at),
+                message_info(&addr(sender), &[]),
+                msg,
+            )
+        };
+        let run = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                   sender: &str,
+                   msg: ExecuteMsg| run_at(deps, sender, 100, msg);
+        let accept =
+            |challenge_id: &str, invite_signature: Option<Binary>| ExecuteMsg::AcceptChallenge {
+                challenge_id: challenge_id.to_string(),
+                invite_signature,
+            };
+
+        // Bob gets a rating bump so he's outside a beginners-only range
+        let game = create_game(&mut deps, "bob", "dave", 10);
+        report(&mut deps, &game, "bob");
+
+        let res = run(
+            &mut deps,
+            "alice",
+            ExecuteMsg::CreateOpenChallenge {
+                wager_amount: 0,
+                min_rating: Some(150_001),
+                max_rating: Some(150_000),
+                invite_pubkey: None,
+                expires_in: None,
+            },
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::InvalidRatingRange { .. }
+        ));
+        let res = run(
+            &mut deps,
+            "alice",
+            ExecuteMsg::CreateOpenChallenge {
+                wager_amount: 0,
+                min_rating: None,
+                max_rating: Some(DEFAULT_RATING),
+                invite_pubkey: None,
+                expires_in: None,
+            },
+        )
+        .unwrap();
+        let public = attr(&res, "challenge_id").unwrap();
+        assert_eq!(
+            attr(&res, "expires_at").unwrap(),
+            (100 + DEFAULT_CHALLENGE_EXPIRY).to_string()
+        );
+
+        assert_eq!(
+            run(&mut deps, "alice", accept(&public, None)).unwrap_err(),
+            ContractError::SelfPlay {}
+        );
+        assert!(matches!(
+            run(&mut deps, "bob", accept(&public, None)).unwrap_err(),
+            ContractError::RatingOutOfRange { .. }
+        ));
+        let res = run(&mut deps, "carol", accept(&public, None)).unwrap();
+        let game_id = attr(&res, "game_id").unwrap();
+        let game = query_game(deps.as_ref(), game_id.clone()).unwrap();
+        assert_eq!(
+            (game.player_a, game.player_b),
+            (addr("alice"), addr("carol"))
+        );
+        assert!(matches!(
+            run(&mut deps, "erin", accept(&public, None)).unwrap_err(),
+            ContractError::ChallengeNotOpen { .. }
+        ));
+
+        // Expired challenges cannot be taken and drop out of the open list
+        let res = run(
+            &mut deps,
+            "alice",
+            ExecuteMsg::CreateOpenChallenge {
+                wager_amount: 0,
+                min_rating: None,
+                max_rating: None,
+                invite_pubkey: None,
+                expires_in: Some(50),
+            },
+        )
+        .unwrap();
+        let expiring = attr(&res, "challenge_id").unwrap();
+        assert!(matches!(
+            run_at(&mut deps, "carol", 150, accept(&expiring, None)).unwrap_err(),
+            ContractError::ChallengeExpired {
+                expires_at: 150,
+                ..
+            }
+        ));
+
+        // Private: only an acceptor the invite key signed for gets in
+        let invite = SigningKey::from_slice(&[3u8; 32]).unwrap();
+        let res = run(
+            &mut deps,
+            "alice",
+            ExecuteMsg::CreateOpenChallenge {
+                wager_amount: 0,
+                min_rating: None,
+                max_rating: None,
+                invite_pubkey: Some(Binary::from(
+                    invite.verifying_key().to_encoded_point(true).as_bytes(),
+                )),
+                expires_in: None,
+            },
+        )
+        .unwrap();
+        let private = attr(&res, "challenge_id").unwrap();
+
+        let open_at = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, at: u64| {
+            from_json::<ChallengesResponse>(
+                query(
+                    deps.as_ref(),
+                    env_at(at),
+                    QueryMsg::OpenChallenges {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+            .challenges
+        };
+        let open = open_at(&deps, 100);
+        assert_eq!(open.len(), 2);
+        assert_eq!(open[0].challenge_id, expiring);
+        assert!(open[1].private);
+        let open = open_at(&deps, 150);
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].challenge_id, private);
+
+        let sign_for = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, acceptor: &str| {
+            let payload: ResultSigningPayloadResponse = from_json(
+                query(
+                    deps.as_ref(),
+                    env_at(100),
+                    QueryMsg::InviteSigningPayload {
+                        challenge_id: private.clone(),
+                        acceptor: addr(acceptor).to_string(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            let sig: Signature = invite.sign_prehash(payload.hash.as_slice()).unwrap();
+            Binary::from(sig.to_bytes().as_slice())
+        };
+        let signature = sign_for(&deps, "bob");
+        assert_eq!(
+            run(&mut deps, "bob", accept(&private, None)).unwrap_err(),
+            ContractError::InvalidInvite {}
+        );
+        // A signature seen in bob's transaction does not let carol in
+        assert_eq!(
+            run(
+                &mut deps,
+                "carol",
+                accept(&private, Some(signature.clone()))
+            )
+            .unwrap_err(),
+            ContractError::InvalidInvite {}
+        );
+        assert_eq!(
+            run(
+                &mut deps,
+                "bob",
+                ExecuteMsg::CancelChallenge {
+                    challenge_id: private.clone()
+                }
+            )
+            .unwrap_err(),
+            ContractError::Unauthorized {}
+        );
+        run(&mut deps, "bob", accept(&private, Some(signature))).unwrap();
+
+        let challenge: ChallengeResponse = from_json(
+            query(
+                deps.as_ref(),
+                env_at(100),
+                QueryMsg::Challenge {
+                    challenge_id: private,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(challenge.status, "Accepted");
+        assert_eq!(challenge.accepted_by, Some(addr("bob")));
+    }
+
+    #[test]
+    fn test_game_proposals_need_opponent_consent() {
+        let mut deps = setup();
+        let run = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                   sender: &str,
+                   at: u64,
+                   msg: ExecuteMsg| {
+            execute(
+                deps.as_mut(),
+                env_at(at),
+                message_info(&addr(sender), &[]),
+                msg,
+            )
+        };
+        let propose = |expires_in: Option<u64>| ExecuteMsg::ProposeGame {
+            opponent: addr("bob").to_string(),
+            wager_amount: 5_000_000,
+            expires_in,
+        };
+        let accept = |proposal_id: &str| ExecuteMsg::AcceptGame {
+            proposal_id: proposal_id.to_string(),
+        };
+
+        // Only the server or admin may create games outright
+        let res = run(
+            &mut deps,
+            "alice",
+            100,
+            ExecuteMsg::CreateGame {
+                player_a: addr("alice").to_string(),
+                player_b: addr("bob").to_string(),
+                wager_amount: 0,
+                game_id: None,
+                idempotency_key: None,
+                server_match_id: None,
+            },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+        let res = run(
+            &mut deps,
+            "alice",
+            100,
+            ExecuteMsg::CreateMatch {
+                player_a: addr("alice").to_string(),
+                player_b: addr("bob").to_string(),
+                match_length: 3,
+                wager_amount: 5_000_000,
+            },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+        assert!(matches!(
+            run(&mut deps, "alice", 100, propose(Some(0))).unwrap_err(),
+            ContractError::InvalidProposalExpiry { .. }
+        ));
+
+        // Proposing creates nothing for bob until he accepts
+        let res = run(&mut deps, "alice", 100, propose(None)).unwrap();
+        let first = attr(&res, "proposal_id").unwrap();
+        assert!(res.messages.is_empty());
+        assert!(!PLAYER_STATS.has(&deps.storage, &addr("bob")));
+
+        let pending: ProposalsResponse = from_json(
+            query(
+                deps.as_ref(),
+                env_at(100),
+                QueryMsg::PendingProposals {
+                    address: addr("bob").to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(pending.proposals.len(), 1);
+        assert_eq!(pending.proposals[0].proposer, addr("alice"));
+
+        assert_eq!(
+            run(&mut deps, "carol", 200, accept(&first)).unwrap_err(),
+            ContractError::Unauthorized {}
+        );
+        let res = run(&mut deps, "bob", 200, accept(&first)).unwrap();
+        let game = query_game(deps.as_ref(), attr(&res, "game_id").unwrap()).unwrap();
+        assert_eq!(
+            (game.player_a, game.player_b, game.wager_amount),
+            (addr("alice"), addr("bob"), 5_000_000)
+        );
+        assert!(matches!(
+            run(&mut deps, "bob", 200, accept(&first)).unwrap_err(),
+            ContractError::ProposalNotPending { .. }
+        ));
+
+        // Declined and lapsed proposals never become games
+        let res = run(&mut deps, "alice", 300, propose(None)).unwrap();
+        let declined = attr(&res, "proposal_id").unwrap();
+        run(
+            &mut deps,
+            "bob",
+            400,
+            ExecuteMsg::DeclineGame {
+                proposal_id: declined.clone(),
+            },
+        )
+        .unwrap();
+        assert!(matches!(
+            run(&mut deps, "bob", 400, accept(&declined)).unwrap_err(),
+            ContractError::ProposalNotPending { .. }
+        ));
+
+        let res = run(&mut deps, "alice", 500, propose(Some(60))).unwrap();
+        let lapsed = attr(&res, "proposal_id").unwrap();
+        assert!(matches!(
+            run(&mut deps, "bob", 560, accept(&lapsed)).unwrap_err(),
+            ContractError::ProposalExpired { .. }
+        ));
+        let proposal: ProposalResponse = from_json(
+            query(
+                deps.as_ref(),
+                env_at(560),
+                QueryMsg::Proposal {
+                    proposal_id: lapsed,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(proposal.status, "Expired");
+        assert_eq!(GAME_COUNTER.load(&deps.storage).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_cancel_and_expire_unstarted_games() {
+        let mut deps = setup();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("admin"), &[]),
+            ExecuteMsg::UpdateConfig {
+                escrow_contract: Some(addr("escrow").to_string()),
+                rating_params: None,
+                challenge_period_seconds: None,
+                game_ttl_seconds: Some(1_000),
+                dispute_period_seconds: None,
+                achievement_nft_contract: None,
+                server_address: None,
+                arbiter: None,
+            },
+        )
+        .unwrap();
+        let run = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                   sender: &str,
+                   at: u64,
+                   msg: ExecuteMsg| {
+            execute(
+                deps.as_mut(),
+                env_at(at),
+                message_info(&addr(sender), &[]),
+                msg,
+            )
+        };
+        let create = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, at: u64| {
+            let res = run(
+                deps,
+                "server",
+                at,
+                ExecuteMsg::CreateGame {
+                    player_a: addr("alice").to_string(),
+                    player_b: addr("bob").to_string(),
+                    wager_amount: 1_000_000,
+                    game_id: None,
+                    idempotency_key: None,
+                    server_match_id: None,
+                },
+            )
+            .unwrap();
+            attr(&res, "game_id").unwrap()
+        };
+        let cancel = |game_id: &str| ExecuteMsg::CancelGame {
+            game_id: game_id.to_string(),
+        };
+        let escrow_cancel = |res: &Response, escrow_id: &str| {
+            res.messages.iter().any(|sub| {
+                matches!(&sub.msg, CosmosMsg::Wasm(WasmMsg::Execute { msg, .. })
+                    if *msg == to_json_binary(&EscrowExecuteMsg::Cancel {
+                        game_id: escrow_id.to_string(),
+                    })
+                    .unwrap())
+            })
+        };
+
+        let cancelled = create(&mut deps, 100);
+        let started = create(&mut deps, 200);
+        let refunded = create(&mut deps, 300);
+        let fresh = create(&mut deps, 1_500);
+        let unwagered = create_game(&mut deps, "carol", "dave", 300);
+        let res = run(
+            &mut deps,
+            "server",
+            500,
+            ExecuteMsg::CreateMatch {
+                player_a: addr("alice").to_string(),
+                player_b: addr("bob").to_string(),
+                match_length: 3,
+                wager_amount: 1_000_000,
+            },
+        )
+        .unwrap();
+        let match_id = attr(&res, "match_id").unwrap();
+
+        // The escrow of `refunded` already timed out and paid everyone back
+        let escrow = addr("escrow").to_string();
+        let open = [cancelled.clone(), match_id.clone()];
+        deps.querier.update_wasm(move |query| match query {
+            cosmwasm_std::WasmQuery::Smart { contract_addr, msg } if *contract_addr == escrow => {
+                let status = if open
+                    .iter()
+                    .any(|id| *msg == to_json_binary(&EscrowQueryMsg::FindEscrow { game_id: id.clone() }).unwrap())
+                {
+                    "Active"
+                } else {
+                    "Cancelled"
+                };
+                let response = format!(
+                    r#"{{"status":"{status}","player_a_deposited":1000000,"player_b_deposited":1000000,
+                        "cube_value":1,"pending_double":null,"projected_payout":0,"projected_rake":0}}"#
+                );
+                cosmwasm_std::SystemResult::Ok(cosmwasm_std::ContractResult::Ok(Binary::from(
+                    response.into_bytes(),
+                )))
+            }
+            _ => cosmwasm_std::SystemResult::Err(cosmwasm_std::SystemError::UnsupportedRequest {
+                kind: "unexpected query".to_string(),
+            }),
+        });
+
+        run(
+            &mut deps,
+            "server",
+            200,
+            ExecuteMsg::StartGame {
+                game_id: started.clone(),
+                commit_dice: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            run(&mut deps, "carol", 400, cancel(&cancelled)).unwrap_err(),
+            ContractError::Unauthorized {}
+        );
+        assert!(matches!(
+            run(&mut deps, "alice", 400, cancel(&started)).unwrap_err(),
+            ContractError::InvalidGameStatus { .. }
+        ));
+        let res = run(&mut deps, "bob", 400, cancel(&cancelled)).unwrap();
+        assert!(escrow_cancel(&res, &cancelled));
+        assert_eq!(
+            query_game(deps.as_ref(), cancelled).unwrap().status,
+            "Cancelled"
+        );
+
+        // Once the match's first game is played, its next one starts at once
+        // and can't be called off by a player
+        let first = MATCHES.load(&deps.storage, &match_id).unwrap().game_ids[0].clone();
+        let res = run(
+            &mut deps,
+            "server",
+            600,
+            ExecuteMsg::ReportResult {
+                game_id: first,
+                winner: addr("alice").to_string(),
+                result_type: "normal".to_string(),
+                move_count: 30,
+                dice_reveals: None,
+                move_log: None,
+                move_log_signatures: None,
+                cube: None,
+            },
+        )
+        .unwrap();
+        let second = attr(&res, "next_game_id").unwrap();
+        assert!(matches!(
+            run(&mut deps, "alice", 700, cancel(&second)).unwrap_err(),
+            ContractError::InvalidGameStatus { .. }
+        ));
+
+        // Only games created at or before now - TTL expire, `limit` at a
+        // time; an escrow that is already closed is left alone, and
+        // unwagered games have none
+        for _ in 0..2 {
+            let res = run(
+                &mut deps,
+                "erin",
+                1_400,
+                ExecuteMsg::ExpireGames { limit: Some(1) },
+            )
+            .unwrap();
+            assert_eq!(attr(&res, "expired").unwrap(), "1");
+            assert!(res.messages.is_empty());
+        }
+        for game_id in [&refunded, &unwagered] {
+            assert_eq!(
+                query_game(deps.as_ref(), game_id.clone()).unwrap().status,
+                "Expired"
+            );
+        }
+        assert_eq!(query_game(deps.as_ref(), fresh).unwrap().status, "Created");
+
+        // A match stalled on its unfinished game expires and is refunded
+        let expire_match = || ExecuteMsg::ExpireMatch {
+            match_id: match_id.clone(),
+        };
+        let err = run(&mut deps, "erin", 1_599, expire_match()).unwrap_err();
+        assert!(
+            matches!(err, ContractError::ExpireNotAllowed { .. }),
+            "{err:?}"
+        );
+        let res = run(&mut deps, "erin", 1_600, expire_match()).unwrap();
+        assert!(escrow_cancel(&res, &match_id));
+        assert_eq!(query_game(deps.as_ref(), second).unwrap().status, "Expired");
+        assert_eq!(
+            MATCHES.load(&deps.storage, &match_id).unwrap().status,
+            MatchStatus::Cancelled
+        );
+        let err = run(&mut deps, "erin", 1_700, expire_match()).unwrap_err();
+        assert!(
+            matches!(err, ContractError::ExpireNotAllowed { .. }),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn test_cube_results_score_points_and_stats() {
+        let mut deps = setup();
+        let report = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                      game_id: &str,
+                      winner: &str,
+                      result_type: &str,
+                      cube: CubeReport| {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr("server"), &[]),
+                ExecuteMsg::ReportResult {
+                    game_id: game_id.to_string(),
+                    winner: addr(winner).to_string(),
+                    result_type: result_type.to_string(),
+                    move_count: 40,
+                    dice_reveals: None,
+                    move_log: None,
+                    move_log_signatures: None,
+                    cube: Some(cube),
+                },
+            )
+        };
+        let cube = |value, owner: Option<&str>, doubles_a, doubles_b, passed| CubeReport {
+            value,
+            owner: owner.map(|o| addr(o).to_string()),
+            doubles_a,
+            doubles_b,
+            passed,
+        };
+        let stats = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, player: &str| {
+            query_player_stats(deps.as_ref(), addr(player).to_string()).unwrap()
+        };
+
+        let game_id = create_game(&mut deps, "alice", "bob", 100);
+        for (bad, reason) in [
+            (cube(3, Some("alice"), 1, 0, false), "power of two"),
+            (cube(4, Some("carol"), 1, 1, false), "owner"),
+            (cube(4, Some("alice"), 1, 0, false), "add up"),
+            (cube(2, Some("bob"), 2, 0, true), "doubler"),
+        ] {
+            let err = report(&mut deps, &game_id, "bob", "gammon", bad).unwrap_err();
+            assert!(
+                matches!(&err, ContractError::InvalidCube { reason: r } if r.contains(reason)),
+                "{err:?}"
+            );
+        }
+
+        // Alice doubles, bob takes and redoubles, alice takes; bob wins a gammon
+        let res = report(
+            &mut deps,
+            &game_id,
+            "bob",
+            "gammon",
+            cube(4, Some("alice"), 1, 1, false),
+        )
+        .unwrap();
+        assert_eq!(attr(&res, "points_won").unwrap(), "8");
+        let game = query_game(deps.as_ref(), game_id).unwrap();
+        assert_eq!(game.points_won, 8);
+        assert_eq!(game.cube.unwrap().owner, Some(addr("alice")));
+
+        // Bob passes alice's double
+        let game_id = create_game(&mut deps, "alice", "bob", 200);
+        report(
+            &mut deps,
+            &game_id,
+            "alice",
+            "normal",
+            cube(1, None, 1, 0, true),
+        )
+        .unwrap();
+
+        let alice = stats(&deps, "alice");
+        assert_eq!((alice.points_won, alice.points_lost), (1, 8));
+        assert_eq!((alice.gammons_won, alice.gammons_lost), (0, 1));
+        assert_eq!(
+            (
+                alice.doubles_offered,
+                alice.doubles_taken,
+                alice.doubles_passed
+            ),
+            (2, 1, 0)
+        );
+        let bob = stats(&deps, "bob");
+        assert_eq!((bob.points_won, bob.points_lost), (8, 1));
+        assert_eq!(bob.gammons_won, 1);
+        assert_eq!(
+            (bob.doubles_offered, bob.doubles_taken, bob.doubles_passed),
+            (1, 1, 1)
+        );
+
+        // In a match the cube multiplies the points scored
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            ExecuteMsg::CreateMatch {
+                player_a: addr("alice").to_string(),
+                player_b: addr("bob").to_string(),
+                match_length: 7,
+                wager_amount: 0,
+            },
+        )
+        .unwrap();
+        let game_id = attr(&res, "game_id").unwrap();
+        let res = report(
+            &mut deps,
+            &game_id,
+            "alice",
+            "gammon",
+            cube(2, Some("bob"), 1, 0, false),
+        )
+        .unwrap();
+        assert_eq!(attr(&res, "score_a").unwrap(), "4");
+    }
+
+    #[test]
+    fn test_achievements_mint_badges_once() {
+        let mut deps = setup();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("admin"), &[]),
+            ExecuteMsg::UpdateConfig {
+                escrow_contract: None,
+                rating_params: None,
+                challenge_period_seconds: None,
+                game_ttl_seconds: None,
+                dispute_period_seconds: None,
+                achievement_nft_contract: Some(addr("badges").to_string()),
+                server_address: None,
+                arbiter: None,
+            },
+        )
+        .unwrap();
+        let minted = |res: &Response| {
+            res.messages
+                .iter()
+                .filter(|sub| {
+                    matches!(&sub.msg, CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. })
+                        if *contract_addr == addr("badges").to_string())
+                })
+                .count()
+        };
+
+        let game_id = create_game(&mut deps, "alice", "bob", 100);
+        let res = report_as(&mut deps, &game_id, "alice", "backgammon");
+        assert_eq!(minted(&res), 2);
+        assert_eq!(
+            attr(&res, "achievement").unwrap(),
+            format!("first-win:{}", addr("alice"))
+        );
+
+        // A mint the NFT contract rejects is swallowed, and the achievement
+        // stays earned without a badge
+        let mint = &res.messages[res.messages.len() - 1];
+        assert_eq!(
+            (mint.id, mint.reply_on.clone()),
+            (MINT_BADGE_REPLY_ID, ReplyOn::Error)
+        );
+        reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: mint.id,
+                payload: mint.payload.clone(),
+                gas_used: 0,
+                result: cosmwasm_std::SubMsgResult::Err("token_id already claimed".to_string()),
+            },
+        )
+        .unwrap();
+
+        let game_id = create_game(&mut deps, "alice", "bob", 200);
+        let res = report_as(&mut deps, &game_id, "alice", "normal");
+        assert_eq!(minted(&res), 0);
+
+        let earned: PlayerAchievementsResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::PlayerAchievements {
+                    address: addr("alice").to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            earned
+                .achievements
+                .iter()
+                .map(|e| e.achievement.clone())
+                .collect::<Vec<_>>(),
+            vec![Achievement::FirstWin, Achievement::FirstBackgammon]
+        );
+        assert_eq!(
+            earned.achievements[0].token_id,
+            Some(format!("first-win-{}", addr("alice")))
+        );
+        assert_eq!(earned.achievements[1].token_id, None);
+        assert_eq!(
+            query_player_stats(deps.as_ref(), addr("bob").to_string())
+                .unwrap()
+                .win_streak,
+            0
+        );
+    }
+
+    #[test]
+    fn test_roles_grant_and_revoke() {
+        let mut deps = setup();
+        let run = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                   sender: &str,
+                   msg: ExecuteMsg| {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr(sender), &[]),
+                msg,
+            )
+        };
+        let grant = |role: Role, name: &str| ExecuteMsg::GrantRole {
+            role,
+            address: addr(name).to_string(),
+        };
+        let start_game = |game_id: &str| ExecuteMsg::StartGame {
+            game_id: game_id.to_string(),
+            commit_dice: None,
+        };
+
+        // Only the admin hands out roles
+        assert_eq!(
+            run(&mut deps, "server", grant(Role::Reporter, "server-2")).unwrap_err(),
+            ContractError::Unauthorized {}
+        );
+        run(&mut deps, "admin", grant(Role::Reporter, "server-2")).unwrap();
+        run(&mut deps, "admin", grant(Role::Operator, "ops")).unwrap();
+
+        // Both reporters can drive games
+        let game_id = create_game(&mut deps, "alice", "bob", 100);
+        run(&mut deps, "server-2", start_game(&game_id)).unwrap();
+
+        // Operators run seasons but cannot touch the config
+        run(
+            &mut deps,
+            "ops",
+            ExecuteMsg::StartSeason {
+                name: "Spring".to_string(),
+                reset_percent: None,
+            },
+        )
+        .unwrap();
+        let update = ExecuteMsg::UpdateConfig {
+            escrow_contract: None,
+            rating_params: None,
+            challenge_period_seconds: Some(60),
+            game_ttl_seconds: None,
+            dispute_period_seconds: None,
+            achievement_nft_contract: None,
+            server_address: None,
+            arbiter: None,
+        };
+        assert_eq!(
+            run(&mut deps, "ops", update.clone()).unwrap_err(),
+            ContractError::Unauthorized {}
+        );
+        run(&mut deps, "admin", grant(Role::ConfigAdmin, "ops")).unwrap();
+        run(&mut deps, "ops", update).unwrap();
+
+        // The pre-roles arbiter field still sets the arbiter, for the admin
+        // only, replacing the previous one so a rotated key stops working
+        let legacy = |arbiter: &str| ExecuteMsg::UpdateConfig {
+            escrow_contract: None,
+            rating_params: None,
+            challenge_period_seconds: None,
+            game_ttl_seconds: None,
+            dispute_period_seconds: None,
+            achievement_nft_contract: None,
+            server_address: None,
+            arbiter: Some(addr(arbiter).to_string()),
+        };
+        assert_eq!(
+            run(&mut deps, "ops", legacy("judge")).unwrap_err(),
+            ContractError::Unauthorized {}
+        );
+        run(&mut deps, "admin", legacy("judge")).unwrap();
+        let res = run(&mut deps, "admin", legacy("judge-2")).unwrap();
+        assert_eq!(
+            attr(&res, "revoked").unwrap(),
+            format!("Arbiter:{}", addr("judge"))
+        );
+        let config: ConfigResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+        assert_eq!(config.arbiter, Some(addr("judge-2")));
+        assert!(!ROLES.has(
+            &deps.storage,
+            &addr("admin"),
+            &Role::Arbiter,
+            &addr("judge")
+        ));
+
+        let roles: RolesResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::Roles {}).unwrap()).unwrap();
+        assert_eq!(roles.admin, addr("admin"));
+        let reporters = roles
+            .roles
+            .iter()
+            .find(|r| r.role == Role::Reporter)
+            .unwrap();
+        assert_eq!(reporters.members.len(), 2);
+        assert!(reporters.members.contains(&addr("server")));
+
+        run(
+            &mut deps,
+            "admin",
+            ExecuteMsg::RevokeRole {
+                role: Role::Reporter,
+                address: addr("server-2").to_string(),
+            },
+        )
+        .unwrap();
+        let game_id = create_game(&mut deps, "alice", "bob", 200);
+        assert_eq!(
+            run(&mut deps, "server-2", start_game(&game_id)).unwrap_err(),
+            ContractError::Unauthorized {}
+        );
+    }
+
+    #[test]
+    fn test_two_step_admin_transfer() {
+        let mut deps = setup();
+        let run = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                   sender: &str,
+                   env: Env,
+                   msg: ExecuteMsg| {
+            execute(deps.as_mut(), env, message_info(&addr(sender), &[]), msg)
+        };
+        let propose = |name: &str| ExecuteMsg::ProposeNewAdmin {
+            address: addr(name).to_string(),
+            expires_in: Some(3600),
+        };
+
+        assert_eq!(
+            run(&mut deps, "multisig", mock_env(), propose("multisig")).unwrap_err(),
+            ContractError::Unauthorized {}
+        );
+        run(&mut deps, "admin", mock_env(), propose("multisig")).unwrap();
+
+        // Only the proposed address can accept, and only before expiry
+        assert_eq!(
+            run(&mut deps, "server", mock_env(), ExecuteMsg::AcceptAdmin {}).unwrap_err(),
+            ContractError::Unauthorized {}
+        );
+        let mut late = mock_env();
+        late.block.time = late.block.time.plus_seconds(3600);
+        assert!(matches!(
+            run(&mut deps, "multisig", late, ExecuteMsg::AcceptAdmin {}).unwrap_err(),
+            ContractError::AdminTransferExpired { .. }
+        ));
+
+        // A cancelled transfer cannot be accepted
+        let res = run(
+            &mut deps,
+            "admin",
+            mock_env(),
+            ExecuteMsg::CancelAdminTransfer {},
+        )
+        .unwrap();
+        assert_eq!(
+            attr(&res, "action"),
+            Some("cancel_admin_transfer".to_string())
+        );
+        let pending: PendingAdminResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::PendingAdmin {}).unwrap())
+                .unwrap();
+        assert_eq!((pending.admin, pending.pending), (addr("admin"), None));
+        assert_eq!(
+            run(
+                &mut deps,
+                "multisig",
+                mock_env(),
+                ExecuteMsg::AcceptAdmin {}
+            )
+            .unwrap_err(),
+            ContractError::NoPendingAdmin {}
+        );
+
+        run(&mut deps, "admin", mock_env(), propose("multisig")).unwrap();
+        let pending: PendingAdminResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::PendingAdmin {}).unwrap())
+                .unwrap();
+        assert_eq!(pending.pending.unwrap().address, addr("multisig"));
+        run(
+            &mut deps,
+            "multisig",
+            mock_env(),
+            ExecuteMsg::AcceptAdmin {},
+        )
+        .unwrap();
+
+        let pending: PendingAdminResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::PendingAdmin {}).unwrap())
+                .unwrap();
+        assert_eq!(pending.admin, addr("multisig"));
+        assert_eq!(pending.pending, None);
+        assert_eq!(
+            run(&mut deps, "admin", mock_env(), propose("admin")).unwrap_err(),
+            ContractError::Unauthorized {}
+        );
+
+        // Grants must all be revoked before renouncing, since nothing could
+        // revoke them afterwards
+        assert_eq!(
+            run(
+                &mut deps,
+                "multisig",
+                mock_env(),
+                ExecuteMsg::RenounceAdmin {}
+            )
+            .unwrap_err(),
+            ContractError::RolesRemain {}
+        );
+        run(
+            &mut deps,
+            "multisig",
+            mock_env(),
+            ExecuteMsg::RevokeRole {
+                role: Role::Reporter,
+                address: addr("server").to_string(),
+            },
+        )
+        .unwrap();
+
+        // Renouncing hands admin to the contract itself and drops any
+        // pending transfer
+        run(&mut deps, "multisig", mock_env(), propose("admin")).unwrap();
+        let res = run(
+            &mut deps,
+            "multisig",
+            mock_env(),
+            ExecuteMsg::RenounceAdmin {},
+        )
+        .unwrap();
+        assert_eq!(
+            attr(&res, "previous_admin"),
+            Some(addr("multisig").to_string())
+        );
+        assert_eq!(
+            run(&mut deps, "admin", mock_env(), ExecuteMsg::AcceptAdmin {}).unwrap_err(),
+            ContractError::NoPendingAdmin {}
+        );
+        assert_eq!(
+            CONFIG.load(&deps.storage).unwrap().admin,
+            mock_env().contract.address
+        );
+        assert_eq!(
+            run(&mut deps, "multisig", mock_env(), propose("admin")).unwrap_err(),
+            ContractError::Unauthorized {}
+        );
+    }
+
+    #[test]
+    fn test_pause_halts_operations_independently() {
+        let mut deps = setup();
+        let run = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                   sender: &str,
+                   msg: ExecuteMsg| {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr(sender), &[]),
+                msg,
+            )
+        };
+        let pause = ExecuteMsg::Pause {
+            operations: vec![Operation::Settlement],
+        };
+
+        assert_eq!(
+            run(&mut deps, "guardian", pause.clone()).unwrap_err(),
+            ContractError::Unauthorized {}
+        );
+        run(
+            &mut deps,
+            "admin",
+            ExecuteMsg::GrantRole {
+                role: Role::Pauser,
+                address: addr("guardian").to_string(),
+            },
+        )
+        .unwrap();
+        run(&mut deps, "guardian", pause).unwrap();
+
+        // Games can still be created, but results cannot be booked
+        let game_id = create_game(&mut deps, "alice", "bob", 100);
+        let report = ExecuteMsg::ReportResult {
+            game_id: game_id.clone(),
+            winner: addr("alice").to_string(),
+            result_type: "normal".to_string(),
+            move_count: 40,
+            dice_reveals: None,
+            move_log: None,
+            move_log_signatures: None,
+            cube: None,
+        };
+        assert_eq!(
+            run(&mut deps, "server", report.clone()).unwrap_err(),
+            ContractError::Paused {
+                operation: "Settlement".to_string()
+            }
+        );
+        let status: PauseStatusResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::PauseStatus {}).unwrap()).unwrap();
+        assert_eq!(status.paused, vec![Operation::Settlement]);
+
+        run(
+            &mut deps,
+            "guardian",
+            ExecuteMsg::Pause {
+                operations: vec![Operation::CreateGame],
+            },
+        )
+        .unwrap();
+        let create = ExecuteMsg::CreateGame {
+            player_a: addr("alice").to_string(),
+            player_b: addr("bob").to_string(),
+            wager_amount: 0,
+            game_id: None,
+            idempotency_key: None,
+            server_match_id: None,
+        };
+        assert_eq!(
+            run(&mut deps, "server", create).unwrap_err(),
+            ContractError::Paused {
+                operation: "CreateGame".to_string()
+            }
+        );
+
+        run(
+            &mut deps,
+            "guardian",
+            ExecuteMsg::Unpause {
+                operations: vec![Operation::Settlement],
+            },
+        )
+        .unwrap();
+        run(&mut deps, "server", report).unwrap();
+        let status: PauseStatusResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::PauseStatus {}).unwrap()).unwrap();
+        assert_eq!(status.paused, vec![Operation::CreateGame]);
+    }
+
+    #[test]
+    fn test_platform_stats_bucketed_by_day() {
+        let mut deps = setup();
+        let day = SECONDS_PER_DAY;
+        let run =
+            |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, at: u64, msg: ExecuteMsg| {
+                execute(
+                    deps.as_mut(),
+                    env_at(at),
+                    message_info(&addr("server"), &[]),
+                    msg,
+                )
+            };
+        let report = |game_id: &str, winner: &str, result_type: &str| ExecuteMsg::ReportResult {
+            game_id: game_id.to_string(),
+            winner: addr(winner).to_string(),
+            result_type: result_type.to_string(),
+            move_count: 40,
+            dice_reveals: None,
+            move_log: None,
+            move_log_signatures: None,
+            cube: None,
+        };
+
+        // Day 1: two games, a normal win and a gammon between the same pair
+        let g1 = create_game(&mut deps, "alice", "bob", day + 10);
+        let g2 = create_game(&mut deps, "alice", "bob", day + 20);
+        run(&mut deps, day + 100, report(&g1, "alice", "normal")).unwrap();
+        run(&mut deps, day + 200, report(&g2, "bob", "gammon")).unwrap();
+
+        // Day 3: carol plays for the first time and bob abandons
+        let g3 = create_game(&mut deps, "bob", "carol", 3 * day + 10);
+        run(
+            &mut deps,
+            3 * day + 100,
+            ExecuteMsg::ReportAbandonment {
+                game_id: g3,
+                abandoner: addr("bob").to_string(),
+                dice_reveals: None,
+                winner_signature: None,
+            },
+        )
+        .unwrap();
+
+        let stats = query_stats(deps.as_ref()).unwrap();
+        assert_eq!(stats.totals.games_created, 3);
+        assert_eq!(stats.totals.normal_wins, 1);
+        assert_eq!(stats.totals.gammons, 1);
+        assert_eq!(stats.totals.abandonments, 1);
+        assert_eq!(stats.totals.unique_players, 3);
+
+        let days = query_daily_stats(deps.as_ref(), None, None, None)
+            .unwrap()
+            .days;
+        assert_eq!(
+            days.iter().map(|d| d.day_start).collect::<Vec<_>>(),
+            vec![day, 3 * day]
+        );
+        assert_eq!(days[0].stats.games_created, 2);
+        assert_eq!(days[0].stats.unique_players, 2);
+        assert_eq!(days[1].stats.abandonments, 1);
+        assert_eq!(days[1].stats.unique_players, 2);
+
+        // The range is inclusive and snaps to whole days
+        let days = query_daily_stats(deps.as_ref(), Some(2 * day), Some(3 * day + 5), None)
+            .unwrap()
+            .days;
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].day_start, 3 * day);
+    }
+
+    #[test]
+    fn test_head_to_head_and_rivals() {
+        let mut deps = setup();
+
+        let g1 = create_game(&mut deps, "alice", "bob", 100);
+        let g2 = create_game(&mut deps, "alice", "bob", 200);
+        let g3 = create_game(&mut deps, "bob", "alice", 300);
+        let g4 = create_game(&mut deps, "alice", "carol", 400);
+        report(&mut deps, &g1, "alice");
+        report_as(&mut deps, &g2, "bob", "gammon");
+        report(&mut deps, &g4, "carol");
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            ExecuteMsg::ReportAbandonment {
+                game_id: g3,
+                abandoner: addr("alice").to_string(),
+                dice_reveals: None,
+                winner_signature: None,
+            },
+        )
+        .unwrap();
+
+        // The same record from either side
+        let alice = query_head_to_head(
+            deps.as_ref(),
+            addr("alice").to_string(),
+            addr("bob").to_string(),
+        )
+        .unwrap();
+        assert_eq!(alice.opponent, addr("bob"));
+        assert_eq!(
+            (alice.games, alice.wins, alice.losses, alice.gammons_lost),
+            (3, 1, 2, 1)
+        );
+        let bob = query_head_to_head(
+            deps.as_ref(),
+            addr("bob").to_string(),
+            addr("alice").to_string(),
+        )
+        .unwrap();
+        assert_eq!((bob.wins, bob.losses, bob.gammons_won), (2, 1, 1));
+        assert!(bob.last_played.is_some());
+
+        let never = query_head_to_head(
+            deps.as_ref(),
+            addr("bob").to_string(),
+            addr("carol").to_string(),
+        )
+        .unwrap();
+        assert_eq!((never.games, never.last_played), (0, None));
+
+        let rivals = query_rivals(deps.as_ref(), addr("alice").to_string(), None)
+            .unwrap()
+            .rivals;
+        assert_eq!(
+            rivals
+                .iter()
+                .map(|r| (r.opponent.clone(), r.games))
+                .collect::<Vec<_>>(),
+            vec![(addr("bob"), 3), (addr("carol"), 1)]
+        );
+        let rivals = query_rivals(deps.as_ref(), addr("alice").to_string(), Some(1))
+            .unwrap()
+            .rivals;
+        assert_eq!(rivals.len(), 1);
+    }
+
+    #[test]
+    fn test_streaks_and_recent_form_with_backfill() {
+        let mut deps = setup();
+
+        for (at, winner) in [(100, "alice"), (200, "alice"), (300, "bob")] {
+            let game_id = create_game(&mut deps, "alice", "bob", at);
+            report(&mut deps, &game_id, winner);
+        }
+        let game_id = create_game(&mut deps, "alice", "bob", 400);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            ExecuteMsg::ReportAbandonment {
+                game_id,
+                abandoner: addr("alice").to_string(),
+                dice_reveals: None,
+                winner_signature: None,
+            },
+        )
+        .unwrap();
+
+        let alice = query_player_stats(deps.as_ref(), addr("alice").to_string()).unwrap();
+        assert_eq!((alice.win_streak, alice.best_win_streak), (0, 2));
+        assert_eq!(alice.abandonments, 1);
+        // Latest first: loss, loss, win, win
+        assert_eq!((alice.recent_results, alice.recent_games), (0b1100, 4));
+        assert_eq!(alice.last_played, Some(mock_env().block.time.seconds()));
+        assert!(alice.peak_rating > DEFAULT_RATING);
+        assert!(alice.peak_rating_at.is_some());
+
+        // Entries saved before these fields existed are rebuilt on migrate
+        let mut stats = PLAYER_STATS.load(&deps.storage, &addr("alice")).unwrap();
+        stats.best_win_streak = 0;
+        stats.abandonments = 0;
+        stats.peak_rating = 0;
+        stats.peak_rating_at = None;
+        stats.last_played = None;
+        stats.recent_results = 0;
+        stats.recent_games = 0;
+        PLAYER_STATS
+            .save(deps.as_mut().storage, &addr("alice"), &stats)
+            .unwrap();
+        cw2::set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "0.0.1").unwrap();
+        loop {
+            let res = migrate(deps.as_mut(), mock_env(), MigrateMsg { limit: Some(1) }).unwrap();
+            if attr(&res, "migration_complete").unwrap() == "true" {
+                break;
+            }
+        }
+
+        let backfilled = query_player_stats(deps.as_ref(), addr("alice").to_string()).unwrap();
+        assert_eq!(backfilled.best_win_streak, 2);
+        assert_eq!(backfilled.abandonments, 1);
+        assert_eq!(
+            (backfilled.recent_results, backfilled.recent_games),
+            (0b1100, 4)
+        );
+        assert_eq!(backfilled.last_played, alice.last_played);
+        assert_eq!(backfilled.peak_rating, backfilled.rating);
+    }
+
+    #[test]
+    fn test_escrow_activation_starts_game() {
+        let mut deps = setup();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("admin"), &[]),
+            ExecuteMsg::UpdateConfig {
+                escrow_contract: Some(addr("escrow").to_string()),
+                rating_params: None,
+                challenge_period_seconds: None,
+                game_ttl_seconds: None,
+                dispute_period_seconds: None,
+                achievement_nft_contract: None,
+                server_address: None,
+                arbiter: None,
+            },
+        )
+        .unwrap();
+        let game_id = create_game(&mut deps, "alice", "bob", 100);
+        let activated = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, sender: &str| {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr(sender), &[]),
+                ExecuteMsg::EscrowActivated {
+                    game_id: game_id.clone(),
+                },
+            )
+        };
+
+        // Only the configured escrow can start a game this way
+        let res = activated(&mut deps, "server");
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+
+        let res = activated(&mut deps, "escrow").unwrap();
+        assert_eq!(attr(&res, "game_id"), Some(game_id.clone()));
+        assert_eq!(
+            query_game(deps.as_ref(), game_id.clone()).unwrap().status,
+            "InProgress"
+        );
+        assert!(matches!(
+            activated(&mut deps, "escrow").unwrap_err(),
+            ContractError::InvalidGameStatus { .. }
+        ));
+
+        // The server can still declare its dice committed
+        let start = ExecuteMsg::StartGame {
+            game_id: game_id.clone(),
+            commit_dice: Some(true),
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            start.clone(),
+        )
+        .unwrap();
+        assert!(GAMES.load(&deps.storage, &game_id).unwrap().dice_committed);
+        // but only once
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            start,
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::InvalidGameStatus { .. }
+        ));
+
+        // A player withdrawing from the escrow calls the wagered game off,
+        // and the escrow is asked to refund the other player
+        let escrow = addr("escrow").to_string();
+        deps.querier.update_wasm(move |query| match query {
+            cosmwasm_std::WasmQuery::Smart { contract_addr, .. } if *contract_addr == escrow => {
+                let response = r#"{"status":"Withdrawn","player_a_deposited":0,"player_b_deposited":1000000,
+                    "cube_value":1,"pending_double":null,"projected_payout":null,"projected_rake":null}"#;
+                cosmwasm_std::SystemResult::Ok(cosmwasm_std::ContractResult::Ok(Binary::from(
+                    response.as_bytes(),
+                )))
+            }
+            _ => cosmwasm_std::SystemResult::Err(cosmwasm_std::SystemError::UnsupportedRequest {
+                kind: "unexpected query".to_string(),
+            }),
+        });
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            ExecuteMsg::CreateGame {
+                player_a: addr("alice").to_string(),
+                player_b: addr("bob").to_string(),
+                wager_amount: 1_000_000,
+                game_id: None,
+                idempotency_key: None,
+                server_match_id: None,
+            },
+        )
+        .unwrap();
+        let game_id = attr(&res, "game_id").unwrap();
+        let withdrawn = ExecuteMsg::EscrowWithdrawn {
+            game_id: game_id.clone(),
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            withdrawn.clone(),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("escrow"), &[]),
+            withdrawn,
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[..],
+            [SubMsg::new(WasmMsg::Execute {
+                contract_addr: addr("escrow").to_string(),
+                msg: to_json_binary(&EscrowExecuteMsg::Cancel {
+                    game_id: game_id.clone(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })]
+        );
+        assert_eq!(
+            query_game(deps.as_ref(), game_id.clone()).unwrap().status,
+            "Cancelled"
+        );
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            ExecuteMsg::ReportResult {
+                game_id,
+                winner: addr("alice").to_string(),
+                result_type: "normal".to_string(),
+                move_count: 40,
+                dice_reveals: None,
+                move_log: None,
+                move_log_signatures: None,
+                cube: None,
+            },
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::InvalidGameStatus { .. }
+        ));
+    }
+
+    #[test]
+    fn test_game_details_include_escrow() {
+        let mut deps = setup();
+        let unwagered = create_game(&mut deps, "alice", "bob", 100);
+        let create_wagered = ExecuteMsg::CreateGame {
+            player_a: addr("alice").to_string(),
+            player_b: addr("bob").to_string(),
+            wager_amount: 5_000_000,
+            game_id: None,
+            idempotency_key: None,
+            server_match_id: None,
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            create_wagered.clone(),
+        )
+        .unwrap();
+        let before_escrow = attr(&res, "game_id").unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("admin"), &[]),
+            ExecuteMsg::UpdateConfig {
+                escrow_contract: Some(addr("escrow").to_string()),
+                rating_params: None,
+                challenge_period_seconds: None,
+                game_ttl_seconds: None,
+                dispute_period_seconds: None,
+                achievement_nft_contract: None,
+                server_address: None,
+                arbiter: None,
+            },
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            create_wagered,
+        )
+        .unwrap();
+        let wagered = attr(&res, "game_id").unwrap();
+
+        // The escrow answers for the game created after it was set only;
+        // fields this contract does not read are ignored
+        let escrow = addr("escrow").to_string();
+        let escrow_game = wagered.clone();
+        let unknown_game = before_escrow.clone();
+        deps.querier.update_wasm(move |query| match query {
+            cosmwasm_std::WasmQuery::Smart { contract_addr, msg }
+                if *contract_addr == escrow
+                    && *msg
+                        == to_json_binary(&EscrowQueryMsg::FindEscrow {
+                            game_id: escrow_game.clone(),
+                        })
+                        .unwrap() =>
+            {
+                let response = Binary::from(
+                    br#"{"game_id":"x","player_a_deposited":10000000,"player_b_deposited":5000000,
+                        "status":"AwaitingDoubleDeposits","cube_value":1,"winner":null,
+                        "pending_double":{"doubler":"d","responder":"r","new_cube_value":2,
+                        "additional_deposit":5000000,"doubler_deposited":true,
+                        "responder_deposited":false},
+                        "projected_payout":14625000,"projected_rake":375000}"#
+                        .as_slice(),
+                );
+                cosmwasm_std::SystemResult::Ok(cosmwasm_std::ContractResult::Ok(response))
+            }
+            // It never heard of the game created before it was set
+            cosmwasm_std::WasmQuery::Smart { msg, .. }
+                if *msg
+                    == to_json_binary(&EscrowQueryMsg::FindEscrow {
+                        game_id: unknown_game.clone(),
+                    })
+                    .unwrap() =>
+            {
+                cosmwasm_std::SystemResult::Ok(cosmwasm_std::ContractResult::Ok(Binary::from(
+                    b"null".as_slice(),
+                )))
+            }
+            _ => cosmwasm_std::SystemResult::Ok(cosmwasm_std::ContractResult::Err(
+                "escrow contract unavailable: not found".to_string(),
+            )),
+        });
+
+        let details: GameDetailsResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GameDetails {
+                    game_id: wagered.clone(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(details.game.game_id, wagered);
+        let escrow = details.escrow.unwrap();
+        assert_eq!(escrow.escrow_id, wagered);
+        assert_eq!(
+            (escrow.player_a_deposited, escrow.player_b_deposited),
+            (10_000_000, 5_000_000)
+        );
+        assert_eq!(escrow.pending_double.unwrap().new_cube_value, 2);
+        assert_eq!(escrow.projected_payout, Some(14_625_000));
+
+        // Unwagered games have no escrow to ask
+        let details = query_game_details(deps.as_ref(), unwagered).unwrap();
+        assert!(details.escrow.is_none());
+
+        // Nor did the escrow hear of a wagered game created before it was set
+        let details = query_game_details(deps.as_ref(), before_escrow).unwrap();
+        assert!(details.escrow.is_none());
+
+        // Any other failure to query the escrow is passed on, whatever it says
+        assert!(query_escrow(&deps.as_ref().querier, &addr("escrow"), "match-1").is_err());
+    }
+
+    #[test]
+    fn test_client_game_ids_and_idempotent_creation() {
+        let mut deps = setup();
+        let create = |game_id: Option<&str>, key: Option<&str>, server_match_id: Option<&str>| {
+            ExecuteMsg::CreateGame {
+                player_a: addr("alice").to_string(),
+                player_b: addr("bob").to_string(),
+                wager_amount: 0,
+                game_id: game_id.map(str::to_string),
+                idempotency_key: key.map(str::to_string),
+                server_match_id: server_match_id.map(str::to_string),
+            }
+        };
+        let run = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, msg| {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr("server"), &[]),
+                msg,
+            )
+        };
+
+        for bad in ["", "has space", "game-7", "match-1", &"x".repeat(65)] {
+            assert!(matches!(
+                run(&mut deps, create(Some(bad), None, None)).unwrap_err(),
+                ContractError::InvalidId { .. }
+            ));
+        }
+
+        let res = run(
+            &mut deps,
+            create(Some("srv_42"), Some("retry-1"), Some("m-42")),
+        )
+        .unwrap();
+        assert_eq!(attr(&res, "game_id").unwrap(), "srv_42");
+        assert_eq!(
+            query_game(deps.as_ref(), "srv_42".to_string())
+                .unwrap()
+                .status,
+            "Created"
+        );
+
+        // A retry returns the same game without creating another
+        let res = run(
+            &mut deps,
+            create(Some("srv_42"), Some("retry-1"), Some("m-42")),
+        )
+        .unwrap();
+        assert_eq!(attr(&res, "game_id").unwrap(), "srv_42");
+        assert_eq!(attr(&res, "duplicate").unwrap(), "true");
+        assert_eq!(TOTAL_GAMES.load(&deps.storage).unwrap(), 1);
+
+        // even when it reaches the chain through another Reporter
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("admin"), &[]),
+            ExecuteMsg::GrantRole {
+                role: Role::Reporter,
+                address: addr("server2").to_string(),
+            },
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server2"), &[]),
+            create(Some("srv_42"), Some("retry-1"), Some("m-42")),
+        )
+        .unwrap();
+        assert_eq!(attr(&res, "duplicate").unwrap(), "true");
+        assert_eq!(TOTAL_GAMES.load(&deps.storage).unwrap(), 1);
+
+        // but the key cannot be reused for a different game
+        let taken = ContractError::GameAlreadyExists {
+            game_id: "srv_42".to_string(),
+        };
+        for msg in [
+            create(Some("srv_43"), Some("retry-1"), Some("m-42")),
+            create(Some("srv_42"), Some("retry-1"), Some("m-43")),
+            create(Some("srv_42"), Some("retry-1"), None),
+            create(None, Some("retry-1"), None),
+            {
+                let mut msg = create(Some("srv_42"), Some("retry-1"), Some("m-42"));
+                if let ExecuteMsg::CreateGame { wager_amount, .. } = &mut msg {
+                    *wager_amount = 5;
+                }
+                msg
+            },
+            {
+                let mut msg = create(Some("srv_42"), Some("retry-1"), Some("m-42"));
+                if let ExecuteMsg::CreateGame { player_b, .. } = &mut msg {
+                    *player_b = addr("carol").to_string();
+                }
+                msg
+            },
+        ] {
+            assert_eq!(run(&mut deps, msg).unwrap_err(), taken);
+        }
+        assert_eq!(TOTAL_GAMES.load(&deps.storage).unwrap(), 1);
+        assert!(!SERVER_MATCH_GAMES.has(&deps.storage, "m-43"));
+
+        // IDs and server match IDs are unique
+        assert_eq!(
+            run(&mut deps, create(Some("srv_42"), None, None)).unwrap_err(),
+            ContractError::GameAlreadyExists {
+                game_id: "srv_42".to_string()
+            }
+        );
+        assert_eq!(
+            run(&mut deps, create(None, None, Some("m-42"))).unwrap_err(),
+            ContractError::GameAlreadyExists {
+                game_id: "srv_42".to_string()
+            }
+        );
+
+        let game: GameResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GameByServerMatch {
+                    server_match_id: "m-42".to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(game.game_id, "srv_42");
+        assert_eq!(game.server_match_id.as_deref(), Some("m-42"));
+
+        // Generated IDs are unaffected
+        let res = run(&mut deps, create(None, None, None)).unwrap();
+        assert_eq!(attr(&res, "game_id").unwrap(), "game-1");
+    }
+
+    #[test]
+    fn test_create_game_is_reporter_only() {
+        let mut deps = setup();
+        let create = ExecuteMsg::CreateGame {
+            player_a: addr("alice").to_string(),
+            player_b: addr("bob").to_string(),
+            wager_amount: 1_000_000,
+            game_id: None,
+            idempotency_key: None,
+            server_match_id: None,
+        };
+
+        // Players, including the one the game would be created for, go
+        // through ProposeGame/AcceptGame so the opponent consents on-chain
+        for name in ["alice", "bob", "carol"] {
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr(name), &[]),
+                create.clone(),
+            );
+            assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+        }
+        assert_eq!(TOTAL_GAMES.load(&deps.storage).unwrap(), 0);
+
+        // A revoked Reporter loses the exception too
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("admin"), &[]),
+            ExecuteMsg::RevokeRole {
+                role: Role::Reporter,
+                address: addr("server").to_string(),
+            },
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            create.clone(),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+
+        // and a game a Reporter creates can be called off by either player
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("admin"), &[]),
+            ExecuteMsg::GrantRole {
+                role: Role::Reporter,
+                address: addr("server").to_string(),
+            },
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("server"), &[]),
+            create,
+        )
+        .unwrap();
+        let game_id = attr(&res, "game_id").unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("bob"), &[]),
+            ExecuteMsg::CancelGame {
+                game_id: game_id.clone(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            query_game(deps.as_ref(), game_id).unwrap().status,
+            "Cancelled"
+        );
+    }
 }
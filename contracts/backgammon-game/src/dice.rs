@@ -0,0 +1,113 @@
+//! Commit–reveal dice, matching the game server's `dice.ts`.
+//!
+//! Every turn gets its own random server seed. Before the roll the server
+//! publishes the turn's commitment `sha256(serverSeed)`, and the dice come
+//! from `sha256(serverSeed + clientSeed + turn)`, all over the hex/decimal
+//! strings exactly as the server concatenates them. A revealed turn is
+//! checked on its own against the commitment published for it.
+
+use cosmwasm_std::HexBinary;
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+use crate::state::DiceReveal;
+
+/// Hex-encoded sha256 of a seed string, as `dice.ts` computes commitments
+pub fn commit_hash(server_seed: &str) -> String {
+    HexBinary::from(Sha256::digest(server_seed.as_bytes()).as_slice()).to_hex()
+}
+
+/// Dice for a turn: the first two bytes of sha256(serverSeed + clientSeed + turn)
+pub fn derive_dice(server_seed: &str, client_seed: &str, turn: u32) -> [u8; 2] {
+    let mut hasher = Sha256::new();
+    hasher.update(server_seed.as_bytes());
+    hasher.update(client_seed.as_bytes());
+    hasher.update(turn.to_string().as_bytes());
+    let digest = hasher.finalize();
+    [digest[0] % 6 + 1, digest[1] % 6 + 1]
+}
+
+/// Checks that `reveals` cover turns 1..=n in order and that every seed
+/// hashes to the commitment published for its turn.
+pub fn verify_reveals(reveals: &[DiceReveal]) -> Result<(), ContractError> {
+    for (i, reveal) in reveals.iter().enumerate() {
+        let expected_turn = i as u32 + 1;
+        if reveal.turn != expected_turn {
+            return Err(ContractError::InvalidDiceReveal {
+                turn: reveal.turn,
+                reason: format!("expected turn {}", expected_turn),
+            });
+        }
+        if commit_hash(&reveal.server_seed) != reveal.commit_hash {
+            return Err(ContractError::InvalidDiceReveal {
+                turn: reveal.turn,
+                reason: "server seed does not match commitment".to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reveals for `turns` turns, each with an independent seed, as
+    /// `dice.ts` produces them
+    fn reveals(turns: u32) -> Vec<DiceReveal> {
+        (1..=turns)
+            .map(|turn| {
+                let server_seed = format!("{:064x}", turn * 7919);
+                DiceReveal {
+                    turn,
+                    commit_hash: commit_hash(&server_seed),
+                    server_seed,
+                    client_seed: "xion1player".to_string(),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_derive_dice_matches_server() {
+        // Seeds and turn number are concatenated as strings before hashing
+        let digest = Sha256::digest(b"abcdef7");
+        let expected = [digest[0] % 6 + 1, digest[1] % 6 + 1];
+        assert_eq!(derive_dice("abc", "def", 7), expected);
+        for die in derive_dice("abc", "def", 7) {
+            assert!((1..=6).contains(&die));
+        }
+    }
+
+    #[test]
+    fn test_commit_hash_is_hex_of_string() {
+        assert_eq!(
+            commit_hash("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_valid_reveals_verify() {
+        verify_reveals(&reveals(5)).unwrap();
+        verify_reveals(&[]).unwrap();
+    }
+
+    #[test]
+    fn test_tampered_seed_is_rejected() {
+        let mut reveals = reveals(5);
+        reveals[2].server_seed = "00".repeat(32);
+        let err = verify_reveals(&reveals).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::InvalidDiceReveal { turn: 3, .. }
+        ));
+    }
+
+    #[test]
+    fn test_gap_in_turns_is_rejected() {
+        let mut reveals = reveals(3);
+        reveals.remove(1);
+        assert!(verify_reveals(&reveals).is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use platform_roles::RoleError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -9,12 +10,30 @@ pub enum ContractError {
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("{operation} is paused")]
+    Paused { operation: String },
+
+    #[error("No admin transfer is pending")]
+    NoPendingAdmin {},
+
+    #[error("Admin transfer expired at {expires_at}")]
+    AdminTransferExpired { expires_at: u64 },
+
+    #[error("Revoke every granted role before renouncing admin")]
+    RolesRemain {},
+
+    #[error("Invalid admin transfer expiry {seconds}s: must be between 1 and {max}")]
+    InvalidAdminTransferExpiry { seconds: u64, max: u64 },
+
     #[error("Game not found: {game_id}")]
     GameNotFound { game_id: String },
 
     #[error("Game already exists: {game_id}")]
     GameAlreadyExists { game_id: String },
 
+    #[error("Invalid ID {id}: {reason}")]
+    InvalidId { id: String, reason: String },
+
     #[error("Invalid game status: expected {expected}, got {got}")]
     InvalidGameStatus { expected: String, got: String },
 
@@ -26,4 +45,127 @@ pub enum ContractError {
 
     #[error("Cannot play against yourself")]
     SelfPlay {},
+
+    #[error("Invalid match length {match_length}: must be between 1 and {max}")]
+    InvalidMatchLength { match_length: u32, max: u32 },
+
+    #[error("Invalid cube: {reason}")]
+    InvalidCube { reason: String },
+
+    #[error("Invalid dice reveal for turn {turn}: {reason}")]
+    InvalidDiceReveal { turn: u32, reason: String },
+
+    #[error("Game {game_id} has a dice commitment; its seeds must be revealed")]
+    DiceRevealRequired { game_id: String },
+
+    #[error("Game {game_id} has no dice commitment to check reveals against")]
+    DiceNotCommitted { game_id: String },
+
+    #[error("Invalid move log commitment: {reason}")]
+    InvalidMoveLog { reason: String },
+
+    #[error("Game {game_id} cannot be disputed: {reason}")]
+    DisputeNotAllowed { game_id: String, reason: String },
+
+    #[error("Invalid secp256k1 public key")]
+    InvalidSigningKey {},
+
+    #[error("No signing key registered for {address}")]
+    SigningKeyNotRegistered { address: String },
+
+    #[error("Invalid result signature from {signer}")]
+    InvalidSignature { signer: String },
+
+    #[error("Nonce {nonce} already used: must be greater than {last}")]
+    StaleNonce { nonce: u64, last: u64 },
+
+    #[error("Both players of game {game_id} co-sign results; the server cannot report it without their signatures")]
+    CoSignatureRequired { game_id: String },
+
+    #[error("Challenge period for game {game_id} ended at {deadline}")]
+    ChallengePeriodEnded { game_id: String, deadline: u64 },
+
+    #[error("Challenge period for game {game_id} runs until {deadline}")]
+    ChallengePeriodActive { game_id: String, deadline: u64 },
+
+    #[error("Result of game {game_id} is already challenged")]
+    AlreadyChallenged { game_id: String },
+
+    #[error("Result of game {game_id} has not been challenged")]
+    NotChallenged { game_id: String },
+
+    #[error("Game {game_id} cannot be cancelled: {reason}")]
+    CancelNotAllowed { game_id: String, reason: String },
+
+    #[error("Match {match_id} cannot be expired: {reason}")]
+    ExpireNotAllowed { match_id: String, reason: String },
+
+    #[error("Challenge not found: {challenge_id}")]
+    ChallengeNotFound { challenge_id: String },
+
+    #[error("Challenge {challenge_id} is no longer open")]
+    ChallengeNotOpen { challenge_id: String },
+
+    #[error("Invalid or missing invite signature")]
+    InvalidInvite {},
+
+    #[error("Challenge {challenge_id} expired at {expires_at}")]
+    ChallengeExpired {
+        challenge_id: String,
+        expires_at: u64,
+    },
+
+    #[error("Invalid challenge expiry {seconds}s: must be between 1 and {max}")]
+    InvalidChallengeExpiry { seconds: u64, max: u64 },
+
+    #[error("Invalid rating range: min {min} is above max {max}")]
+    InvalidRatingRange { min: u32, max: u32 },
+
+    #[error("Rating {rating} is outside the challenge's allowed range")]
+    RatingOutOfRange { rating: u32 },
+
+    #[error("Proposal not found: {proposal_id}")]
+    ProposalNotFound { proposal_id: String },
+
+    #[error("Proposal {proposal_id} is no longer pending")]
+    ProposalNotPending { proposal_id: String },
+
+    #[error("Proposal {proposal_id} expired at {expires_at}")]
+    ProposalExpired {
+        proposal_id: String,
+        expires_at: u64,
+    },
+
+    #[error("Invalid proposal expiry {seconds}s: must be between 1 and {max}")]
+    InvalidProposalExpiry { seconds: u64, max: u64 },
+
+    #[error("Season {season_id} is still running")]
+    SeasonActive { season_id: u32 },
+
+    #[error("No season is running")]
+    NoActiveSeason {},
+
+    #[error("Season {season_id} is still being ended; repeat EndSeason")]
+    SeasonEnding { season_id: u32 },
+
+    #[error("Invalid reset percent {percent}: must be at most 100")]
+    InvalidResetPercent { percent: u32 },
+
+    #[error("Invalid rating params: {reason}")]
+    InvalidRatingParams { reason: String },
+
+    #[error("Invalid migration: {reason}")]
+    InvalidMigration { reason: String },
+
+    #[error("Invalid escrow settlement reply: {reason}")]
+    InvalidEscrowReply { reason: String },
+}
+
+impl From<RoleError> for ContractError {
+    fn from(err: RoleError) -> Self {
+        match err {
+            RoleError::Std(err) => ContractError::Std(err),
+            RoleError::Unauthorized {} => ContractError::Unauthorized {},
+        }
+    }
 }
@@ -1,6 +1,11 @@
+pub mod achievements;
 pub mod contract;
+pub mod dice;
 pub mod error;
+pub mod merkle;
 pub mod msg;
+pub mod rating;
+pub mod rules;
 pub mod state;
 
 pub use crate::error::ContractError;
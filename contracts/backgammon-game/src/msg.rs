@@ -1,51 +1,336 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Binary, HexBinary};
+
+use crate::state::{
+    CubeResult, DiceReveal, EarnedAchievement, LoggedTurn, MoveLogCommitment, Operation,
+    PendingAdmin, PlatformStats, RatingParams, Role, SeasonStanding,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub escrow_contract: Option<String>,
+    /// Initial Reporter; more can be granted with `GrantRole`
     pub server_address: Option<String>,
     pub usdc_denom: String,
+    /// Rating model parameters; FIBS defaults when omitted
+    pub rating_params: Option<RatingParams>,
+    /// Challenge period for reported results, in seconds; 0 or omitted disables it
+    pub challenge_period_seconds: Option<u64>,
+    /// Initial Arbiter
+    pub arbiter: Option<String>,
+    /// Seconds a game may sit unstarted before it can be expired; 0 or
+    /// omitted disables expiry
+    pub game_ttl_seconds: Option<u64>,
+    /// Seconds after completion a result can be disputed; 0 or omitted
+    /// leaves results open to dispute indefinitely
+    pub dispute_period_seconds: Option<u64>,
+    /// CW721 contract to mint achievement badges on
+    pub achievement_nft_contract: Option<String>,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    /// Create a new game. Also creates an escrow if escrow_contract is set.
+    /// Reporter: create a game between two players who agreed to it
+    /// off-chain. Also creates an escrow if escrow_contract is set.
+    /// This is the one way to create a game without the opponent's on-chain
+    /// consent (see `ProposeGame`/`AcceptGame`); it is kept for the server's
+    /// lobby, which collects that consent itself. It cannot take a player's
+    /// funds: nothing is staked until each player deposits into the escrow,
+    /// and either player can `CancelGame` until it starts.
+    /// `game_id` replaces the generated `game-N` ID; it takes 1-64 letters,
+    /// digits, '-' or '_' and may not start with `game-` or `match-`.
+    /// Retrying with the same `idempotency_key`, from any Reporter, returns
+    /// the game the first attempt created; a retry whose players, wager,
+    /// `game_id` or `server_match_id` differ fails with `GameAlreadyExists`.
+    /// `server_match_id` records the server's own ID for the game, looked up
+    /// with `GameByServerMatch`.
     CreateGame {
+        player_a: String,
+        player_b: String,
+        wager_amount: u128,
+        game_id: Option<String>,
+        idempotency_key: Option<String>,
+        server_match_id: Option<String>,
+    },
+
+    /// Offer a game to `opponent`. Nothing is created until they accept.
+    /// The offer lapses after `expires_in` seconds (default one day).
+    ProposeGame {
         opponent: String,
         wager_amount: u128,
+        expires_in: Option<u64>,
     },
 
-    /// Mark game as in progress (both players deposited)
+    /// Opponent: accept a pending proposal, creating the game (and its
+    /// escrow) with the proposer as player A
+    AcceptGame { proposal_id: String },
+
+    /// Opponent: turn down a pending proposal
+    DeclineGame { proposal_id: String },
+
+    /// Proposer: withdraw a proposal that has not been answered yet
+    CancelProposal { proposal_id: String },
+
+    /// Post a game with an open seat. Any player whose rating falls within
+    /// the bounds (stored units, rating * 100) can accept it until it
+    /// expires (`expires_in` seconds, default one day). If `invite_pubkey`
+    /// (secp256k1) is set, the challenge is private: the creator shares the
+    /// invite's private key, and an acceptor must sign for their own address
+    /// with it (see `InviteSigningPayload`).
+    CreateOpenChallenge {
+        wager_amount: u128,
+        min_rating: Option<u32>,
+        max_rating: Option<u32>,
+        invite_pubkey: Option<Binary>,
+        expires_in: Option<u64>,
+    },
+
+    /// Take the open seat, creating the game (and its escrow) with the
+    /// challenge's creator as player A. Private challenges need the invite
+    /// key's signature over the acceptor's payload.
+    AcceptChallenge {
+        challenge_id: String,
+        invite_signature: Option<Binary>,
+    },
+
+    /// Creator: withdraw a challenge nobody has accepted yet
+    CancelChallenge { challenge_id: String },
+
+    /// Reporter: create an N-point match between two players who agreed to
+    /// it off-chain, and its first game. The wager covers the whole match
+    /// and is escrowed under the match ID. Later games are opened
+    /// automatically as results are reported.
+    CreateMatch {
+        player_a: String,
+        player_b: String,
+        match_length: u32,
+        wager_amount: u128,
+    },
+
+    /// Mark game as in progress (both players deposited).
+    /// `commit_dice` declares that the server publishes a commitment to each
+    /// turn's seed before rolling, as `dice.ts` does, so the seeds must be
+    /// revealed when the game ends; see `dice.rs` for the scheme.
     StartGame {
         game_id: String,
+        commit_dice: Option<bool>,
     },
 
-    /// Report game result. Only callable by server_address or admin.
-    /// Updates player stats and triggers escrow settlement.
+    /// Escrow contract hook: both players have deposited into the escrow
+    /// held under `game_id` (the match ID for matches), so its waiting game
+    /// moves to InProgress. Its dice can still be declared committed with
+    /// `StartGame` until its result is reported.
+    EscrowActivated { game_id: String },
+
+    /// Escrow contract hook: a rejected double forfeited the escrow held
+    /// under `game_id`, paying `payout` to `winner`. Books the payout, rake
+    /// and stakes in the players' stats. The unfinished game is completed
+    /// as won by `winner` at `cube_value`, the cube the double was passed
+    /// at, and its match with it; no result is reported for it.
+    EscrowForfeited {
+        game_id: String,
+        winner: String,
+        loser: String,
+        payout: u128,
+        rake: u128,
+        winner_stake: u128,
+        loser_stake: u128,
+        /// Escrows that predate it don't send it; the game is then worth 1
+        #[serde(default)]
+        cube_value: u32,
+    },
+
+    /// Escrow contract hook: a player withdrew from the escrow held under
+    /// `game_id` in withdraw-only mode, so it can never settle. Its
+    /// unfinished game, and match, are cancelled and the escrow asked to
+    /// refund the other player.
+    EscrowWithdrawn { game_id: String },
+
+    /// Either player (or a Reporter): call off a game that has not
+    /// started. Any deposits are refunded by the escrow. Cancelling the
+    /// first game of a match cancels the match.
+    CancelGame { game_id: String },
+
+    /// Anyone: expire up to `limit` games that have waited in Created past
+    /// the configured TTL, oldest first, refunding their escrows
+    ExpireGames { limit: Option<u32> },
+
+    /// Anyone: expire a match whose current game has gone unfinished for
+    /// longer than the game TTL. The match is called off and its escrow
+    /// refunded; games already played keep their results.
+    ExpireMatch { match_id: String },
+
+    /// Report game result. Only callable by a Reporter.
+    /// Updates player stats and triggers escrow settlement. For a game in a
+    /// match, the points are scored into the match, and settlement and
+    /// rating happen only when the match ends. With a challenge period
+    /// configured, the result is held as PendingResult until finalized.
+    /// Games whose players both registered signing keys need
+    /// `move_log_signatures`, or `SubmitSignedResult` instead.
     ReportResult {
         game_id: String,
         winner: String,
         result_type: String, // "normal", "gammon", or "backgammon"
         move_count: u32,
+        /// Seeds for every turn, in order. Required if the game was started
+        /// with committed dice, and rejected otherwise; each is checked
+        /// against its turn's commitment and stored.
+        dice_reveals: Option<Vec<DiceReveal>>,
+        /// Merkle root of the game's move/roll log, checkable later with
+        /// `VerifyMoveProof`
+        move_log: Option<MoveLogCommitment>,
+        /// Both players' signatures over `move_log` (see
+        /// `MoveLogSigningPayload`); without them the result cannot be
+        /// disputed
+        move_log_signatures: Option<MoveLogSignatures>,
+        /// Final doubling cube; omit if it was never turned
+        cube: Option<CubeReport>,
     },
 
     /// Report game abandonment (disconnect/timeout).
-    /// Only callable by server_address or admin.
+    /// Only callable by a Reporter. With a challenge period configured, the
+    /// abandonment is held as PendingResult like any other result.
     ReportAbandonment {
         game_id: String,
         abandoner: String,
+        /// Seeds for the turns rolled before the game was abandoned, as for
+        /// `ReportResult`
+        dice_reveals: Option<Vec<DiceReveal>>,
+        /// Required when both players registered signing keys: the winner's
+        /// signature over `AbandonmentSigningPayload`
+        winner_signature: Option<Binary>,
+    },
+
+    /// Either player: challenge a pending result within the challenge period.
+    /// An Arbiter then resolves it.
+    ChallengeResult { game_id: String },
+
+    /// Arbiter: settle a challenged result with the correct outcome
+    ResolveChallenge {
+        game_id: String,
+        winner: String,
+        result_type: String,
+    },
+
+    /// Anyone: finalize an unchallenged pending result once the challenge
+    /// period is over
+    FinalizeResult { game_id: String },
+
+    /// Register (or replace) the caller's secp256k1 public key for
+    /// co-signing results
+    RegisterSigningKey { pubkey: Binary },
+
+    /// Record a result both players signed, without the server. Anyone can
+    /// submit it, and it is final immediately since neither player can
+    /// dispute it. Each player signs the sha256 of the canonical payload (see
+    /// `ResultSigningPayload`); `nonce` must be above the last one accepted
+    /// for the game, so a signed result can only be used once. Committed
+    /// dice are revealed as for `ReportResult`.
+    SubmitSignedResult {
+        game_id: String,
+        winner: String,
+        result_type: String,
+        nonce: u64,
+        cube: Option<CubeReport>,
+        dice_reveals: Option<Vec<DiceReveal>>,
+        signature_a: Binary,
+        signature_b: Binary,
     },
 
-    /// Admin: update configuration
+    /// Dispute a reported result. Either player submits the full move log,
+    /// which must match the committed Merkle root that both players signed
+    /// when the result was reported (and the dice commitment, if any). The
+    /// contract replays it; if the verdict differs from the report it
+    /// overrides the result, corrects stats and ratings, and asks the escrow
+    /// to re-settle with the corrected winner and multiplier. Standalone
+    /// games only, one dispute per game, within `dispute_period_seconds` of
+    /// completion if one is configured.
+    DisputeResult {
+        game_id: String,
+        turns: Vec<LoggedTurn>,
+    },
+
+    /// Operator: start a new season. `reset_percent` (default 50) is how far
+    /// ratings are pulled back toward the default when it ends.
+    StartSeason {
+        name: String,
+        reset_percent: Option<u32>,
+    },
+
+    /// Operator: end the running season, archive its standings and soft-reset
+    /// every rating. The first call ends the season; each call then processes
+    /// up to `limit` players, so repeat it until it reports `complete`.
+    EndSeason { limit: Option<u32> },
+
+    /// Pauser: halt the given operations until they are unpaused
+    Pause { operations: Vec<Operation> },
+
+    /// Pauser: resume the given operations
+    Unpause { operations: Vec<Operation> },
+
+    /// Admin: propose `address` as the next admin. It takes over once it
+    /// sends `AcceptAdmin` before the proposal expires (default 7 days, at
+    /// most 30). Replaces any earlier proposal.
+    ProposeNewAdmin {
+        address: String,
+        expires_in: Option<u64>,
+    },
+
+    /// Proposed admin: accept a pending admin transfer
+    AcceptAdmin {},
+
+    /// Admin: withdraw a pending admin transfer
+    CancelAdminTransfer {},
+
+    /// Admin: give up admin rights for good. The contract becomes its own
+    /// admin, so nothing can change the configuration or roles afterwards.
+    /// Every granted role must be revoked first.
+    RenounceAdmin {},
+
+    /// Admin: give `address` a role
+    GrantRole { role: Role, address: String },
+
+    /// Admin: take a role away from `address`
+    RevokeRole { role: Role, address: String },
+
+    /// ConfigAdmin: update configuration
     UpdateConfig {
         escrow_contract: Option<String>,
+        rating_params: Option<RatingParams>,
+        challenge_period_seconds: Option<u64>,
+        game_ttl_seconds: Option<u64>,
+        dispute_period_seconds: Option<u64>,
+        achievement_nft_contract: Option<String>,
+        /// Deprecated: replaces every Reporter with this address, as it
+        /// replaced the server before roles. Admin only; use
+        /// `GrantRole`/`RevokeRole`.
         server_address: Option<String>,
+        /// Deprecated: replaces every Arbiter with this address. Admin
+        /// only; use `GrantRole`/`RevokeRole`.
+        arbiter: Option<String>,
     },
 }
 
+/// The doubling cube as it stood when a game ended
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct CubeReport {
+    /// Final cube value: a power of two up to 64
+    pub value: u32,
+    /// Player holding the cube; required once it has been turned
+    pub owner: Option<String>,
+    /// Doubles offered by player A and player B, including a final passed one
+    pub doubles_a: u32,
+    pub doubles_b: u32,
+    /// The loser passed the winner's last double, ending the game
+    pub passed: bool,
+}
+
+/// A migration runs in batches of `limit` entries; repeat `migrate` until
+/// it reports `migration_complete`
+#[cw_serde]
+pub struct MigrateMsg {
+    pub limit: Option<u32>,
+}
 
 #[cw_serde]
 #[derive(QueryResponses)]
@@ -53,20 +338,184 @@ pub enum QueryMsg {
     #[returns(ConfigResponse)]
     Config {},
 
+    /// Holders of each role, besides the admin who holds them all
+    #[returns(RolesResponse)]
+    Roles {},
+
+    /// The current admin and any transfer awaiting acceptance
+    #[returns(PendingAdminResponse)]
+    PendingAdmin {},
+
+    /// Operations currently halted by a Pauser
+    #[returns(PauseStatusResponse)]
+    PauseStatus {},
+
     #[returns(GameResponse)]
     Game { game_id: String },
 
+    /// The game created for a server match ID (see `CreateGame`)
+    #[returns(GameResponse)]
+    GameByServerMatch { server_match_id: String },
+
+    /// A game together with its escrow's deposits, cube and projected
+    /// payout, read from the configured escrow contract
+    #[returns(GameDetailsResponse)]
+    GameDetails { game_id: String },
+
+    #[returns(MatchResponse)]
+    Match { match_id: String },
+
     #[returns(PlayerStatsResponse)]
     PlayerStats { address: String },
 
     #[returns(StatsResponse)]
     Stats {},
 
+    /// Platform stats per UTC day, oldest first, for days with activity
+    /// between `start_time` and `end_time` (inclusive, in seconds). Page by
+    /// passing the day after the last one returned as `start_time`.
+    #[returns(DailyStatsResponse)]
+    DailyStats {
+        start_time: Option<u64>,
+        end_time: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// Players ordered by rating, highest first. Page with the previous
+    /// page's `next_start_after`; `min_games` hides players with fewer games,
+    /// so a page may come back short while more remain.
+    #[returns(LeaderboardResponse)]
+    Leaderboard {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        min_games: Option<u32>,
+    },
+
+    #[returns(ChallengeResponse)]
+    Challenge { challenge_id: String },
+
+    /// Unexpired challenges still waiting for an opponent, soonest to expire
+    /// first. Page with the last challenge_id of the previous page.
+    #[returns(ChallengesResponse)]
+    OpenChallenges {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// The exact bytes the invite key signs to let `acceptor` take a
+    /// private challenge, and their hash
+    #[returns(ResultSigningPayloadResponse)]
+    InviteSigningPayload {
+        challenge_id: String,
+        acceptor: String,
+    },
+
+    #[returns(ProposalResponse)]
+    Proposal { proposal_id: String },
+
+    /// Unexpired proposals waiting on `address` to answer, soonest to
+    /// expire first. Page with the last proposal_id of the previous page.
+    #[returns(ProposalsResponse)]
+    PendingProposals {
+        address: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(SeasonResponse)]
+    Season { season_id: u32 },
+
+    #[returns(CurrentSeasonResponse)]
+    CurrentSeason {},
+
+    /// Final standings of an ended season by rank. Page with the last rank
+    /// of the previous page.
+    #[returns(SeasonStandingsResponse)]
+    SeasonStandings {
+        season_id: u32,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    },
+
+    /// A player's placings in past seasons, oldest first. Page with the last
+    /// season_id of the previous page.
+    #[returns(SeasonStandingsResponse)]
+    PlayerSeasons {
+        address: String,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    },
+
+    /// Achievements a player has earned, in the order they are checked
+    #[returns(PlayerAchievementsResponse)]
+    PlayerAchievements { address: String },
+
+    /// A player's 1-based position on the unfiltered leaderboard: one more
+    /// than the players rated above them, so tied players share a rank
+    #[returns(PlayerRankResponse)]
+    PlayerRank { address: String },
+
+    /// The record between two players, from `a`'s side
+    #[returns(HeadToHeadResponse)]
+    HeadToHead { a: String, b: String },
+
+    /// A player's opponents, most-played first
+    #[returns(RivalsResponse)]
+    Rivals { address: String, limit: Option<u32> },
+
+    /// Recompute a turn's dice from the revealed seeds and check the seed
+    /// against the game's commitment.
+    #[returns(VerifyRollResponse)]
+    VerifyRoll { game_id: String, turn: u32 },
+
+    /// Check that `leaf` is entry `index` (0-based) of the game's committed
+    /// move/roll log. `proof` lists sibling hashes from the leaf upwards.
+    #[returns(VerifyMoveProofResponse)]
+    VerifyMoveProof {
+        game_id: String,
+        index: u32,
+        leaf: Binary,
+        proof: Vec<HexBinary>,
+    },
+
+    /// The exact bytes players sign for `SubmitSignedResult`, and their hash
+    #[returns(ResultSigningPayloadResponse)]
+    ResultSigningPayload {
+        game_id: String,
+        winner: String,
+        result_type: String,
+        nonce: u64,
+        cube: Option<CubeReport>,
+    },
+
+    /// The exact bytes a winner signs to confirm `abandoner` left the game,
+    /// for `ReportAbandonment` on co-signed games, and their hash
+    #[returns(ResultSigningPayloadResponse)]
+    AbandonmentSigningPayload { game_id: String, abandoner: String },
+
+    /// The exact bytes both players sign to vouch for a game's move log
+    /// before it is reported, and their hash
+    #[returns(ResultSigningPayloadResponse)]
+    MoveLogSigningPayload {
+        game_id: String,
+        root: HexBinary,
+        leaf_count: u32,
+    },
+
+    #[returns(DisputeResponse)]
+    Dispute { game_id: String },
+
+    /// Games a player took part in, newest first. `start_after` is the
+    /// last game_id of the previous page. Optional filters narrow by status
+    /// (e.g. "Completed") and by `created_at` range (inclusive, in seconds).
     #[returns(GamesListResponse)]
     PlayerGames {
         address: String,
         start_after: Option<String>,
         limit: Option<u32>,
+        status: Option<String>,
+        start_time: Option<u64>,
+        end_time: Option<u64>,
     },
 }
 
@@ -74,8 +523,40 @@ pub enum QueryMsg {
 pub struct ConfigResponse {
     pub admin: Addr,
     pub escrow_contract: Option<Addr>,
-    pub server_address: Option<Addr>,
     pub usdc_denom: String,
+    pub rating_params: RatingParams,
+    pub challenge_period_seconds: u64,
+    pub game_ttl_seconds: u64,
+    pub dispute_period_seconds: u64,
+    pub achievement_nft_contract: Option<Addr>,
+    /// Deprecated: the first Reporter, kept for older clients. The `Roles`
+    /// query lists every holder.
+    pub server_address: Option<Addr>,
+    /// Deprecated: the first Arbiter, kept for older clients
+    pub arbiter: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct RoleMembers {
+    pub role: Role,
+    pub members: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct RolesResponse {
+    pub admin: Addr,
+    pub roles: Vec<RoleMembers>,
+}
+
+#[cw_serde]
+pub struct PauseStatusResponse {
+    pub paused: Vec<Operation>,
+}
+
+#[cw_serde]
+pub struct PendingAdminResponse {
+    pub admin: Addr,
+    pub pending: Option<PendingAdmin>,
 }
 
 #[cw_serde]
@@ -90,6 +571,163 @@ pub struct GameResponse {
     pub created_at: u64,
     pub completed_at: Option<u64>,
     pub move_count: u32,
+    pub match_id: Option<String>,
+    pub dice_committed: bool,
+    pub move_log: Option<MoveLogCommitment>,
+    pub move_log_signed: bool,
+    pub result_reported_at: Option<u64>,
+    pub challenge_deadline: Option<u64>,
+    pub abandoned_by: Option<Addr>,
+    pub challenged_by: Option<Addr>,
+    pub cube: Option<CubeResult>,
+    pub points_won: u32,
+    pub server_match_id: Option<String>,
+}
+
+#[cw_serde]
+pub struct GameDetailsResponse {
+    pub game: GameResponse,
+    /// None for unwagered games, when no escrow contract is configured, or
+    /// when the escrow holds nothing for the game yet
+    pub escrow: Option<EscrowDetails>,
+}
+
+/// A game's escrow, held under the match ID for match games
+#[cw_serde]
+pub struct EscrowDetails {
+    pub escrow_id: String,
+    pub status: String,
+    pub player_a_deposited: u128,
+    pub player_b_deposited: u128,
+    pub cube_value: u32,
+    pub pending_double: Option<PendingDoubleDetails>,
+    /// What the winner would receive if the escrow settled now, and the
+    /// rake. None once the escrow can no longer settle.
+    pub projected_payout: Option<u128>,
+    pub projected_rake: Option<u128>,
+}
+
+#[cw_serde]
+pub struct PendingDoubleDetails {
+    pub doubler: Addr,
+    pub responder: Addr,
+    pub new_cube_value: u32,
+    pub additional_deposit: u128,
+    pub doubler_deposited: bool,
+    pub responder_deposited: bool,
+}
+
+#[cw_serde]
+pub struct VerifyRollResponse {
+    pub game_id: String,
+    pub turn: u32,
+    pub dice: [u8; 2],
+    pub server_seed: String,
+    pub client_seed: String,
+    /// Commitment published for the turn before the roll
+    pub commit_hash: String,
+    /// The seed hashes to the turn's commitment
+    pub verified: bool,
+}
+
+#[cw_serde]
+pub struct VerifyMoveProofResponse {
+    pub game_id: String,
+    pub index: u32,
+    /// False if the proof does not match, or the game has no committed log
+    pub valid: bool,
+}
+
+/// Canonical result payload co-signed by both players. Fields serialize in
+/// this order.
+#[cw_serde]
+pub struct SignedResultPayload {
+    pub chain_id: String,
+    pub contract: String,
+    pub game_id: String,
+    pub winner: String,
+    pub result_type: String,
+    pub nonce: u64,
+    /// Left out of the payload entirely when there is no cube to report
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cube: Option<CubeReport>,
+}
+
+/// Both players' signatures over a game's move log payload
+#[cw_serde]
+pub struct MoveLogSignatures {
+    pub signature_a: Binary,
+    pub signature_b: Binary,
+}
+
+/// Canonical payload a winner signs to confirm an abandonment. Fields
+/// serialize in this order.
+#[cw_serde]
+pub struct SignedAbandonmentPayload {
+    pub chain_id: String,
+    pub contract: String,
+    pub game_id: String,
+    pub abandoner: String,
+}
+
+/// Canonical move log payload co-signed by both players, binding the log a
+/// dispute replays to the game they played. Fields serialize in this order.
+#[cw_serde]
+pub struct SignedMoveLogPayload {
+    pub chain_id: String,
+    pub contract: String,
+    pub game_id: String,
+    pub root: HexBinary,
+    pub leaf_count: u32,
+}
+
+/// Canonical payload an invite key signs to admit one acceptor to a private
+/// challenge. Fields serialize in this order.
+#[cw_serde]
+pub struct SignedInvitePayload {
+    pub chain_id: String,
+    pub contract: String,
+    pub challenge_id: String,
+    pub acceptor: String,
+}
+
+#[cw_serde]
+pub struct ResultSigningPayloadResponse {
+    pub payload: Binary,
+    /// sha256 of `payload`; this is what gets signed
+    pub hash: HexBinary,
+}
+
+#[cw_serde]
+pub struct DisputeResponse {
+    pub game_id: String,
+    pub disputer: Addr,
+    pub reported_winner: Addr,
+    pub reported_result_type: String,
+    pub winner: Addr,
+    pub result_type: String,
+    pub overturned: bool,
+    pub illegal_turn: Option<u32>,
+    pub reason: Option<String>,
+    pub filed_at: u64,
+}
+
+#[cw_serde]
+pub struct MatchResponse {
+    pub match_id: String,
+    pub player_a: Addr,
+    pub player_b: Addr,
+    pub match_length: u32,
+    pub wager_amount: u128,
+    pub score_a: u32,
+    pub score_b: u32,
+    pub crawford: bool,
+    pub crawford_played: bool,
+    pub status: String,
+    pub game_ids: Vec<String>,
+    pub winner: Option<Addr>,
+    pub created_at: u64,
+    pub completed_at: Option<u64>,
 }
 
 #[cw_serde]
@@ -100,11 +738,153 @@ pub struct PlayerStatsResponse {
     pub rating: u32,
     pub total_wagered: u128,
     pub total_won: u128,
+    pub experience: u32,
+    pub season_games_played: u32,
+    pub season_games_won: u32,
+    pub gammons_won: u32,
+    pub gammons_lost: u32,
+    pub backgammons_won: u32,
+    pub backgammons_lost: u32,
+    pub points_won: u64,
+    pub points_lost: u64,
+    pub doubles_offered: u32,
+    pub doubles_taken: u32,
+    pub doubles_passed: u32,
+    pub win_streak: u32,
+    pub best_win_streak: u32,
+    pub abandonments: u32,
+    pub peak_rating: u32,
+    pub peak_rating_at: Option<u64>,
+    pub last_played: Option<u64>,
+    /// The last `recent_games` results, latest in bit 0, set for a win
+    pub recent_results: u32,
+    pub recent_games: u32,
+    pub total_staked: u128,
+    pub rake_paid: u128,
+    /// Awarded by disputes but paid to the other player; not in total_won
+    pub total_owed: u128,
+    /// total_won minus total_staked
+    pub net_winnings: i128,
+}
+
+#[cw_serde]
+pub struct PlayerAchievementsResponse {
+    pub address: Addr,
+    pub achievements: Vec<EarnedAchievement>,
+}
+
+#[cw_serde]
+pub struct LeaderboardResponse {
+    pub players: Vec<PlayerStatsResponse>,
+    /// Last address looked at, if the board may continue past it
+    pub next_start_after: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct PlayerRankResponse {
+    pub address: Addr,
+    pub rating: u32,
+    /// None for addresses that have never been in a game
+    pub rank: Option<u64>,
+}
+
+/// A head-to-head record seen from `player`'s side
+#[cw_serde]
+pub struct HeadToHeadResponse {
+    pub player: Addr,
+    pub opponent: Addr,
+    pub games: u64,
+    pub wins: u64,
+    pub losses: u64,
+    /// Gammons and backgammons
+    pub gammons_won: u64,
+    pub gammons_lost: u64,
+    pub total_wagered: u128,
+    /// None if the two have never finished a game
+    pub last_played: Option<u64>,
+}
+
+#[cw_serde]
+pub struct RivalsResponse {
+    pub rivals: Vec<HeadToHeadResponse>,
+}
+
+#[cw_serde]
+pub struct ChallengeResponse {
+    pub challenge_id: String,
+    pub creator: Addr,
+    pub wager_amount: u128,
+    pub min_rating: Option<u32>,
+    pub max_rating: Option<u32>,
+    /// Whether accepting requires an invite signature
+    pub private: bool,
+    /// "Expired" once an open challenge is past `expires_at`
+    pub status: String,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub accepted_by: Option<Addr>,
+    pub game_id: Option<String>,
+}
+
+#[cw_serde]
+pub struct ChallengesResponse {
+    pub challenges: Vec<ChallengeResponse>,
+}
+
+#[cw_serde]
+pub struct ProposalResponse {
+    pub proposal_id: String,
+    pub proposer: Addr,
+    pub opponent: Addr,
+    pub wager_amount: u128,
+    /// "Expired" for a pending proposal past `expires_at`
+    pub status: String,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub game_id: Option<String>,
+}
+
+#[cw_serde]
+pub struct ProposalsResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
+
+#[cw_serde]
+pub struct SeasonResponse {
+    pub season_id: u32,
+    pub name: String,
+    pub started_at: u64,
+    pub ended_at: Option<u64>,
+    pub reset_percent: u32,
+    pub player_count: u32,
+}
+
+#[cw_serde]
+pub struct CurrentSeasonResponse {
+    pub season: Option<SeasonResponse>,
+}
+
+#[cw_serde]
+pub struct SeasonStandingsResponse {
+    pub standings: Vec<SeasonStanding>,
 }
 
 #[cw_serde]
 pub struct StatsResponse {
     pub total_games: u64,
+    pub totals: PlatformStats,
+}
+
+#[cw_serde]
+pub struct DailyStats {
+    /// Start of the UTC day, in seconds
+    pub day_start: u64,
+    pub stats: PlatformStats,
+}
+
+#[cw_serde]
+pub struct DailyStatsResponse {
+    pub days: Vec<DailyStats>,
 }
 
 #[cw_serde]
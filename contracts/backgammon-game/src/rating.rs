@@ -0,0 +1,218 @@
+//! FIBS-style rating model.
+//!
+//! For a match of length `N` between a winner rated `Rw` and a loser rated `Rl`,
+//! the probability the loser would have won is
+//!
+//! ```text
+//! P = 1 / (10^((Rw - Rl) * sqrt(N) / 2000) + 1)
+//! ```
+//!
+//! and each player's rating moves by `base_factor * K * sqrt(N) * P`, where `K`
+//! is that player's experience factor. Ratings are stored as integers scaled by
+//! 100, and all math is done in fixed point since wasm contracts cannot use floats.
+
+use cosmwasm_std::{Decimal, StdResult};
+
+use crate::state::{PlayerStats, RatingParams};
+
+/// ln(10) with 18 decimal places
+const LN_10: Decimal = Decimal::raw(2_302_585_092_994_045_684);
+
+/// Beyond this exponent the upset probability is below 1e-8 and the rating
+/// change rounds to zero, so larger gaps are clamped to keep the math bounded.
+const MAX_EXPONENT: u128 = 8;
+
+/// Applies the rating and experience update for a finished match.
+/// Returns the (gain, loss) applied, in stored rating units.
+pub fn apply_match_result(
+    params: &RatingParams,
+    winner: &mut PlayerStats,
+    loser: &mut PlayerStats,
+    match_length: u32,
+) -> StdResult<(u32, u32)> {
+    let (gain, loss) = rating_changes(
+        params,
+        winner.rating,
+        loser.rating,
+        winner.experience,
+        loser.experience,
+        match_length,
+    )?;
+
+    winner.rating = winner.rating.saturating_add(gain);
+    loser.rating = loser.rating.saturating_sub(loss).max(params.min_rating);
+    winner.experience = winner.experience.saturating_add(match_length);
+    loser.experience = loser.experience.saturating_add(match_length);
+
+    Ok((gain, loss))
+}
+
+/// Computes the winner's gain and the loser's loss in stored rating units
+/// (rating * 100), before the loser's rating floor is applied.
+pub fn rating_changes(
+    params: &RatingParams,
+    winner_rating: u32,
+    loser_rating: u32,
+    winner_experience: u32,
+    loser_experience: u32,
+    match_length: u32,
+) -> StdResult<(u32, u32)> {
+    let sqrt_n = Decimal::from_ratio(match_length.max(1), 1u128).sqrt();
+    let upset = loser_win_probability(winner_rating, loser_rating, sqrt_n)?;
+    let base = Decimal::from_ratio(params.base_factor, 1u128)
+        .checked_mul(sqrt_n)?
+        .checked_mul(upset)?;
+
+    let gain = to_stored(base.checked_mul(experience_factor(params, winner_experience))?);
+    let loss = to_stored(base.checked_mul(experience_factor(params, loser_experience))?);
+    Ok((gain, loss))
+}
+
+/// Probability that the loser would have won, given both ratings.
+fn loser_win_probability(
+    winner_rating: u32,
+    loser_rating: u32,
+    sqrt_n: Decimal,
+) -> StdResult<Decimal> {
+    let diff = winner_rating.abs_diff(loser_rating);
+    // |Rw - Rl| * sqrt(N) / 2000, with ratings unscaled from * 100
+    let exponent = Decimal::from_ratio(diff, 200_000u128)
+        .checked_mul(sqrt_n)?
+        .min(Decimal::from_ratio(MAX_EXPONENT, 1u128));
+    let p = pow10(exponent)?;
+
+    // p >= 1, so the denominator is never zero
+    if loser_rating > winner_rating {
+        // The favourite lost: P = 10^x / (10^x + 1)
+        Ok(p / (p + Decimal::one()))
+    } else {
+        Ok(Decimal::one() / (p + Decimal::one()))
+    }
+}
+
+/// Experience factor K: `max_provisional_factor` for a new player, falling
+/// linearly to 1 once `provisional_experience` match points have been played.
+fn experience_factor(params: &RatingParams, experience: u32) -> Decimal {
+    if experience >= params.provisional_experience || params.max_provisional_factor <= 1 {
+        return Decimal::one();
+    }
+    let boost = Decimal::from_ratio(
+        (params.max_provisional_factor - 1) as u128
+            * (params.provisional_experience - experience) as u128,
+        params.provisional_experience as u128,
+    );
+    Decimal::one() + boost
+}
+
+/// 10^x for 0 <= x <= MAX_EXPONENT, as 10^floor(x) * e^(frac(x) * ln 10)
+fn pow10(x: Decimal) -> StdResult<Decimal> {
+    let whole = x.to_uint_floor().u128();
+    let frac = x - Decimal::from_ratio(whole, 1u128);
+    let int_part = Decimal::from_ratio(10u128.pow(whole as u32), 1u128);
+    Ok(int_part.checked_mul(exp(frac.checked_mul(LN_10)?)?)?)
+}
+
+/// e^y by Taylor series. Only called with 0 <= y < ln 10, where 40 terms
+/// are more than enough for 18-decimal precision.
+fn exp(y: Decimal) -> StdResult<Decimal> {
+    let mut sum = Decimal::one();
+    let mut term = Decimal::one();
+    for n in 1..40u128 {
+        term = term.checked_mul(y)? / Decimal::from_ratio(n, 1u128);
+        if term.is_zero() {
+            break;
+        }
+        sum += term;
+    }
+    Ok(sum)
+}
+
+/// Pulls `rating` `percent`% of the way toward `center`, as at the start of
+/// a new season. 0 keeps the rating, 100 resets it to `center`.
+pub fn soft_reset(rating: u32, center: u32, percent: u32) -> u32 {
+    let distance = rating as i64 - center as i64;
+    (rating as i64 - distance * percent.min(100) as i64 / 100) as u32
+}
+
+/// Rating points to stored units (* 100), rounded to nearest
+fn to_stored(points: Decimal) -> u32 {
+    let scaled = points * Decimal::from_ratio(100u128, 1u128) + Decimal::percent(50);
+    scaled.to_uint_floor().u128().min(u32::MAX as u128) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fibs() -> RatingParams {
+        RatingParams::default()
+    }
+
+    #[test]
+    fn test_equal_ratings_one_point() {
+        // Experienced players, equal ratings, 1-point match: +/- 2.00
+        let (gain, loss) = rating_changes(&fibs(), 150_000, 150_000, 1_000, 1_000, 1).unwrap();
+        assert_eq!(gain, 200);
+        assert_eq!(loss, 200);
+    }
+
+    #[test]
+    fn test_match_length_scales_change() {
+        // sqrt(9) = 3, so a 9-point match between equals moves 3x as much
+        let (gain, _) = rating_changes(&fibs(), 150_000, 150_000, 1_000, 1_000, 9).unwrap();
+        assert_eq!(gain, 600);
+    }
+
+    #[test]
+    fn test_upset_pays_more_than_expected_win() {
+        let (favourite_gain, _) =
+            rating_changes(&fibs(), 210_000, 150_000, 1_000, 1_000, 1).unwrap();
+        let (underdog_gain, _) =
+            rating_changes(&fibs(), 150_000, 210_000, 1_000, 1_000, 1).unwrap();
+        assert!(underdog_gain > favourite_gain);
+        // The two outcomes split the 4-point pot between them
+        assert_eq!(favourite_gain + underdog_gain, 400);
+        // 600 point gap in a 1-point match: P(upset) = 1 / (10^0.3 + 1) ~= 0.334
+        assert_eq!(underdog_gain, 266);
+    }
+
+    #[test]
+    fn test_provisional_players_move_faster() {
+        // New winner (K = 5) against an experienced loser (K = 1)
+        let (gain, loss) = rating_changes(&fibs(), 150_000, 150_000, 0, 1_000, 1).unwrap();
+        assert_eq!(gain, 1_000);
+        assert_eq!(loss, 200);
+
+        // Halfway through the provisional period K = 3
+        let (gain, _) = rating_changes(&fibs(), 150_000, 150_000, 200, 1_000, 1).unwrap();
+        assert_eq!(gain, 600);
+    }
+
+    #[test]
+    fn test_apply_respects_floor_and_tracks_experience() {
+        let params = fibs();
+        let mut winner = PlayerStats::new(cosmwasm_std::Addr::unchecked("w"), 150_000);
+        let mut loser =
+            PlayerStats::new(cosmwasm_std::Addr::unchecked("l"), params.min_rating + 50);
+        apply_match_result(&params, &mut winner, &mut loser, 3).unwrap();
+        assert_eq!(loser.rating, params.min_rating);
+        assert_eq!(winner.experience, 3);
+        assert_eq!(loser.experience, 3);
+    }
+
+    #[test]
+    fn test_huge_gap_is_clamped() {
+        let (gain, loss) = rating_changes(&fibs(), 1_000_000, 100_000, 1_000, 1_000, 25).unwrap();
+        assert_eq!(gain, 0);
+        assert_eq!(loss, 0);
+    }
+
+    #[test]
+    fn test_soft_reset_pulls_toward_center() {
+        assert_eq!(soft_reset(170_000, 150_000, 50), 160_000);
+        assert_eq!(soft_reset(130_000, 150_000, 25), 135_000);
+        assert_eq!(soft_reset(170_000, 150_000, 0), 170_000);
+        assert_eq!(soft_reset(170_000, 150_000, 100), 150_000);
+        assert_eq!(soft_reset(150_001, 150_000, 50), 150_001);
+    }
+}
@@ -0,0 +1,473 @@
+//! Backgammon rules engine used to adjudicate disputed results, ported from
+//! `packages/backgammon-core` so a replay here agrees with the game server.
+//!
+//! The board uses the same layout: index 0 is white's bar, 1-24 are the
+//! points and 25 is black's bar. Positive counts are white checkers, negative
+//! counts black. White moves from 24 towards 1 and bears off to 0; black moves
+//! from 1 towards 24 and bears off to 25. White (player A) moves first.
+
+use crate::state::{LoggedTurn, ResultType};
+
+const WHITE_BAR: usize = 0;
+const BLACK_BAR: usize = 25;
+const TOTAL_CHECKERS: u8 = 15;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Player {
+    White,
+    Black,
+}
+
+impl Player {
+    fn opponent(self) -> Player {
+        match self {
+            Player::White => Player::Black,
+            Player::Black => Player::White,
+        }
+    }
+
+    fn bar(self) -> usize {
+        match self {
+            Player::White => WHITE_BAR,
+            Player::Black => BLACK_BAR,
+        }
+    }
+
+    /// Where this player's checkers go when borne off
+    fn off(self) -> usize {
+        match self {
+            Player::White => 0,
+            Player::Black => 25,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Board {
+    pub points: [i8; 26],
+    pub white_off: u8,
+    pub black_off: u8,
+}
+
+impl Board {
+    pub fn initial() -> Self {
+        let mut points = [0i8; 26];
+        points[24] = 2;
+        points[13] = 5;
+        points[8] = 3;
+        points[6] = 5;
+        points[1] = -2;
+        points[12] = -5;
+        points[17] = -3;
+        points[19] = -5;
+        Board {
+            points,
+            white_off: 0,
+            black_off: 0,
+        }
+    }
+
+    fn count(&self, point: usize, player: Player) -> u8 {
+        let val = self.points[point];
+        match player {
+            Player::White if val > 0 => val as u8,
+            Player::Black if val < 0 => val.unsigned_abs(),
+            _ => 0,
+        }
+    }
+
+    /// Two or more opposing checkers make a point
+    fn is_blocked(&self, point: usize, player: Player) -> bool {
+        match player {
+            Player::White => self.points[point] <= -2,
+            Player::Black => self.points[point] >= 2,
+        }
+    }
+
+    /// All checkers are in the home board, so bearing off is allowed
+    fn can_bear_off(&self, player: Player) -> bool {
+        if self.count(player.bar(), player) > 0 {
+            return false;
+        }
+        let outside = match player {
+            Player::White => 7..=24,
+            Player::Black => 1..=18,
+        };
+        outside.into_iter().all(|p| self.count(p, player) == 0)
+    }
+
+    /// Destination of a single legal move of `die` from `from`, ignoring the
+    /// bar-first and use-both-dice rules, which apply to the whole turn.
+    fn destination(&self, player: Player, from: usize, die: u8) -> Option<usize> {
+        if from > 25 || !(1..=6).contains(&die) || self.count(from, player) == 0 {
+            return None;
+        }
+        let die = die as i32;
+        let dest = match player {
+            Player::White if from == WHITE_BAR => 25 - die,
+            Player::White => from as i32 - die,
+            Player::Black if from == BLACK_BAR => die,
+            Player::Black => from as i32 + die,
+        };
+
+        let bearing_off = match player {
+            Player::White => dest <= 0,
+            Player::Black => dest >= 25,
+        };
+        if bearing_off {
+            if !self.can_bear_off(player) {
+                return None;
+            }
+            let exact = dest == player.off() as i32;
+            // Bearing off with a larger die is only allowed from the
+            // rearmost occupied point
+            let rearmost = match player {
+                Player::White => (from + 1..=6).all(|p| self.count(p, player) == 0),
+                Player::Black => (19..from).all(|p| self.count(p, player) == 0),
+            };
+            return (exact || rearmost).then_some(player.off());
+        }
+
+        let dest = dest as usize;
+        (!self.is_blocked(dest, player)).then_some(dest)
+    }
+
+    fn apply(&mut self, player: Player, from: usize, to: usize) {
+        let sign: i8 = match player {
+            Player::White => 1,
+            Player::Black => -1,
+        };
+        self.points[from] -= sign;
+
+        if to == player.off() {
+            match player {
+                Player::White => self.white_off += 1,
+                Player::Black => self.black_off += 1,
+            }
+            return;
+        }
+
+        // Hitting a blot sends it to its owner's bar
+        if self.points[to] == -sign {
+            self.points[to] = 0;
+            self.points[player.opponent().bar()] -= sign;
+        }
+        self.points[to] += sign;
+    }
+
+    /// Legal first moves for the remaining dice, as (from, to, die)
+    fn single_moves(&self, player: Player, dice: &[u8]) -> Vec<(usize, usize, u8)> {
+        let mut moves = vec![];
+        let sources: Vec<usize> = if self.count(player.bar(), player) > 0 {
+            vec![player.bar()]
+        } else {
+            (1..=24).collect()
+        };
+        let mut tried = vec![];
+        for &die in dice {
+            if tried.contains(&die) {
+                continue;
+            }
+            tried.push(die);
+            for &from in &sources {
+                if let Some(to) = self.destination(player, from, die) {
+                    moves.push((from, to, die));
+                }
+            }
+        }
+        moves
+    }
+
+    /// Most dice that can be played from this position
+    fn max_dice_usable(&self, player: Player, dice: &[u8]) -> usize {
+        let mut best = 0;
+        for (from, to, die) in self.single_moves(player, dice) {
+            let mut next = self.clone();
+            next.apply(player, from, to);
+            let used = 1 + next.max_dice_usable(player, &without(dice, die));
+            best = best.max(used);
+            if best == dice.len() {
+                break;
+            }
+        }
+        best
+    }
+
+    /// Winner and result type once a side has borne off all its checkers
+    pub fn game_over(&self) -> Option<(Player, ResultType)> {
+        let (winner, loser_off) = if self.white_off == TOTAL_CHECKERS {
+            (Player::White, self.black_off)
+        } else if self.black_off == TOTAL_CHECKERS {
+            (Player::Black, self.white_off)
+        } else {
+            return None;
+        };
+        if loser_off > 0 {
+            return Some((winner, ResultType::Normal));
+        }
+
+        // A loser still on the bar or in the winner's home board is backgammoned
+        let loser = winner.opponent();
+        let winner_home = match winner {
+            Player::White => 1..=6,
+            Player::Black => 19..=24,
+        };
+        let trapped = self.count(loser.bar(), loser) > 0
+            || winner_home.into_iter().any(|p| self.count(p, loser) > 0);
+        Some((
+            winner,
+            if trapped {
+                ResultType::Backgammon
+            } else {
+                ResultType::Gammon
+            },
+        ))
+    }
+}
+
+fn without(dice: &[u8], die: u8) -> Vec<u8> {
+    let mut rest = dice.to_vec();
+    if let Some(i) = rest.iter().position(|d| *d == die) {
+        rest.remove(i);
+    }
+    rest
+}
+
+/// Checks one turn's moves against the dice and plays them onto `board`.
+/// Stops early if the game ends mid-turn; any moves after that are illegal.
+pub fn play_turn(board: &mut Board, player: Player, turn: &LoggedTurn) -> Result<(), String> {
+    let [d1, d2] = turn.dice;
+    if !(1..=6).contains(&d1) || !(1..=6).contains(&d2) {
+        return Err(format!("invalid dice {}-{}", d1, d2));
+    }
+    let dice = if d1 == d2 { vec![d1; 4] } else { vec![d1, d2] };
+
+    let required = board.max_dice_usable(player, &dice);
+    let start = board.clone();
+    let mut remaining = dice.clone();
+    for (i, mv) in turn.moves.iter().enumerate() {
+        if board.game_over().is_some() {
+            return Err("move after the game ended".to_string());
+        }
+        if !remaining.contains(&mv.die) {
+            return Err(format!("die {} is not available", mv.die));
+        }
+        let from = mv.from as usize;
+        if board.count(player.bar(), player) > 0 && from != player.bar() {
+            return Err("checkers on the bar must enter first".to_string());
+        }
+        if board.destination(player, from, mv.die) != Some(mv.to as usize) {
+            return Err(format!(
+                "move {}/{} with a {} is illegal",
+                mv.from, mv.to, mv.die
+            ));
+        }
+        board.apply(player, from, mv.to as usize);
+        remaining = without(&remaining, mv.die);
+
+        // A player may stop short only once the game is won
+        if board.game_over().is_some() && i + 1 < turn.moves.len() {
+            return Err("move after the game ended".to_string());
+        }
+    }
+
+    if board.game_over().is_none() && turn.moves.len() < required {
+        return Err(format!(
+            "played {} dice when {} could be used",
+            turn.moves.len(),
+            required
+        ));
+    }
+
+    // With only one die playable, the higher one must be played if possible
+    if required == 1 && d1 != d2 {
+        let higher = d1.max(d2);
+        let higher_playable = start
+            .single_moves(player, &[higher])
+            .iter()
+            .any(|(_, _, die)| *die == higher);
+        if higher_playable && turn.moves[0].die != higher {
+            return Err(format!("the higher die {} must be played", higher));
+        }
+    }
+
+    Ok(())
+}
+
+/// Outcome of replaying a complete move log
+#[derive(Debug, PartialEq)]
+pub struct Verdict {
+    pub winner: Player,
+    pub result_type: ResultType,
+    /// Set when the game was decided by an illegal move rather than played out
+    pub illegal_turn: Option<(u32, String)>,
+}
+
+/// Replays a game from the opening position. A player who makes an illegal
+/// move forfeits a single game. Errors if the log ends before the game does.
+pub fn replay(turns: &[LoggedTurn]) -> Result<Verdict, String> {
+    let mut board = Board::initial();
+    let mut player = Player::White;
+
+    for (i, turn) in turns.iter().enumerate() {
+        let expected = i as u32 + 1;
+        if turn.turn != expected {
+            return Err(format!("expected turn {}, got {}", expected, turn.turn));
+        }
+        if let Err(reason) = play_turn(&mut board, player, turn) {
+            return Ok(Verdict {
+                winner: player.opponent(),
+                result_type: ResultType::Normal,
+                illegal_turn: Some((turn.turn, reason)),
+            });
+        }
+        if let Some((winner, result_type)) = board.game_over() {
+            if i + 1 < turns.len() {
+                return Err(format!(
+                    "log continues after the game ended at turn {}",
+                    turn.turn
+                ));
+            }
+            return Ok(Verdict {
+                winner,
+                result_type,
+                illegal_turn: None,
+            });
+        }
+        player = player.opponent();
+    }
+
+    Err("log ends before the game is over".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::CheckerMove;
+
+    fn turn(n: u32, dice: [u8; 2], moves: &[(u8, u8, u8)]) -> LoggedTurn {
+        LoggedTurn {
+            turn: n,
+            dice,
+            moves: moves
+                .iter()
+                .map(|&(from, to, die)| CheckerMove { from, to, die })
+                .collect(),
+        }
+    }
+
+    fn board(white: &[(usize, i8)], black: &[(usize, i8)], white_off: u8, black_off: u8) -> Board {
+        let mut points = [0i8; 26];
+        for &(p, n) in white {
+            points[p] = n;
+        }
+        for &(p, n) in black {
+            points[p] = -n;
+        }
+        Board {
+            points,
+            white_off,
+            black_off,
+        }
+    }
+
+    #[test]
+    fn test_opening_moves() {
+        let mut b = Board::initial();
+        // 3-1 makes the 5 point
+        play_turn(
+            &mut b,
+            Player::White,
+            &turn(1, [3, 1], &[(8, 5, 3), (6, 5, 1)]),
+        )
+        .unwrap();
+        assert_eq!(b.points[5], 2);
+
+        // 5 from the 1 point would land on white's 6 point
+        let err = play_turn(
+            &mut b,
+            Player::Black,
+            &turn(2, [5, 6], &[(1, 6, 5), (12, 18, 6)]),
+        );
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_must_use_both_dice() {
+        let mut b = Board::initial();
+        let err = play_turn(&mut b, Player::White, &turn(1, [3, 1], &[(8, 5, 3)])).unwrap_err();
+        assert!(err.contains("could be used"));
+    }
+
+    #[test]
+    fn test_bar_must_enter_first_and_hits() {
+        // White on the bar, a black blot on white's entry point 22
+        let mut b = board(&[(0, 1), (6, 14)], &[(22, 1), (19, 14)], 0, 0);
+        let err = play_turn(
+            &mut b,
+            Player::White,
+            &turn(1, [3, 1], &[(6, 3, 3), (6, 5, 1)]),
+        );
+        assert!(err.is_err());
+
+        let mut b = board(&[(0, 1), (6, 14)], &[(22, 1), (19, 14)], 0, 0);
+        play_turn(
+            &mut b,
+            Player::White,
+            &turn(1, [3, 1], &[(0, 22, 3), (6, 5, 1)]),
+        )
+        .unwrap();
+        assert_eq!(b.points[22], 1);
+        assert_eq!(b.points[BLACK_BAR], -1);
+    }
+
+    #[test]
+    fn test_bear_off_with_larger_die_only_from_rearmost_point() {
+        let b = board(&[(2, 1), (4, 1)], &[(24, 15)], 13, 0);
+        // A 6 cannot bear off from the 2 point while the 4 point is occupied
+        assert_eq!(b.destination(Player::White, 2, 6), None);
+        assert_eq!(b.destination(Player::White, 4, 6), Some(0));
+    }
+
+    #[test]
+    fn test_higher_die_rule() {
+        // White's last outside checker can play the 6 or the 5, but black's
+        // 1 and 2 points stop it playing both
+        let b = board(&[(13, 1), (6, 14)], &[(2, 2), (1, 13)], 0, 0);
+        let mut played = b.clone();
+        let err = play_turn(&mut played, Player::White, &turn(1, [6, 5], &[(13, 8, 5)]));
+        assert!(err.unwrap_err().contains("higher die"));
+        let mut played = b;
+        play_turn(&mut played, Player::White, &turn(1, [6, 5], &[(13, 7, 6)])).unwrap();
+    }
+
+    #[test]
+    fn test_gammon_and_backgammon_detection() {
+        let b = board(&[], &[(19, 15)], 15, 0);
+        assert_eq!(b.game_over(), Some((Player::White, ResultType::Gammon)));
+
+        let b = board(&[], &[(19, 14), (3, 1)], 15, 0);
+        assert_eq!(b.game_over(), Some((Player::White, ResultType::Backgammon)));
+
+        let b = board(&[], &[(19, 14)], 15, 1);
+        assert_eq!(b.game_over(), Some((Player::White, ResultType::Normal)));
+    }
+
+    #[test]
+    fn test_replay_illegal_move_forfeits() {
+        let turns = vec![
+            turn(1, [3, 1], &[(8, 5, 3), (6, 5, 1)]),
+            // Black tries to move onto white's made 5 point
+            turn(2, [4, 2], &[(1, 5, 4), (12, 14, 2)]),
+        ];
+        let verdict = replay(&turns).unwrap();
+        assert_eq!(verdict.winner, Player::White);
+        assert_eq!(verdict.illegal_turn.unwrap().0, 2);
+    }
+
+    #[test]
+    fn test_replay_incomplete_log_is_rejected() {
+        let turns = vec![turn(1, [3, 1], &[(8, 5, 3), (6, 5, 1)])];
+        assert!(replay(&turns).is_err());
+    }
+}
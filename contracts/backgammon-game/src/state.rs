@@ -1,16 +1,98 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Binary, Empty, HexBinary, Order, StdResult, Storage};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use platform_roles::Roles;
 
 #[cw_serde]
 pub struct Config {
     pub admin: Addr,
     /// Wager escrow contract address
     pub escrow_contract: Option<Addr>,
-    /// Authorized game server address (can report results)
+    /// Game server from before roles existed; `migrate` moves it into the
+    /// Reporter role
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub server_address: Option<Addr>,
     /// USDC denom for wager creation
     pub usdc_denom: String,
+    /// Rating model parameters
+    #[serde(default)]
+    pub rating_params: RatingParams,
+    /// Seconds a reported result stays pending and open to challenge before
+    /// it can be finalized. 0 finalizes results immediately.
+    #[serde(default)]
+    pub challenge_period_seconds: u64,
+    /// Arbiter from before roles existed; `migrate` moves it into the
+    /// Arbiter role
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arbiter: Option<Addr>,
+    /// Seconds a game may wait in `Created` before anyone can expire it.
+    /// 0 lets games wait indefinitely.
+    #[serde(default)]
+    pub game_ttl_seconds: u64,
+    /// Seconds after a game completes that its players can dispute the
+    /// result. 0 leaves results open to dispute indefinitely.
+    #[serde(default)]
+    pub dispute_period_seconds: u64,
+    /// CW721 contract achievement badges are minted on
+    #[serde(default)]
+    pub achievement_nft_contract: Option<Addr>,
+}
+
+/// Operations a Pauser can halt independently of each other
+#[cw_serde]
+pub enum Operation {
+    /// Creating games and matches, including accepting proposals and
+    /// challenges
+    CreateGame,
+    /// Reporting, resolving and finalizing results, which settles escrows
+    Settlement,
+}
+
+/// An admin transfer the proposed admin has not yet accepted
+#[cw_serde]
+pub struct PendingAdmin {
+    pub address: Addr,
+    pub expires_at: u64,
+}
+
+/// Permissions the admin can grant to other addresses. The admin holds
+/// every role implicitly.
+#[cw_serde]
+pub enum Role {
+    /// Updates the configuration
+    ConfigAdmin,
+    /// Runs seasons
+    Operator,
+    /// Game servers: create and start games and report their results
+    Reporter,
+    /// Resolves challenged results
+    Arbiter,
+    /// Pauses the contract
+    Pauser,
+}
+
+/// Parameters for the FIBS-style rating formula (see `rating.rs`).
+#[cw_serde]
+pub struct RatingParams {
+    /// Multiplier on every rating change (FIBS uses 4)
+    pub base_factor: u32,
+    /// Experience (match points played) after which a player is no longer provisional
+    pub provisional_experience: u32,
+    /// Experience factor for a brand new player, falling linearly to 1 (FIBS uses 5)
+    pub max_provisional_factor: u32,
+    /// Rating floor, stored as rating * 100
+    pub min_rating: u32,
+}
+
+impl Default for RatingParams {
+    fn default() -> Self {
+        RatingParams {
+            base_factor: 4,
+            provisional_experience: 400,
+            max_provisional_factor: 5,
+            min_rating: 100_000, // 1000.00
+        }
+    }
 }
 
 #[cw_serde]
@@ -25,14 +107,137 @@ pub struct Game {
     pub created_at: u64,
     pub completed_at: Option<u64>,
     pub move_count: u32,
+    /// Match this game belongs to, if it is part of an N-point match
+    #[serde(default)]
+    pub match_id: Option<String>,
+    /// The server commits to each turn's dice before rolling, so the seeds
+    /// must be revealed when the game ends (see `dice.rs`)
+    #[serde(default)]
+    pub dice_committed: bool,
+    /// Merkle commitment to the full move/roll log, set when the result is reported
+    #[serde(default)]
+    pub move_log: Option<MoveLogCommitment>,
+    /// Both players signed `move_log`, so a dispute can replay it
+    #[serde(default)]
+    pub move_log_signed: bool,
+    /// When a pending result was reported; the challenge period runs from here
+    #[serde(default)]
+    pub result_reported_at: Option<u64>,
+    /// End of the challenge period, fixed when the result was reported
+    #[serde(default)]
+    pub challenge_deadline: Option<u64>,
+    /// Player reported as abandoning the game, while that report is pending
+    #[serde(default)]
+    pub abandoned_by: Option<Addr>,
+    /// Player who challenged the pending result
+    #[serde(default)]
+    pub challenged_by: Option<Addr>,
+    /// Doubling cube at the end of the game; None if it was never turned
+    #[serde(default)]
+    pub cube: Option<CubeResult>,
+    /// Result multiplier times the final cube value
+    #[serde(default)]
+    pub points_won: u32,
+    /// The server's own ID for the game, if it gave one on creation
+    #[serde(default)]
+    pub server_match_id: Option<String>,
+}
+
+/// Final state of a game's doubling cube, reported with its result
+#[cw_serde]
+pub struct CubeResult {
+    /// Final cube value, a power of two
+    pub value: u32,
+    /// Player holding the cube at the end
+    pub owner: Option<Addr>,
+    /// Doubles offered by each player
+    pub doubles_a: u32,
+    pub doubles_b: u32,
+    /// The game ended with the winner's double being passed
+    pub passed: bool,
+}
+
+/// Root of a game's move/roll log and how many leaves it has (see `merkle.rs`)
+#[cw_serde]
+pub struct MoveLogCommitment {
+    pub root: HexBinary,
+    pub leaf_count: u32,
+}
+
+/// One turn of a game's move log. The log committed with a result is a
+/// Merkle tree whose leaves are the JSON encoding of each turn, in order.
+#[cw_serde]
+pub struct LoggedTurn {
+    pub turn: u32,
+    pub dice: [u8; 2],
+    pub moves: Vec<CheckerMove>,
+}
+
+/// A single checker move, numbered as in `backgammon-core`: 0 is white's bar
+/// and bear-off, 25 black's, 1-24 the points
+#[cw_serde]
+pub struct CheckerMove {
+    pub from: u8,
+    pub to: u8,
+    pub die: u8,
+}
+
+/// Seeds revealed for one turn of a committed game
+#[cw_serde]
+pub struct DiceReveal {
+    pub turn: u32,
+    /// Commitment published before the roll: hex sha256 of `server_seed`
+    pub commit_hash: String,
+    pub server_seed: String,
+    pub client_seed: String,
 }
 
 #[cw_serde]
 pub enum GameStatus {
     Created,
     InProgress,
+    /// Result reported, waiting out the challenge period
+    PendingResult,
     Completed,
     Abandoned,
+    /// Called off by a player before it started, or left unfinished when a
+    /// player withdrew its escrow
+    Cancelled,
+    /// Never started within the configured TTL
+    Expired,
+}
+
+/// An N-point match played as a sequence of games. The wager is escrowed
+/// once per match under `match_id`, and ratings move only when it ends.
+#[cw_serde]
+pub struct Match {
+    pub match_id: String,
+    pub player_a: Addr,
+    pub player_b: Addr,
+    pub match_length: u32,
+    pub wager_amount: u128,
+    pub score_a: u32,
+    pub score_b: u32,
+    /// The current game is the Crawford game (no doubling allowed)
+    pub crawford: bool,
+    /// The Crawford game has already been played in this match
+    pub crawford_played: bool,
+    pub status: MatchStatus,
+    /// Games in play order; the last one is the current game
+    pub game_ids: Vec<String>,
+    pub winner: Option<Addr>,
+    pub created_at: u64,
+    pub completed_at: Option<u64>,
+}
+
+#[cw_serde]
+pub enum MatchStatus {
+    InProgress,
+    Completed,
+    /// Called off before it finished: its first game was cancelled or
+    /// expired, a later one expired unplayed and the escrow was refunded, or
+    /// a player withdrew the escrow
+    Cancelled,
 }
 
 #[cw_serde]
@@ -50,11 +255,731 @@ pub struct PlayerStats {
     /// Rating stored as integer (actual rating * 100, e.g., 1500.00 = 150000)
     pub rating: u32,
     pub total_wagered: u128,
+    /// Payouts received from the escrow, net of rake
     pub total_won: u128,
+    /// Sum of match lengths played; drives the provisional rating boost
+    #[serde(default)]
+    pub experience: u32,
+    /// Games and wins in the current season; archived and zeroed when it ends
+    #[serde(default)]
+    pub season_games_played: u32,
+    #[serde(default)]
+    pub season_games_won: u32,
+    #[serde(default)]
+    pub gammons_won: u32,
+    #[serde(default)]
+    pub gammons_lost: u32,
+    #[serde(default)]
+    pub backgammons_won: u32,
+    #[serde(default)]
+    pub backgammons_lost: u32,
+    /// Points scored for and against, counting gammons and the cube
+    #[serde(default)]
+    pub points_won: u64,
+    #[serde(default)]
+    pub points_lost: u64,
+    /// Cube decisions: doubles offered, and doubles taken or passed
+    #[serde(default)]
+    pub doubles_offered: u32,
+    #[serde(default)]
+    pub doubles_taken: u32,
+    #[serde(default)]
+    pub doubles_passed: u32,
+    /// Consecutive games won, up to the latest game
+    #[serde(default)]
+    pub win_streak: u32,
+    #[serde(default)]
+    pub best_win_streak: u32,
+    /// Games this player abandoned
+    #[serde(default)]
+    pub abandonments: u32,
+    /// Highest rating reached, and when
+    #[serde(default)]
+    pub peak_rating: u32,
+    #[serde(default)]
+    pub peak_rating_at: Option<u64>,
+    /// When the player last finished a game
+    #[serde(default)]
+    pub last_played: Option<u64>,
+    /// The last `recent_games` results, latest in bit 0, set for a win
+    #[serde(default)]
+    pub recent_results: u32,
+    /// Results held in `recent_results`, up to `RECENT_RESULTS`
+    #[serde(default)]
+    pub recent_games: u32,
+    /// Deposits in settled escrows, including cube doubles
+    #[serde(default)]
+    pub total_staked: u128,
+    /// Rake taken from this player's payouts
+    #[serde(default)]
+    pub rake_paid: u128,
+    /// Payouts awarded by a dispute after the escrow had already paid the
+    /// other player; not in `total_won` until paid (see the escrow's `Owed`)
+    #[serde(default)]
+    pub total_owed: u128,
+}
+
+/// Number of results kept in `PlayerStats::recent_results`
+pub const RECENT_RESULTS: u32 = 20;
+const RECENT_RESULTS_MASK: u32 = (1 << RECENT_RESULTS) - 1;
+
+impl PlayerStats {
+    pub fn new(address: Addr, rating: u32) -> Self {
+        PlayerStats {
+            address,
+            games_played: 0,
+            games_won: 0,
+            rating,
+            total_wagered: 0,
+            total_won: 0,
+            experience: 0,
+            season_games_played: 0,
+            season_games_won: 0,
+            gammons_won: 0,
+            gammons_lost: 0,
+            backgammons_won: 0,
+            backgammons_lost: 0,
+            points_won: 0,
+            points_lost: 0,
+            doubles_offered: 0,
+            doubles_taken: 0,
+            doubles_passed: 0,
+            win_streak: 0,
+            best_win_streak: 0,
+            abandonments: 0,
+            peak_rating: rating,
+            peak_rating_at: None,
+            last_played: None,
+            recent_results: 0,
+            recent_games: 0,
+            total_staked: 0,
+            rake_paid: 0,
+            total_owed: 0,
+        }
+    }
+
+    /// Count a finished game in the streaks and recent form
+    pub fn record_result(&mut self, won: bool, at: u64) {
+        if won {
+            self.win_streak += 1;
+            self.best_win_streak = self.best_win_streak.max(self.win_streak);
+        } else {
+            self.win_streak = 0;
+        }
+        self.recent_results = ((self.recent_results << 1) | u32::from(won)) & RECENT_RESULTS_MASK;
+        self.recent_games = (self.recent_games + 1).min(RECENT_RESULTS);
+        self.last_played = Some(at);
+    }
+
+    /// Raise the peak rating if the current rating exceeds it, returning
+    /// the change
+    pub fn record_peak_rating(&mut self, at: u64) -> Option<PeakChange> {
+        if self.rating <= self.peak_rating {
+            return None;
+        }
+        let change = PeakChange {
+            previous: self.peak_rating,
+            previous_at: self.peak_rating_at,
+            rating: self.rating,
+            at,
+        };
+        self.peak_rating = self.rating;
+        self.peak_rating_at = Some(at);
+        Some(change)
+    }
+
+    /// Put back the peak `change` replaced, unless a later game has set a
+    /// new one since
+    pub fn revert_peak_rating(&mut self, change: &PeakChange) {
+        if self.peak_rating == change.rating && self.peak_rating_at == Some(change.at) {
+            self.peak_rating = change.previous;
+            self.peak_rating_at = change.previous_at;
+        }
+    }
+}
+
+/// A new peak rating, and the one it replaced. Ratings before it aren't
+/// stored, so this is all a dispute has to take a peak back with.
+#[cw_serde]
+pub struct PeakChange {
+    pub previous: u32,
+    pub previous_at: Option<u64>,
+    pub rating: u32,
+    pub at: u64,
+}
+
+/// Milestones a player can earn a badge for (see `achievements.rs`)
+#[cw_serde]
+pub enum Achievement {
+    FirstWin,
+    FirstBackgammon,
+    HundredGames,
+    WinStreak10,
+    Rating1800,
+}
+
+#[cw_serde]
+pub struct EarnedAchievement {
+    pub achievement: Achievement,
+    pub earned_at: u64,
+    /// Game whose result earned it
+    pub game_id: String,
+    /// Badge minted for it, if an achievement NFT contract was configured
+    pub token_id: Option<String>,
+}
+
+/// A game offer with an open seat, filled by the first eligible player to
+/// accept it
+#[cw_serde]
+pub struct Challenge {
+    pub challenge_id: String,
+    pub creator: Addr,
+    pub wager_amount: u128,
+    /// Inclusive bounds on the acceptor's rating, stored as rating * 100
+    pub min_rating: Option<u32>,
+    pub max_rating: Option<u32>,
+    /// secp256k1 public key of the invite; only an acceptor it signed for
+    /// can accept
+    pub invite_pubkey: Option<Binary>,
+    pub status: ChallengeStatus,
+    pub created_at: u64,
+    /// An open challenge can no longer be accepted from this time
+    pub expires_at: u64,
+    pub accepted_by: Option<Addr>,
+    /// Game created on acceptance
+    pub game_id: Option<String>,
+}
+
+#[cw_serde]
+pub enum ChallengeStatus {
+    Open,
+    Accepted,
+    Cancelled,
+}
+
+/// A game offered to a specific opponent. The game and its escrow are only
+/// created once the opponent accepts.
+#[cw_serde]
+pub struct Proposal {
+    pub proposal_id: String,
+    pub proposer: Addr,
+    pub opponent: Addr,
+    pub wager_amount: u128,
+    pub status: ProposalStatus,
+    pub created_at: u64,
+    /// The offer can no longer be accepted after this time
+    pub expires_at: u64,
+    /// Game created on acceptance
+    pub game_id: Option<String>,
+}
+
+#[cw_serde]
+pub enum ProposalStatus {
+    Pending,
+    Accepted,
+    Declined,
+    Cancelled,
+}
+
+#[cw_serde]
+pub struct Season {
+    pub season_id: u32,
+    pub name: String,
+    pub started_at: u64,
+    pub ended_at: Option<u64>,
+    /// Percent of each rating's distance from the default removed when the
+    /// season ends
+    pub reset_percent: u32,
+    /// Players archived into the standings when the season ended
+    pub player_count: u32,
+}
+
+/// A player's final placing in a finished season
+#[cw_serde]
+pub struct SeasonStanding {
+    pub season_id: u32,
+    /// 1-based, by final rating
+    pub rank: u32,
+    pub address: Addr,
+    pub rating: u32,
+    /// Rating carried into the next season after the soft reset
+    pub reset_rating: u32,
+    pub games_played: u32,
+    pub games_won: u32,
+}
+
+/// A player's key for co-signing results
+#[cw_serde]
+pub struct SigningKey {
+    /// SEC1-encoded secp256k1 public key
+    pub pubkey: Binary,
+}
+
+/// What settle_match booked for an escrow, so a dispute can reverse it
+#[cw_serde]
+pub struct Settlement {
+    pub winner: Addr,
+    pub loser: Addr,
+    pub match_length: u32,
+    pub wager_amount: u128,
+    /// Rating actually added to the winner and taken from the loser
+    pub rating_gain: u32,
+    pub rating_loss: u32,
+    /// What the escrow reported paying out, once its reply is in
+    #[serde(default)]
+    pub payout: u128,
+    #[serde(default)]
+    pub rake: u128,
+    #[serde(default)]
+    pub cube_value: u32,
+    #[serde(default)]
+    pub winner_stake: u128,
+    #[serde(default)]
+    pub loser_stake: u128,
+    /// What a resettlement left owed to the winner instead of paying
+    #[serde(default)]
+    pub owed: u128,
+    /// The winner's peak rating, if this match raised it
+    #[serde(default)]
+    pub peak: Option<PeakChange>,
+}
+
+/// Outcome of a player's dispute of a reported result
+#[cw_serde]
+pub struct Dispute {
+    pub game_id: String,
+    pub disputer: Addr,
+    pub reported_winner: Addr,
+    pub reported_result_type: ResultType,
+    /// Result established by replaying the move log
+    pub winner: Addr,
+    pub result_type: ResultType,
+    pub overturned: bool,
+    /// Turn and reason, if the game was decided by an illegal move
+    pub illegal_turn: Option<u32>,
+    pub reason: Option<String>,
+    pub filed_at: u64,
+}
+
+/// Platform-wide activity counters, kept as all-time totals and per UTC day
+#[cw_serde]
+#[derive(Default)]
+pub struct PlatformStats {
+    pub games_created: u64,
+    /// Completed games by result type
+    pub normal_wins: u64,
+    pub gammons: u64,
+    pub backgammons: u64,
+    pub abandonments: u64,
+    /// Both players' wagers in settled games and matches
+    pub wager_volume: u128,
+    pub doubles_offered: u64,
+    pub doubles_taken: u64,
+    pub doubles_passed: u64,
+    /// Distinct players who finished a game: ever in the totals, or that day
+    /// in a daily bucket
+    pub unique_players: u64,
+}
+
+/// Record between two players, stored under the ordered pair so `player_a`
+/// is always the lower address
+#[cw_serde]
+pub struct HeadToHead {
+    pub player_a: Addr,
+    pub player_b: Addr,
+    pub games: u64,
+    pub wins_a: u64,
+    pub wins_b: u64,
+    /// Gammons and backgammons won by each player
+    pub gammons_a: u64,
+    pub gammons_b: u64,
+    /// Both players' wagers in games and matches settled between them
+    pub total_wagered: u128,
+    pub last_played: u64,
+}
+
+impl HeadToHead {
+    pub fn new(player_a: Addr, player_b: Addr) -> Self {
+        HeadToHead {
+            player_a,
+            player_b,
+            games: 0,
+            wins_a: 0,
+            wins_b: 0,
+            gammons_a: 0,
+            gammons_b: 0,
+            total_wagered: 0,
+            last_played: 0,
+        }
+    }
+
+    /// `player`'s (wins, gammons) counters
+    pub fn side_mut(&mut self, player: &Addr) -> (&mut u64, &mut u64) {
+        if *player == self.player_a {
+            (&mut self.wins_a, &mut self.gammons_a)
+        } else {
+            (&mut self.wins_b, &mut self.gammons_b)
+        }
+    }
+}
+
+/// How far an unfinished `migrate` has got. Each call processes one batch
+/// and saves where the next should start.
+#[cw_serde]
+pub enum MigrationStage {
+    /// Re-saving games to populate their indexes
+    Games {
+        start_after: Option<String>,
+        /// Whether the players' streaks and recent form are rebuilt too
+        #[serde(default)]
+        backfill_stats: bool,
+    },
+    /// Re-saving player stats to populate the rating index
+    Players {
+        start_after: Option<Addr>,
+        #[serde(default)]
+        backfill_stats: bool,
+        /// The player whose games a batch stopped partway through replaying
+        #[serde(default)]
+        backfilling: Option<BackfillCursor>,
+    },
+}
+
+/// How far a player's stats backfill has replayed their games: up to the
+/// one keyed by (created_at, game_id), or none yet
+#[cw_serde]
+pub struct BackfillCursor {
+    pub player: Addr,
+    pub games_after: Option<(u64, String)>,
+}
+
+/// How far an `EndSeason` spread over several calls has got
+#[cw_serde]
+pub enum SeasonEndStage {
+    /// Archiving and soft-resetting players, in address order
+    Players { start_after: Option<Addr> },
+    /// Ranking the archived players into the standings
+    Standings { ranked: u32 },
+}
+
+/// A season whose end is still being processed
+#[cw_serde]
+pub struct SeasonEnding {
+    pub season_id: u32,
+    pub stage: SeasonEndStage,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
-pub const GAMES: Map<&str, Game> = Map::new("games");
-pub const PLAYER_STATS: Map<&Addr, PlayerStats> = Map::new("player_stats");
+/// Set while a migration spans several `migrate` calls
+pub const MIGRATION: Item<MigrationStage> = Item::new("migration");
+/// Admin transfer awaiting acceptance; see `ProposeNewAdmin`
+pub const PENDING_ADMIN: Item<PendingAdmin> = Item::new("pending_admin");
+/// Role holders keyed by (role, address); see `Role`
+pub const ROLES: Roles = Roles::new("roles");
+/// Halted operations keyed by name; see `Operation`
+pub const PAUSED: Map<String, Empty> = Map::new("paused");
 pub const TOTAL_GAMES: Item<u64> = Item::new("total_games");
 pub const GAME_COUNTER: Item<u64> = Item::new("game_counter");
+/// Games created with an idempotency key. Keys are shared by every
+/// Reporter, so a retry sent through another server instance still finds
+/// the game.
+pub const IDEMPOTENCY_KEYS: Map<&str, String> = Map::new("game_idempotency_keys");
+/// Game created for each of the server's match IDs
+pub const SERVER_MATCH_GAMES: Map<&str, String> = Map::new("server_match_games");
+pub const MATCHES: Map<&str, Match> = Map::new("matches");
+/// Revealed dice seeds keyed by (game_id, turn)
+pub const DICE_REVEALS: Map<(&str, u32), DiceReveal> = Map::new("dice_reveals");
+pub const MATCH_COUNTER: Item<u64> = Item::new("match_counter");
+/// Keyed by escrow ID (the game ID, or the match ID for matches)
+pub const SETTLEMENTS: Map<&str, Settlement> = Map::new("settlements");
+pub const DISPUTES: Map<&str, Dispute> = Map::new("disputes");
+/// Keyed by (player, achievement slug)
+pub const ACHIEVEMENTS: Map<(&Addr, &str), EarnedAchievement> = Map::new("achievements");
+pub const SIGNING_KEYS: Map<&Addr, SigningKey> = Map::new("signing_keys");
+/// Last nonce accepted for each game's co-signed result
+pub const RESULT_NONCES: Map<&str, u64> = Map::new("result_nonces");
+pub const CHALLENGE_COUNTER: Item<u64> = Item::new("challenge_counter");
+pub const PROPOSAL_COUNTER: Item<u64> = Item::new("proposal_counter");
+pub const SEASONS: Map<u32, Season> = Map::new("seasons");
+pub const SEASON_COUNTER: Item<u32> = Item::new("season_counter");
+/// Set only while a season is running
+pub const ACTIVE_SEASON: Item<u32> = Item::new("active_season");
+/// Set while `EndSeason` spans several calls
+pub const SEASON_ENDING: Item<SeasonEnding> = Item::new("season_ending");
+/// Players archived by an ending season, waiting to be ranked. Keyed by
+/// (season, final rating, address) so they come out in leaderboard order.
+pub const SEASON_ENTRIES: Map<(u32, u32, &Addr), SeasonStanding> = Map::new("season_entries");
+pub const PLATFORM_STATS: Item<PlatformStats> = Item::new("platform_stats");
+/// Keyed by UTC day number (block time in seconds / 86400)
+pub const DAILY_STATS: Map<u64, PlatformStats> = Map::new("daily_stats");
+/// Players who finished a game on a UTC day, keyed by (day, player)
+pub const DAILY_PLAYERS: Map<(u64, &Addr), Empty> = Map::new("daily_players");
+
+/// Challenges by status, then expiry, so unexpired open ones can be paged
+/// without walking the rest
+pub struct ChallengeIndexes<'a> {
+    pub status: MultiIndex<'a, (String, u64), Challenge, String>,
+}
+
+impl<'a> IndexList<Challenge> for ChallengeIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Challenge>> + '_> {
+        let v: Vec<&dyn Index<Challenge>> = vec![&self.status];
+        Box::new(v.into_iter())
+    }
+}
+
+pub const CHALLENGES: IndexedMap<&str, Challenge, ChallengeIndexes> = IndexedMap::new(
+    "challenges",
+    ChallengeIndexes {
+        status: MultiIndex::new(
+            |_pk, c| (format!("{:?}", c.status), c.expires_at),
+            "challenges",
+            "challenges__status",
+        ),
+    },
+);
+
+/// Lets a player see the proposals waiting on them. Keyed by opponent,
+/// status and expiry, so the pending, unexpired ones are a contiguous range.
+pub struct ProposalIndexes<'a> {
+    pub opponent: MultiIndex<'a, (Addr, String, u64), Proposal, String>,
+}
+
+impl<'a> IndexList<Proposal> for ProposalIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Proposal>> + '_> {
+        let v: Vec<&dyn Index<Proposal>> = vec![&self.opponent];
+        Box::new(v.into_iter())
+    }
+}
+
+pub const PROPOSALS: IndexedMap<&str, Proposal, ProposalIndexes> = IndexedMap::new(
+    "proposals",
+    ProposalIndexes {
+        opponent: MultiIndex::new(
+            |_pk, p| (p.opponent.clone(), format!("{:?}", p.status), p.expires_at),
+            "proposals",
+            "proposals__opponent",
+        ),
+    },
+);
+
+/// Lets a player's season history be read without scanning every season
+pub struct SeasonStandingIndexes<'a> {
+    pub player: MultiIndex<'a, Addr, SeasonStanding, (u32, u32)>,
+}
+
+impl<'a> IndexList<SeasonStanding> for SeasonStandingIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<SeasonStanding>> + '_> {
+        let v: Vec<&dyn Index<SeasonStanding>> = vec![&self.player];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Archived standings keyed by (season_id, rank)
+pub const SEASON_STANDINGS: IndexedMap<(u32, u32), SeasonStanding, SeasonStandingIndexes> =
+    IndexedMap::new(
+        "season_standings",
+        SeasonStandingIndexes {
+            player: MultiIndex::new(
+                |_pk, s| s.address.clone(),
+                "season_standings",
+                "season_standings__player",
+            ),
+        },
+    );
+
+/// Orders players by rating for the leaderboard. Entries are keyed by
+/// (rating, address), so ties break on address.
+pub struct PlayerStatsIndexes<'a> {
+    pub rating: MultiIndex<'a, u32, PlayerStats, Addr>,
+    pub rating_counts: RatingCounts,
+}
+
+impl<'a> IndexList<PlayerStats> for PlayerStatsIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<PlayerStats>> + '_> {
+        let v: Vec<&dyn Index<PlayerStats>> = vec![&self.rating, &self.rating_counts];
+        Box::new(v.into_iter())
+    }
+}
+
+pub const PLAYER_STATS: IndexedMap<&Addr, PlayerStats, PlayerStatsIndexes> = IndexedMap::new(
+    "player_stats",
+    PlayerStatsIndexes {
+        rating: MultiIndex::new(|_pk, s| s.rating, "player_stats", "player_stats__rating"),
+        rating_counts: RatingCounts::new(
+            "player_stats__rating_count",
+            "player_stats__rating_band_count",
+        ),
+    },
+);
+
+/// Width of the rating bands `RatingCounts` totals: 10.00 rating points
+pub const RATING_BAND: u32 = 1_000;
+
+/// How many players hold each rating, and each band of `RATING_BAND`
+/// ratings. Kept as an index of `PLAYER_STATS` so every save keeps it
+/// current; counting the players above someone then reads the bands above
+/// theirs and the ratings above theirs within it, however many players
+/// there are.
+pub struct RatingCounts {
+    ratings: Map<u32, u64>,
+    bands: Map<u32, u64>,
+}
+
+impl RatingCounts {
+    pub const fn new(ratings_namespace: &'static str, bands_namespace: &'static str) -> Self {
+        RatingCounts {
+            ratings: Map::new(ratings_namespace),
+            bands: Map::new(bands_namespace),
+        }
+    }
+
+    /// Players rated strictly above `rating`
+    pub fn count_above(&self, storage: &dyn Storage, rating: u32) -> StdResult<u64> {
+        let band = rating / RATING_BAND;
+        let band_end = band.saturating_add(1).saturating_mul(RATING_BAND);
+        let in_bands_above = self
+            .bands
+            .range(
+                storage,
+                Some(Bound::exclusive(band)),
+                None,
+                Order::Ascending,
+            )
+            .map(|item| item.map(|(_, count)| count))
+            .sum::<StdResult<u64>>()?;
+        let in_band = self
+            .ratings
+            .range(
+                storage,
+                Some(Bound::exclusive(rating)),
+                Some(Bound::exclusive(band_end)),
+                Order::Ascending,
+            )
+            .map(|item| item.map(|(_, count)| count))
+            .sum::<StdResult<u64>>()?;
+        Ok(in_bands_above + in_band)
+    }
+
+    /// Count a player in or out of their rating. Entries saved before the
+    /// counts existed were never counted in, so taking them out is a no-op;
+    /// the migration re-saves every player to count them.
+    fn count(&self, storage: &mut dyn Storage, rating: u32, add: bool) -> StdResult<()> {
+        for (counts, key) in [(&self.ratings, rating), (&self.bands, rating / RATING_BAND)] {
+            let count = counts.may_load(storage, key)?.unwrap_or_default();
+            let count = if add {
+                count + 1
+            } else {
+                count.saturating_sub(1)
+            };
+            if count == 0 {
+                counts.remove(storage, key);
+            } else {
+                counts.save(storage, key, &count)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Index<PlayerStats> for RatingCounts {
+    fn save(&self, storage: &mut dyn Storage, _pk: &[u8], data: &PlayerStats) -> StdResult<()> {
+        self.count(storage, data.rating, true)
+    }
+
+    fn remove(
+        &self,
+        storage: &mut dyn Storage,
+        _pk: &[u8],
+        old_data: &PlayerStats,
+    ) -> StdResult<()> {
+        self.count(storage, old_data.rating, false)
+    }
+}
+
+/// Secondary indexes over `GAMES`. Player indexes are suffixed with
+/// `created_at` so a player's history can be paged newest-first.
+pub struct GameIndexes<'a> {
+    pub player_a: MultiIndex<'a, (Addr, u64), Game, String>,
+    pub player_b: MultiIndex<'a, (Addr, u64), Game, String>,
+    pub status: MultiIndex<'a, (String, u64), Game, String>,
+    /// The player indexes again with the status ahead of `created_at`, so a
+    /// player's games in one status can be paged without skipping the rest
+    pub player_a_status: MultiIndex<'a, (Addr, String, u64), Game, String>,
+    pub player_b_status: MultiIndex<'a, (Addr, String, u64), Game, String>,
+}
+
+impl<'a> IndexList<Game> for GameIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Game>> + '_> {
+        let v: Vec<&dyn Index<Game>> = vec![
+            &self.player_a,
+            &self.player_b,
+            &self.status,
+            &self.player_a_status,
+            &self.player_b_status,
+        ];
+        Box::new(v.into_iter())
+    }
+}
+
+pub const GAMES: IndexedMap<&str, Game, GameIndexes> = IndexedMap::new(
+    "games",
+    GameIndexes {
+        player_a: MultiIndex::new(
+            |_pk, g| (g.player_a.clone(), g.created_at),
+            "games",
+            "games__player_a",
+        ),
+        player_b: MultiIndex::new(
+            |_pk, g| (g.player_b.clone(), g.created_at),
+            "games",
+            "games__player_b",
+        ),
+        status: MultiIndex::new(
+            |_pk, g| (format!("{:?}", g.status), g.created_at),
+            "games",
+            "games__status",
+        ),
+        player_a_status: MultiIndex::new(
+            |_pk, g| (g.player_a.clone(), format!("{:?}", g.status), g.created_at),
+            "games",
+            "games__player_a_status",
+        ),
+        player_b_status: MultiIndex::new(
+            |_pk, g| (g.player_b.clone(), format!("{:?}", g.status), g.created_at),
+            "games",
+            "games__player_b_status",
+        ),
+    },
+);
+
+/// Indexes each pair under both players, suffixed with the number of games
+/// so a player's opponents can be paged most-played first.
+pub struct HeadToHeadIndexes<'a> {
+    pub player_a: MultiIndex<'a, (Addr, u64), HeadToHead, (Addr, Addr)>,
+    pub player_b: MultiIndex<'a, (Addr, u64), HeadToHead, (Addr, Addr)>,
+}
+
+impl<'a> IndexList<HeadToHead> for HeadToHeadIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<HeadToHead>> + '_> {
+        let v: Vec<&dyn Index<HeadToHead>> = vec![&self.player_a, &self.player_b];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Keyed by (lower address, higher address)
+pub const HEAD_TO_HEAD: IndexedMap<(&Addr, &Addr), HeadToHead, HeadToHeadIndexes> = IndexedMap::new(
+    "head_to_head",
+    HeadToHeadIndexes {
+        player_a: MultiIndex::new(
+            |_pk, h| (h.player_a.clone(), h.games),
+            "head_to_head",
+            "head_to_head__player_a",
+        ),
+        player_b: MultiIndex::new(
+            |_pk, h| (h.player_b.clone(), h.games),
+            "head_to_head",
+            "head_to_head__player_b",
+        ),
+    },
+);
@@ -0,0 +1,229 @@
+//! Grantable roles shared by the platform contracts.
+//!
+//! Each contract keeps its own `Role` enum and stores the holders in a
+//! `Roles` map keyed by (role, address), where the role key is the variant's
+//! name. The contract admin holds every role implicitly, and only the admin
+//! grants or revokes them.
+
+use std::fmt::Debug;
+
+use cosmwasm_std::{Addr, DepsMut, Empty, Order, Response, StdError, StdResult, Storage};
+use cw_storage_plus::Map;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RoleError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+}
+
+/// Role holders keyed by (role, address)
+pub struct Roles<'a>(Map<(String, &'a Addr), Empty>);
+
+impl Roles<'_> {
+    pub const fn new(namespace: &'static str) -> Self {
+        Roles(Map::new(namespace))
+    }
+
+    /// Whether `addr` holds `role`. The admin holds every role.
+    pub fn has(&self, storage: &dyn Storage, admin: &Addr, role: &impl Debug, addr: &Addr) -> bool {
+        addr == admin || self.0.has(storage, (key(role), addr))
+    }
+
+    /// Fail with `Unauthorized` unless `addr` holds `role`
+    pub fn ensure(
+        &self,
+        storage: &dyn Storage,
+        admin: &Addr,
+        role: &impl Debug,
+        addr: &Addr,
+    ) -> Result<(), RoleError> {
+        if !self.has(storage, admin, role, addr) {
+            return Err(RoleError::Unauthorized {});
+        }
+        Ok(())
+    }
+
+    pub fn grant(
+        &self,
+        storage: &mut dyn Storage,
+        role: &impl Debug,
+        addr: &Addr,
+    ) -> StdResult<()> {
+        self.0.save(storage, (key(role), addr), &Empty {})
+    }
+
+    pub fn revoke(&self, storage: &mut dyn Storage, role: &impl Debug, addr: &Addr) {
+        self.0.remove(storage, (key(role), addr));
+    }
+
+    /// Make `addr` the only holder of `role`, returning the holders it
+    /// replaced. This is what the single-address config fields that
+    /// predate roles did.
+    pub fn replace(
+        &self,
+        storage: &mut dyn Storage,
+        role: &impl Debug,
+        addr: &Addr,
+    ) -> StdResult<Vec<Addr>> {
+        let replaced: Vec<Addr> = self
+            .members(storage, role)?
+            .into_iter()
+            .filter(|member| member != addr)
+            .collect();
+        for member in &replaced {
+            self.revoke(storage, role, member);
+        }
+        self.grant(storage, role, addr)?;
+        Ok(replaced)
+    }
+
+    /// Addresses explicitly granted `role`, not counting the admin
+    pub fn members(&self, storage: &dyn Storage, role: &impl Debug) -> StdResult<Vec<Addr>> {
+        self.0
+            .prefix(key(role))
+            .keys(storage, None, None, Order::Ascending)
+            .collect()
+    }
+
+    /// Whether any address still holds a granted role
+    pub fn is_empty(&self, storage: &dyn Storage) -> bool {
+        self.0
+            .keys_raw(storage, None, None, Order::Ascending)
+            .next()
+            .is_none()
+    }
+
+    /// `GrantRole`: the admin grants `role` to `address`
+    pub fn execute_grant(
+        &self,
+        deps: DepsMut,
+        admin: &Addr,
+        sender: &Addr,
+        role: &impl Debug,
+        address: &str,
+    ) -> Result<Response, RoleError> {
+        if sender != admin {
+            return Err(RoleError::Unauthorized {});
+        }
+
+        let addr = deps.api.addr_validate(address)?;
+        self.grant(deps.storage, role, &addr)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "grant_role")
+            .add_attribute("role", key(role))
+            .add_attribute("address", addr))
+    }
+
+    /// `RevokeRole`: the admin takes `role` back from `address`
+    pub fn execute_revoke(
+        &self,
+        deps: DepsMut,
+        admin: &Addr,
+        sender: &Addr,
+        role: &impl Debug,
+        address: &str,
+    ) -> Result<Response, RoleError> {
+        if sender != admin {
+            return Err(RoleError::Unauthorized {});
+        }
+
+        let addr = deps.api.addr_validate(address)?;
+        self.revoke(deps.storage, role, &addr);
+
+        Ok(Response::new()
+            .add_attribute("action", "revoke_role")
+            .add_attribute("role", key(role))
+            .add_attribute("address", addr))
+    }
+}
+
+fn key(role: &impl Debug) -> String {
+    format!("{:?}", role)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, MockApi};
+
+    #[derive(Debug)]
+    enum Role {
+        Reporter,
+        Pauser,
+    }
+
+    const ROLES: Roles = Roles::new("roles");
+
+    #[test]
+    fn test_grant_revoke_and_admin() {
+        let mut deps = mock_dependencies();
+        let api = MockApi::default();
+        let (admin, server) = (api.addr_make("admin"), api.addr_make("server"));
+
+        assert_eq!(
+            ROLES.execute_grant(
+                deps.as_mut(),
+                &admin,
+                &server,
+                &Role::Reporter,
+                server.as_str()
+            ),
+            Err(RoleError::Unauthorized {})
+        );
+        let res = ROLES
+            .execute_grant(
+                deps.as_mut(),
+                &admin,
+                &admin,
+                &Role::Reporter,
+                server.as_str(),
+            )
+            .unwrap();
+        assert_eq!(res.attributes[1].value, "Reporter");
+
+        assert!(ROLES.has(&deps.storage, &admin, &Role::Reporter, &server));
+        assert!(!ROLES.has(&deps.storage, &admin, &Role::Pauser, &server));
+        assert!(ROLES.has(&deps.storage, &admin, &Role::Pauser, &admin));
+        assert_eq!(
+            ROLES.ensure(&deps.storage, &admin, &Role::Pauser, &server),
+            Err(RoleError::Unauthorized {})
+        );
+        assert_eq!(
+            ROLES.members(&deps.storage, &Role::Reporter).unwrap(),
+            vec![server.clone()]
+        );
+        assert!(!ROLES.is_empty(&deps.storage));
+
+        let rotated = api.addr_make("rotated");
+        assert_eq!(
+            ROLES
+                .replace(&mut deps.storage, &Role::Reporter, &rotated)
+                .unwrap(),
+            vec![server.clone()]
+        );
+        assert_eq!(
+            ROLES.members(&deps.storage, &Role::Reporter).unwrap(),
+            vec![rotated.clone()]
+        );
+        ROLES
+            .replace(&mut deps.storage, &Role::Reporter, &server)
+            .unwrap();
+
+        ROLES
+            .execute_revoke(
+                deps.as_mut(),
+                &admin,
+                &admin,
+                &Role::Reporter,
+                server.as_str(),
+            )
+            .unwrap();
+        assert!(!ROLES.has(&deps.storage, &admin, &Role::Reporter, &server));
+        assert!(ROLES.is_empty(&deps.storage));
+    }
+}
@@ -5,10 +5,17 @@ use cosmwasm_std::{
 
 use crate::error::ContractError;
 use crate::msg::{
-    ConfigResponse, ExecuteMsg, InstantiateMsg, MemberInfo, MemberResponse, MigrateMsg, QueryMsg,
-    ReferralsResponse, StatsResponse,
+    ConfigResponse, ExecuteMsg, InstantiateMsg, MemberInfo, MemberResponse, MigrateMsg,
+    PendingAdminResponse, QueryMsg, ReferralsResponse, RoleMembers, RolesResponse, StatsResponse,
 };
-use crate::state::{Config, Member, CONFIG, MEMBERS, TOTAL_MEMBERS, TOTAL_PAID_OUT};
+use crate::state::{
+    Config, Member, PendingAdmin, Role, CONFIG, MEMBERS, PENDING_ADMIN, ROLES, TOTAL_MEMBERS,
+    TOTAL_PAID_OUT,
+};
+
+/// How long a proposed admin has to accept by default, and at most
+const DEFAULT_ADMIN_TRANSFER_EXPIRY: u64 = 7 * 24 * 60 * 60;
+const MAX_ADMIN_TRANSFER_EXPIRY: u64 = 30 * 24 * 60 * 60;
 
 #[entry_point]
 pub fn instantiate(
@@ -57,6 +64,15 @@ pub fn execute(
     match msg {
         ExecuteMsg::Join { referrer } => execute_join(deps, env, info, referrer),
         ExecuteMsg::Claim { referrer } => execute_claim(deps, env, info, referrer),
+        ExecuteMsg::ProposeNewAdmin {
+            address,
+            expires_in,
+        } => execute_propose_new_admin(deps, env, info, address, expires_in),
+        ExecuteMsg::AcceptAdmin {} => execute_accept_admin(deps, env, info),
+        ExecuteMsg::CancelAdminTransfer {} => execute_cancel_admin_transfer(deps, info),
+        ExecuteMsg::RenounceAdmin {} => execute_renounce_admin(deps, env, info),
+        ExecuteMsg::GrantRole { role, address } => execute_grant_role(deps, info, role, address),
+        ExecuteMsg::RevokeRole { role, address } => execute_revoke_role(deps, info, role, address),
         ExecuteMsg::UpdateConfig {
             platform_address,
             entry_fee,
@@ -201,9 +217,9 @@ fn execute_join(
         let new_total = TOTAL_MEMBERS.load(deps.storage)?;
         let token_id = format!("pyramid-{}", new_total);
         let mint_msg = create_cw721_mint_msg(
-            &nft_contract.to_string(),
+            nft_contract.as_ref(),
             &token_id,
-            &info.sender.to_string(),
+            info.sender.as_ref(),
         )?;
         response = response.add_message(mint_msg);
         response = response.add_attribute("nft_minted", token_id);
@@ -414,6 +430,128 @@ fn execute_claim(
     Ok(response)
 }
 
+fn execute_propose_new_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+    expires_in: Option<u64>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    let expires_in = expires_in.unwrap_or(DEFAULT_ADMIN_TRANSFER_EXPIRY);
+    if expires_in == 0 || expires_in > MAX_ADMIN_TRANSFER_EXPIRY {
+        return Err(ContractError::InvalidAdminTransferExpiry {
+            seconds: expires_in,
+            max: MAX_ADMIN_TRANSFER_EXPIRY,
+        });
+    }
+
+    let pending = PendingAdmin {
+        address: deps.api.addr_validate(&address)?,
+        expires_at: env.block.time.seconds() + expires_in,
+    };
+    PENDING_ADMIN.save(deps.storage, &pending)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose_new_admin")
+        .add_attribute("pending_admin", pending.address)
+        .add_attribute("expires_at", pending.expires_at.to_string()))
+}
+
+fn execute_accept_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let pending = PENDING_ADMIN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingAdmin {})?;
+    if info.sender != pending.address {
+        return Err(ContractError::Unauthorized {});
+    }
+    if env.block.time.seconds() >= pending.expires_at {
+        return Err(ContractError::AdminTransferExpired {
+            expires_at: pending.expires_at,
+        });
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+    let previous = std::mem::replace(&mut config.admin, pending.address);
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_admin")
+        .add_attribute("previous_admin", previous)
+        .add_attribute("admin", config.admin))
+}
+
+fn execute_cancel_admin_transfer(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    if !PENDING_ADMIN.exists(deps.storage) {
+        return Err(ContractError::NoPendingAdmin {});
+    }
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new().add_attribute("action", "cancel_admin_transfer"))
+}
+
+fn execute_renounce_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Grants could never be revoked afterwards, so none may outlive the admin
+    if !ROLES.is_empty(deps.storage) {
+        return Err(ContractError::RolesRemain {});
+    }
+
+    // The contract never sends messages to itself, so this leaves no one
+    // able to act as admin
+    config.admin = env.contract.address;
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "renounce_admin")
+        .add_attribute("previous_admin", info.sender))
+}
+
+fn execute_grant_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ROLES.execute_grant(deps, &config.admin, &info.sender, &role, &address)?)
+}
+
+fn execute_revoke_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ROLES.execute_revoke(deps, &config.admin, &info.sender, &role, &address)?)
+}
+
+#[allow(clippy::too_many_arguments)]
 fn execute_update_config(
     deps: DepsMut,
     info: MessageInfo,
@@ -426,8 +564,8 @@ fn execute_update_config(
 ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
 
-    // Only admin can update
-    if info.sender != config.admin {
+    // Only a ConfigAdmin can update
+    if !ROLES.has(deps.storage, &config.admin, &Role::ConfigAdmin, &info.sender) {
         return Err(ContractError::Unauthorized {});
     }
 
@@ -459,6 +597,8 @@ fn execute_update_config(
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::Roles {} => to_json_binary(&query_roles(deps)?),
+        QueryMsg::PendingAdmin {} => to_json_binary(&query_pending_admin(deps)?),
         QueryMsg::Member { address } => to_json_binary(&query_member(deps, address)?),
         QueryMsg::Stats {} => to_json_binary(&query_stats(deps)?),
         QueryMsg::Referrals { address } => to_json_binary(&query_referrals(deps, address)?),
@@ -479,6 +619,28 @@ fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     })
 }
 
+fn query_pending_admin(deps: Deps) -> StdResult<PendingAdminResponse> {
+    Ok(PendingAdminResponse {
+        admin: CONFIG.load(deps.storage)?.admin,
+        pending: PENDING_ADMIN.may_load(deps.storage)?,
+    })
+}
+
+fn query_roles(deps: Deps) -> StdResult<RolesResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let roles = [Role::ConfigAdmin]
+        .into_iter()
+        .map(|role| {
+            let members = ROLES.members(deps.storage, &role)?;
+            Ok(RoleMembers { role, members })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(RolesResponse {
+        admin: config.admin,
+        roles,
+    })
+}
+
 fn query_member(deps: Deps, address: String) -> StdResult<MemberResponse> {
     let addr = deps.api.addr_validate(&address)?;
 
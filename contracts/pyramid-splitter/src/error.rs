@@ -1,5 +1,6 @@
 use cosmwasm_std::StdError;
 use thiserror::Error;
+use platform_roles::RoleError;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
@@ -9,6 +10,18 @@ pub enum ContractError {
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("No admin transfer is pending")]
+    NoPendingAdmin {},
+
+    #[error("Admin transfer expired at {expires_at}")]
+    AdminTransferExpired { expires_at: u64 },
+
+    #[error("Revoke every granted role before renouncing admin")]
+    RolesRemain {},
+
+    #[error("Invalid admin transfer expiry {seconds}s: must be between 1 and {max}")]
+    InvalidAdminTransferExpiry { seconds: u64, max: u64 },
+
     #[error("Already a member")]
     AlreadyMember {},
 
@@ -37,3 +50,12 @@ pub enum ContractError {
     #[error("Caller does not own a Crossmint NFT")]
     NoNftOwnership {},
 }
+
+impl From<RoleError> for ContractError {
+    fn from(err: RoleError) -> Self {
+        match err {
+            RoleError::Std(err) => ContractError::Std(err),
+            RoleError::Unauthorized {} => ContractError::Unauthorized {},
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Addr;
 
+use crate::state::{PendingAdmin, Role};
+
 #[cw_serde]
 pub struct InstantiateMsg {
     pub platform_address: String,
@@ -28,7 +30,32 @@ pub enum ExecuteMsg {
     /// User provides referrer (honor system - no incentive to lie).
     Claim { referrer: Option<String> },
 
-    /// Admin only: update config
+    /// Admin only: propose `address` as the next admin. It takes over once it
+    /// sends `AcceptAdmin` before the proposal expires (default 7 days, at
+    /// most 30). Replaces any earlier proposal.
+    ProposeNewAdmin {
+        address: String,
+        expires_in: Option<u64>,
+    },
+
+    /// Proposed admin: accept a pending admin transfer
+    AcceptAdmin {},
+
+    /// Admin only: withdraw a pending admin transfer
+    CancelAdminTransfer {},
+
+    /// Admin only: give up admin rights for good. The contract becomes its own
+    /// admin, so nothing can change the configuration or roles afterwards.
+    /// Every granted role must be revoked first.
+    RenounceAdmin {},
+
+    /// Admin only: give `address` a role
+    GrantRole { role: Role, address: String },
+
+    /// Admin only: take a role away from `address`
+    RevokeRole { role: Role, address: String },
+
+    /// ConfigAdmin only: update config
     UpdateConfig {
         platform_address: Option<String>,
         entry_fee: Option<u128>,
@@ -48,6 +75,14 @@ pub enum QueryMsg {
     #[returns(ConfigResponse)]
     Config {},
 
+    /// Holders of each role, besides the admin who holds them all
+    #[returns(RolesResponse)]
+    Roles {},
+
+    /// The current admin and any transfer awaiting acceptance
+    #[returns(PendingAdminResponse)]
+    PendingAdmin {},
+
     #[returns(MemberResponse)]
     Member { address: String },
 
@@ -70,6 +105,24 @@ pub struct ConfigResponse {
     pub crossmint_nft_contract: Option<Addr>,
 }
 
+#[cw_serde]
+pub struct RoleMembers {
+    pub role: Role,
+    pub members: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct RolesResponse {
+    pub admin: Addr,
+    pub roles: Vec<RoleMembers>,
+}
+
+#[cw_serde]
+pub struct PendingAdminResponse {
+    pub admin: Addr,
+    pub pending: Option<PendingAdmin>,
+}
+
 #[cw_serde]
 pub struct MemberResponse {
     pub is_member: bool,
@@ -1,13 +1,14 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::Addr;
 use cw_storage_plus::{Item, Map};
+use platform_roles::Roles;
 
 #[cw_serde]
 pub struct Config {
     pub admin: Addr,
     pub platform_address: Addr,
     pub usdc_denom: String,
-    pub entry_fee: u128,      // 8 USDC = 8_000_000
+    pub entry_fee: u128,       // 8 USDC = 8_000_000
     pub referral_reward: u128, // 5 USDC = 5_000_000
     pub platform_fee: u128,    // 3 USDC = 3_000_000
     /// Optional CW-721 contract for membership NFTs (direct USDC flow)
@@ -18,6 +19,21 @@ pub struct Config {
     pub crossmint_nft_contract: Option<Addr>,
 }
 
+/// An admin transfer the proposed admin has not yet accepted
+#[cw_serde]
+pub struct PendingAdmin {
+    pub address: Addr,
+    pub expires_at: u64,
+}
+
+/// Permissions the admin can grant to other addresses. The admin holds
+/// every role implicitly.
+#[cw_serde]
+pub enum Role {
+    /// Updates the configuration
+    ConfigAdmin,
+}
+
 #[cw_serde]
 pub struct Member {
     pub address: Addr,
@@ -32,6 +48,10 @@ pub struct Member {
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
+/// Admin transfer awaiting acceptance; see `ProposeNewAdmin`
+pub const PENDING_ADMIN: Item<PendingAdmin> = Item::new("pending_admin");
+/// Role holders keyed by (role, address); see `Role`
+pub const ROLES: Roles = Roles::new("roles");
 pub const MEMBERS: Map<&Addr, Member> = Map::new("members");
 pub const TOTAL_MEMBERS: Item<u64> = Item::new("total_members");
 pub const TOTAL_PAID_OUT: Item<u128> = Item::new("total_paid_out");
@@ -78,13 +78,23 @@ echo ""
 echo "Get ESCROW_ADDRESS: xiond query tx <TXHASH> --node $NODE | grep -A1 '_contract_address'"
 echo ""
 
-# Step 3: Set game contract (after deploying the game server)
-echo "Step 3: Set the game contract that can settle matches (replace addresses):"
+# Step 3: Grant the game contract the reporter role (after deploying the game server)
+echo "Step 3: Allow the game contract to settle matches and start games on deposit (replace addresses):"
 echo ""
 cat << EOF
-UPDATE_MSG='{"update_config":{"game_contract":"GAME_CONTRACT_ADDRESS"}}'
+GRANT_MSG='{"grant_role":{"role":"reporter","address":"GAME_CONTRACT_ADDRESS"}}'
+HOOK_MSG='{"update_config":{"game_hook":"GAME_CONTRACT_ADDRESS"}}'
 
-xiond tx wasm execute ESCROW_ADDRESS "\$UPDATE_MSG" \\
+xiond tx wasm execute ESCROW_ADDRESS "\$GRANT_MSG" \\
+  --from $WALLET_ADDRESS \\
+  --chain-id $CHAIN_ID \\
+  --node $NODE \\
+  --gas auto \\
+  --gas-adjustment 1.5 \\
+  --gas-prices 0.025uxion \\
+  -y
+
+xiond tx wasm execute ESCROW_ADDRESS "\$HOOK_MSG" \\
   --from $WALLET_ADDRESS \\
   --chain-id $CHAIN_ID \\
   --node $NODE \\
@@ -101,7 +111,9 @@ echo "==================================="
 echo ""
 echo "Notes:"
 echo "- The admin (deployer) can create escrows and settle games"
-echo "- Set game_contract to allow a backend service to settle games"
+echo "- Grant the reporter role to let the game contract or a backend settle games"
+echo "- update_config game_contract is deprecated: it replaces every reporter"
+echo "- game_hook: game contract told when both deposits are in, so the game starts"
 echo "- Players deposit USDC directly into the escrow contract"
 echo "- Winner receives (2 * wager) minus the platform rake"
 echo "==================================="
@@ -1,19 +1,57 @@
 use cosmwasm_std::{
-    entry_point, to_json_binary, BankMsg, Binary, Coin, Deps, DepsMut, Env, MessageInfo,
-    Response, StdResult, Uint128,
+    entry_point, to_json_binary, Addr, BankMsg, Binary, Coin, Deps, DepsMut, Empty, Env,
+    MessageInfo, Order, Reply, Response, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
 
+use cw_storage_plus::Bound;
+
 use crate::error::ContractError;
 use crate::msg::{
-    ConfigResponse, EscrowResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, PendingDoubleResponse,
-    QueryMsg, StatsResponse,
+    ConfigResponse, DailyStats, DailyStatsResponse, EscrowResponse, ExecuteMsg, InstantiateMsg,
+    MigrateMsg, OwedResponse, PauseStatusResponse, PendingAdminResponse, PendingDoubleResponse,
+    QueryMsg, RoleMembers, RolesResponse, StatsResponse,
 };
 use crate::state::{
-    Config, Escrow, EscrowStatus, PendingDouble, CONFIG, ESCROWS, TOTAL_GAMES_SETTLED,
-    TOTAL_RAKE_COLLECTED,
+    Config, Escrow, EscrowStats, EscrowStatus, Operation, PendingAdmin, PendingDouble, Role,
+    CONFIG, DAILY_DEPOSITORS, DAILY_STATS, DEPOSITORS, ESCROWS, ESCROW_STATS, OWED_BALANCES,
+    PAUSED, PENDING_ADMIN, ROLES, TOTAL_GAMES_SETTLED, TOTAL_RAKE_COLLECTED, WITHDRAW_ONLY,
 };
 
 const MAX_CUBE_VALUE: u32 = 64;
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+/// How long a proposed admin has to accept by default, and at most
+const DEFAULT_ADMIN_TRANSFER_EXPIRY: u64 = 7 * 24 * 60 * 60;
+const MAX_ADMIN_TRANSFER_EXPIRY: u64 = 30 * 24 * 60 * 60;
+
+/// Reply ID for game hooks, whose failure must not block the deposit,
+/// forfeit or withdrawal that triggered them
+const GAME_HOOK_REPLY_ID: u64 = 1;
+
+/// Game contract execute messages (typed for to_json_binary)
+#[derive(serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+#[allow(clippy::enum_variant_names)]
+enum GameHookMsg {
+    EscrowActivated {
+        game_id: String,
+    },
+    EscrowForfeited {
+        game_id: String,
+        winner: String,
+        loser: String,
+        payout: u128,
+        rake: u128,
+        winner_stake: u128,
+        loser_stake: u128,
+        cube_value: u32,
+    },
+    EscrowWithdrawn {
+        game_id: String,
+    },
+}
 
 #[entry_point]
 pub fn instantiate(
@@ -22,23 +60,23 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    let game_contract = msg
-        .game_contract
-        .map(|addr| deps.api.addr_validate(&addr))
-        .transpose()?;
-
     let config = Config {
         admin: info.sender.clone(),
-        game_contract,
+        game_contract: None,
         usdc_denom: msg.usdc_denom,
         rake_bps: msg.rake_bps,
         rake_recipient: deps.api.addr_validate(&msg.rake_recipient)?,
         min_wager: msg.min_wager,
         max_wager: msg.max_wager,
         timeout_seconds: msg.timeout_seconds,
+        game_hook: None,
     };
 
     CONFIG.save(deps.storage, &config)?;
+    if let Some(addr) = msg.game_contract {
+        let addr = deps.api.addr_validate(&addr)?;
+        ROLES.grant(deps.storage, &Role::Reporter, &addr)?;
+    }
     TOTAL_RAKE_COLLECTED.save(deps.storage, &0u128)?;
     TOTAL_GAMES_SETTLED.save(deps.storage, &0u64)?;
 
@@ -54,6 +92,10 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    if let Some(operation) = pausable_operation(&msg) {
+        ensure_not_paused(deps.storage, &operation)?;
+    }
+
     match msg {
         ExecuteMsg::CreateEscrow {
             game_id,
@@ -70,6 +112,12 @@ pub fn execute(
             winner,
             multiplier,
         } => execute_settle(deps, env, info, game_id, winner, multiplier),
+        ExecuteMsg::Resettle {
+            game_id,
+            winner,
+            multiplier,
+        } => execute_resettle(deps, env, info, game_id, winner, multiplier),
+        ExecuteMsg::PayOwed { game_id } => execute_pay_owed(deps, info, game_id),
         ExecuteMsg::Cancel { game_id } => execute_cancel(deps, env, info, game_id),
         ExecuteMsg::ClaimTimeout { game_id } => execute_claim_timeout(deps, env, info, game_id),
         ExecuteMsg::OfferDouble {
@@ -81,22 +129,37 @@ pub fn execute(
         ExecuteMsg::RejectDouble { game_id, rejecter } => {
             execute_reject_double(deps, env, info, game_id, rejecter)
         }
+        ExecuteMsg::Pause { operations } => execute_set_paused(deps, info, operations, true),
+        ExecuteMsg::Unpause { operations } => execute_set_paused(deps, info, operations, false),
+        ExecuteMsg::SetWithdrawOnly { enabled } => execute_set_withdraw_only(deps, info, enabled),
+        ExecuteMsg::Withdraw { game_id } => execute_withdraw(deps, env, info, game_id),
+        ExecuteMsg::ProposeNewAdmin {
+            address,
+            expires_in,
+        } => execute_propose_new_admin(deps, env, info, address, expires_in),
+        ExecuteMsg::AcceptAdmin {} => execute_accept_admin(deps, env, info),
+        ExecuteMsg::CancelAdminTransfer {} => execute_cancel_admin_transfer(deps, info),
+        ExecuteMsg::RenounceAdmin {} => execute_renounce_admin(deps, env, info),
+        ExecuteMsg::GrantRole { role, address } => execute_grant_role(deps, info, role, address),
+        ExecuteMsg::RevokeRole { role, address } => execute_revoke_role(deps, info, role, address),
         ExecuteMsg::UpdateConfig {
-            game_contract,
             rake_bps,
             rake_recipient,
             min_wager,
             max_wager,
             timeout_seconds,
+            game_hook,
+            game_contract,
         } => execute_update_config(
             deps,
             info,
-            game_contract,
             rake_bps,
             rake_recipient,
             min_wager,
             max_wager,
             timeout_seconds,
+            game_hook,
+            game_contract,
         ),
     }
 }
@@ -112,15 +175,7 @@ fn execute_create_escrow(
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
-    // Only admin or game contract can create escrows
-    let is_authorized = info.sender == config.admin
-        || config
-            .game_contract
-            .as_ref()
-            .map_or(false, |gc| info.sender == *gc);
-    if !is_authorized {
-        return Err(ContractError::Unauthorized {});
-    }
+    ROLES.ensure(deps.storage, &config.admin, &Role::Reporter, &info.sender)?;
 
     // Check escrow doesn't already exist
     if ESCROWS.has(deps.storage, &game_id) {
@@ -151,9 +206,15 @@ fn execute_create_escrow(
         settled_at: None,
         cube_value: 1,
         pending_double: None,
+        winner: None,
+        multiplier: 0,
+        owed: 0,
     };
 
     ESCROWS.save(deps.storage, &game_id, &escrow)?;
+    record_stats(deps.storage, escrow.created_at, |stats| {
+        stats.escrows_created += 1
+    })?;
 
     Ok(Response::new()
         .add_attribute("action", "create_escrow")
@@ -163,16 +224,17 @@ fn execute_create_escrow(
 
 fn execute_deposit(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     game_id: String,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let mut escrow = ESCROWS
-        .may_load(deps.storage, &game_id)?
-        .ok_or(ContractError::EscrowNotFound {
-            game_id: game_id.clone(),
-        })?;
+    let mut escrow =
+        ESCROWS
+            .may_load(deps.storage, &game_id)?
+            .ok_or(ContractError::EscrowNotFound {
+                game_id: game_id.clone(),
+            })?;
 
     // Must be in AwaitingDeposits status
     if escrow.status != EscrowStatus::AwaitingDeposits {
@@ -218,14 +280,32 @@ fn execute_deposit(
         escrow.player_b_deposited = payment.amount.u128();
     }
 
-    // If both deposited, transition to Active
+    // If both deposited, transition to Active and let the game start
+    let mut response = Response::new();
     if escrow.player_a_deposited > 0 && escrow.player_b_deposited > 0 {
         escrow.status = EscrowStatus::Active;
+        if let Some(game_hook) = &config.game_hook {
+            let msg = WasmMsg::Execute {
+                contract_addr: game_hook.to_string(),
+                msg: to_json_binary(&GameHookMsg::EscrowActivated {
+                    game_id: game_id.clone(),
+                })?,
+                funds: vec![],
+            };
+            response = response.add_submessage(
+                SubMsg::reply_on_error(msg, GAME_HOOK_REPLY_ID)
+                    .with_payload(game_id.as_bytes().to_vec()),
+            );
+        }
     }
 
     ESCROWS.save(deps.storage, &game_id, &escrow)?;
+    let now = env.block.time.seconds();
+    let amount = payment.amount.u128();
+    record_stats(deps.storage, now, |stats| stats.volume_deposited += amount)?;
+    record_depositor(deps.storage, now, &info.sender)?;
 
-    Ok(Response::new()
+    Ok(response
         .add_attribute("action", "deposit")
         .add_attribute("game_id", game_id)
         .add_attribute("player", info.sender.to_string())
@@ -235,7 +315,7 @@ fn execute_deposit(
 
 fn execute_offer_double(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     game_id: String,
     doubler: String,
@@ -243,21 +323,14 @@ fn execute_offer_double(
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
-    // Only admin or game contract can call
-    let is_authorized = info.sender == config.admin
-        || config
-            .game_contract
-            .as_ref()
-            .map_or(false, |gc| info.sender == *gc);
-    if !is_authorized {
-        return Err(ContractError::Unauthorized {});
-    }
+    ROLES.ensure(deps.storage, &config.admin, &Role::Reporter, &info.sender)?;
 
-    let mut escrow = ESCROWS
-        .may_load(deps.storage, &game_id)?
-        .ok_or(ContractError::EscrowNotFound {
-            game_id: game_id.clone(),
-        })?;
+    let mut escrow =
+        ESCROWS
+            .may_load(deps.storage, &game_id)?
+            .ok_or(ContractError::EscrowNotFound {
+                game_id: game_id.clone(),
+            })?;
 
     // Must be Active
     if escrow.status != EscrowStatus::Active {
@@ -309,6 +382,9 @@ fn execute_offer_double(
     escrow.status = EscrowStatus::AwaitingDoubleDeposits;
 
     ESCROWS.save(deps.storage, &game_id, &escrow)?;
+    record_stats(deps.storage, env.block.time.seconds(), |stats| {
+        stats.doubles_offered += 1
+    })?;
 
     Ok(Response::new()
         .add_attribute("action", "offer_double")
@@ -321,16 +397,17 @@ fn execute_offer_double(
 
 fn execute_double_deposit(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     game_id: String,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let mut escrow = ESCROWS
-        .may_load(deps.storage, &game_id)?
-        .ok_or(ContractError::EscrowNotFound {
-            game_id: game_id.clone(),
-        })?;
+    let mut escrow =
+        ESCROWS
+            .may_load(deps.storage, &game_id)?
+            .ok_or(ContractError::EscrowNotFound {
+                game_id: game_id.clone(),
+            })?;
 
     // Must be AwaitingDoubleDeposits
     if escrow.status != EscrowStatus::AwaitingDoubleDeposits {
@@ -400,6 +477,13 @@ fn execute_double_deposit(
     }
 
     ESCROWS.save(deps.storage, &game_id, &escrow)?;
+    let amount = payment.amount.u128();
+    record_stats(deps.storage, env.block.time.seconds(), |stats| {
+        stats.volume_deposited += amount;
+        if both_deposited {
+            stats.doubles_accepted += 1;
+        }
+    })?;
 
     let mut response = Response::new()
         .add_attribute("action", "double_deposit")
@@ -425,21 +509,14 @@ fn execute_reject_double(
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
-    // Only admin or game contract can call
-    let is_authorized = info.sender == config.admin
-        || config
-            .game_contract
-            .as_ref()
-            .map_or(false, |gc| info.sender == *gc);
-    if !is_authorized {
-        return Err(ContractError::Unauthorized {});
-    }
+    ROLES.ensure(deps.storage, &config.admin, &Role::Reporter, &info.sender)?;
 
-    let mut escrow = ESCROWS
-        .may_load(deps.storage, &game_id)?
-        .ok_or(ContractError::EscrowNotFound {
-            game_id: game_id.clone(),
-        })?;
+    let mut escrow =
+        ESCROWS
+            .may_load(deps.storage, &game_id)?
+            .ok_or(ContractError::EscrowNotFound {
+                game_id: game_id.clone(),
+            })?;
 
     // Can reject from Active (before escrow offerDouble) or AwaitingDoubleDeposits
     if escrow.status != EscrowStatus::Active
@@ -523,7 +600,32 @@ fn execute_reject_double(
         });
     }
 
+    // Tell the game what the forfeit paid, since it never settled it
+    let (winner_stake, loser_stake) = stakes(&escrow, &doubler_addr);
+    if let Some(game_hook) = &config.game_hook {
+        let msg = WasmMsg::Execute {
+            contract_addr: game_hook.to_string(),
+            msg: to_json_binary(&GameHookMsg::EscrowForfeited {
+                game_id: game_id.clone(),
+                winner: doubler_addr.to_string(),
+                loser: rejecter_addr.to_string(),
+                payout,
+                rake,
+                winner_stake,
+                loser_stake,
+                cube_value: escrow.cube_value,
+            })?,
+            funds: vec![],
+        };
+        response = response.add_submessage(
+            SubMsg::reply_on_error(msg, GAME_HOOK_REPLY_ID)
+                .with_payload(game_id.as_bytes().to_vec()),
+        );
+    }
+
     escrow.status = EscrowStatus::Forfeited;
+    escrow.winner = Some(doubler_addr.clone());
+    escrow.multiplier = 1;
     escrow.settled_at = Some(env.block.time.seconds());
     escrow.pending_double = None;
     ESCROWS.save(deps.storage, &game_id, &escrow)?;
@@ -533,6 +635,12 @@ fn execute_reject_double(
     TOTAL_RAKE_COLLECTED.save(deps.storage, &(total_rake + rake))?;
     let total_settled = TOTAL_GAMES_SETTLED.load(deps.storage)?;
     TOTAL_GAMES_SETTLED.save(deps.storage, &(total_settled + 1))?;
+    record_stats(deps.storage, env.block.time.seconds(), |stats| {
+        stats.doubles_rejected += 1;
+        stats.games_settled += 1;
+        stats.volume_settled += total_pot;
+        stats.rake_collected += rake;
+    })?;
 
     Ok(response
         .add_attribute("action", "reject_double")
@@ -540,7 +648,9 @@ fn execute_reject_double(
         .add_attribute("rejecter", rejecter_addr.to_string())
         .add_attribute("winner", doubler_addr.to_string())
         .add_attribute("payout", payout.to_string())
-        .add_attribute("rake", rake.to_string()))
+        .add_attribute("rake", rake.to_string())
+        .add_attribute("winner_stake", winner_stake.to_string())
+        .add_attribute("loser_stake", loser_stake.to_string()))
 }
 
 fn execute_settle(
@@ -549,25 +659,18 @@ fn execute_settle(
     info: MessageInfo,
     game_id: String,
     winner: String,
-    _multiplier: u32, // Kept for API compatibility; payout now based on actual deposits
+    multiplier: u32, // Recorded only; the payout is based on actual deposits
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
-    // Only admin or game contract can settle
-    let is_authorized = info.sender == config.admin
-        || config
-            .game_contract
-            .as_ref()
-            .map_or(false, |gc| info.sender == *gc);
-    if !is_authorized {
-        return Err(ContractError::Unauthorized {});
-    }
+    ROLES.ensure(deps.storage, &config.admin, &Role::Reporter, &info.sender)?;
 
-    let mut escrow = ESCROWS
-        .may_load(deps.storage, &game_id)?
-        .ok_or(ContractError::EscrowNotFound {
-            game_id: game_id.clone(),
-        })?;
+    let mut escrow =
+        ESCROWS
+            .may_load(deps.storage, &game_id)?
+            .ok_or(ContractError::EscrowNotFound {
+                game_id: game_id.clone(),
+            })?;
 
     // Must be Active
     if escrow.status != EscrowStatus::Active {
@@ -623,8 +726,12 @@ fn execute_settle(
         });
     }
 
+    let (winner_stake, loser_stake) = stakes(&escrow, &winner_addr);
+
     // Update escrow status
     escrow.status = EscrowStatus::Settled;
+    escrow.winner = Some(winner_addr.clone());
+    escrow.multiplier = multiplier.max(1);
     escrow.settled_at = Some(env.block.time.seconds());
     ESCROWS.save(deps.storage, &game_id, &escrow)?;
 
@@ -633,6 +740,11 @@ fn execute_settle(
     TOTAL_RAKE_COLLECTED.save(deps.storage, &(total_rake_collected + rake))?;
     let total_settled = TOTAL_GAMES_SETTLED.load(deps.storage)?;
     TOTAL_GAMES_SETTLED.save(deps.storage, &(total_settled + 1))?;
+    record_stats(deps.storage, env.block.time.seconds(), |stats| {
+        stats.games_settled += 1;
+        stats.volume_settled += total_pot;
+        stats.rake_collected += rake;
+    })?;
 
     Ok(response
         .add_attribute("action", "settle")
@@ -640,7 +752,167 @@ fn execute_settle(
         .add_attribute("winner", winner_addr.to_string())
         .add_attribute("payout", payout.to_string())
         .add_attribute("rake", rake.to_string())
-        .add_attribute("cube_value", escrow.cube_value.to_string()))
+        .add_attribute("cube_value", escrow.cube_value.to_string())
+        .add_attribute("multiplier", escrow.multiplier.to_string())
+        .add_attribute("winner_stake", winner_stake.to_string())
+        .add_attribute("loser_stake", loser_stake.to_string()))
+}
+
+/// (winner's, loser's) cumulative deposits in the escrow
+fn stakes(escrow: &Escrow, winner: &Addr) -> (u128, u128) {
+    if *winner == escrow.player_a {
+        (escrow.player_a_deposited, escrow.player_b_deposited)
+    } else {
+        (escrow.player_b_deposited, escrow.player_a_deposited)
+    }
+}
+
+fn execute_resettle(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    game_id: String,
+    winner: String,
+    multiplier: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    ROLES.ensure(deps.storage, &config.admin, &Role::Reporter, &info.sender)?;
+
+    let mut escrow =
+        ESCROWS
+            .may_load(deps.storage, &game_id)?
+            .ok_or(ContractError::EscrowNotFound {
+                game_id: game_id.clone(),
+            })?;
+
+    // Nothing paid out yet: an ordinary settlement to the corrected winner
+    if escrow.status == EscrowStatus::Active {
+        return execute_settle(deps, env, info, game_id, winner, multiplier.unwrap_or(1));
+    }
+
+    if escrow.status != EscrowStatus::Settled && escrow.status != EscrowStatus::Forfeited {
+        return Err(ContractError::InvalidEscrowStatus {
+            expected: "Active, Settled or Forfeited".to_string(),
+            got: format!("{:?}", escrow.status),
+        });
+    }
+
+    let winner_addr = deps.api.addr_validate(&winner)?;
+    if winner_addr != escrow.player_a && winner_addr != escrow.player_b {
+        return Err(ContractError::InvalidWinner {});
+    }
+    if let Some(multiplier) = multiplier {
+        escrow.multiplier = multiplier.max(1);
+    }
+
+    // The pot was already paid out, so nothing is paid or raked here; what
+    // the winner should have received is booked as owed instead
+    let total_pot = escrow.player_a_deposited + escrow.player_b_deposited;
+    let payout = total_pot - total_pot * config.rake_bps as u128 / 10_000;
+    let (winner_stake, loser_stake) = stakes(&escrow, &winner_addr);
+
+    let response = Response::new()
+        .add_attribute("action", "resettle")
+        .add_attribute("game_id", &game_id)
+        .add_attribute("winner", winner_addr.to_string())
+        .add_attribute("payout", "0")
+        .add_attribute("rake", "0")
+        .add_attribute("cube_value", escrow.cube_value.to_string())
+        .add_attribute("multiplier", escrow.multiplier.to_string())
+        .add_attribute("winner_stake", winner_stake.to_string())
+        .add_attribute("loser_stake", loser_stake.to_string());
+    if escrow.winner.as_ref() == Some(&winner_addr) {
+        ESCROWS.save(deps.storage, &game_id, &escrow)?;
+        return Ok(response.add_attribute("owed", "0"));
+    }
+
+    // The pot went to the wrong player; book what the winner is owed
+    let owed = payout;
+    OWED_BALANCES.update(deps.storage, &winner_addr, |balance| -> StdResult<_> {
+        Ok(balance.unwrap_or_default() + owed)
+    })?;
+
+    let previous_winner = escrow.winner.replace(winner_addr);
+    escrow.owed = owed;
+    escrow.status = EscrowStatus::Resettled;
+    ESCROWS.save(deps.storage, &game_id, &escrow)?;
+
+    Ok(response
+        .add_attribute(
+            "previous_winner",
+            previous_winner.map(|a| a.to_string()).unwrap_or_default(),
+        )
+        .add_attribute("owed", owed.to_string()))
+}
+
+fn execute_pay_owed(
+    deps: DepsMut,
+    info: MessageInfo,
+    game_id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut escrow =
+        ESCROWS
+            .may_load(deps.storage, &game_id)?
+            .ok_or(ContractError::EscrowNotFound {
+                game_id: game_id.clone(),
+            })?;
+
+    if escrow.status != EscrowStatus::Resettled {
+        return Err(ContractError::InvalidEscrowStatus {
+            expected: "Resettled".to_string(),
+            got: format!("{:?}", escrow.status),
+        });
+    }
+    let winner = escrow
+        .winner
+        .clone()
+        .ok_or(ContractError::InvalidWinner {})?;
+
+    let payment = info
+        .funds
+        .iter()
+        .find(|c| c.denom == config.usdc_denom)
+        .ok_or(ContractError::NoPayment {})?
+        .amount
+        .u128();
+    if payment > escrow.owed {
+        return Err(ContractError::OverpaidOwed {
+            owed: escrow.owed,
+            received: payment,
+        });
+    }
+
+    escrow.owed -= payment;
+    if escrow.owed == 0 {
+        escrow.status = EscrowStatus::Settled;
+    }
+    ESCROWS.save(deps.storage, &game_id, &escrow)?;
+
+    let balance = OWED_BALANCES
+        .may_load(deps.storage, &winner)?
+        .unwrap_or_default()
+        .saturating_sub(payment);
+    if balance == 0 {
+        OWED_BALANCES.remove(deps.storage, &winner);
+    } else {
+        OWED_BALANCES.save(deps.storage, &winner, &balance)?;
+    }
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: winner.to_string(),
+            amount: vec![Coin {
+                denom: config.usdc_denom,
+                amount: Uint128::from(payment),
+            }],
+        })
+        .add_attribute("action", "pay_owed")
+        .add_attribute("game_id", game_id)
+        .add_attribute("winner", winner.to_string())
+        .add_attribute("paid", payment.to_string())
+        .add_attribute("owed", escrow.owed.to_string()))
 }
 
 fn execute_cancel(
@@ -651,29 +923,29 @@ fn execute_cancel(
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
-    // Only admin or game contract can cancel
-    let is_authorized = info.sender == config.admin
-        || config
-            .game_contract
-            .as_ref()
-            .map_or(false, |gc| info.sender == *gc);
-    if !is_authorized {
+    // Only a Reporter or Operator can cancel
+    if !ROLES.has(deps.storage, &config.admin, &Role::Reporter, &info.sender)
+        && !ROLES.has(deps.storage, &config.admin, &Role::Operator, &info.sender)
+    {
         return Err(ContractError::Unauthorized {});
     }
 
-    let mut escrow = ESCROWS
-        .may_load(deps.storage, &game_id)?
-        .ok_or(ContractError::EscrowNotFound {
-            game_id: game_id.clone(),
-        })?;
+    let mut escrow =
+        ESCROWS
+            .may_load(deps.storage, &game_id)?
+            .ok_or(ContractError::EscrowNotFound {
+                game_id: game_id.clone(),
+            })?;
 
-    // Can cancel if AwaitingDeposits, Active, or AwaitingDoubleDeposits
+    // Can cancel if AwaitingDeposits, Active, or AwaitingDoubleDeposits, or
+    // to refund whoever has not yet withdrawn from a Withdrawn escrow
     if escrow.status != EscrowStatus::AwaitingDeposits
         && escrow.status != EscrowStatus::Active
         && escrow.status != EscrowStatus::AwaitingDoubleDeposits
+        && escrow.status != EscrowStatus::Withdrawn
     {
         return Err(ContractError::InvalidEscrowStatus {
-            expected: "AwaitingDeposits, Active, or AwaitingDoubleDeposits".to_string(),
+            expected: "AwaitingDeposits, Active, AwaitingDoubleDeposits, or Withdrawn".to_string(),
             got: format!("{:?}", escrow.status),
         });
     }
@@ -717,11 +989,12 @@ fn execute_claim_timeout(
     game_id: String,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let mut escrow = ESCROWS
-        .may_load(deps.storage, &game_id)?
-        .ok_or(ContractError::EscrowNotFound {
-            game_id: game_id.clone(),
-        })?;
+    let mut escrow =
+        ESCROWS
+            .may_load(deps.storage, &game_id)?
+            .ok_or(ContractError::EscrowNotFound {
+                game_id: game_id.clone(),
+            })?;
 
     // Must be in AwaitingDeposits
     if escrow.status != EscrowStatus::AwaitingDeposits {
@@ -782,160 +1055,631 @@ fn execute_claim_timeout(
         .add_attribute("refund_amount", refund_amount.to_string()))
 }
 
-fn execute_update_config(
-    deps: DepsMut,
-    info: MessageInfo,
-    game_contract: Option<String>,
-    rake_bps: Option<u16>,
-    rake_recipient: Option<String>,
-    min_wager: Option<u128>,
-    max_wager: Option<u128>,
-    timeout_seconds: Option<u64>,
-) -> Result<Response, ContractError> {
-    let mut config = CONFIG.load(deps.storage)?;
+/// Apply `update` to the all-time escrow stats and to the bucket for the UTC
+/// day containing `at`
+fn record_stats(
+    storage: &mut dyn Storage,
+    at: u64,
+    update: impl Fn(&mut EscrowStats),
+) -> StdResult<()> {
+    let mut totals = ESCROW_STATS.may_load(storage)?.unwrap_or_default();
+    update(&mut totals);
+    ESCROW_STATS.save(storage, &totals)?;
+
+    let day = at / SECONDS_PER_DAY;
+    let mut daily = DAILY_STATS.may_load(storage, day)?.unwrap_or_default();
+    update(&mut daily);
+    DAILY_STATS.save(storage, day, &daily)
+}
 
-    if info.sender != config.admin {
-        return Err(ContractError::Unauthorized {});
+/// Count a depositing player among the unique players, all-time and for the
+/// day
+fn record_depositor(storage: &mut dyn Storage, now: u64, player: &Addr) -> StdResult<()> {
+    if !DEPOSITORS.has(storage, player) {
+        DEPOSITORS.save(storage, player, &Empty {})?;
+        let mut totals = ESCROW_STATS.may_load(storage)?.unwrap_or_default();
+        totals.unique_players += 1;
+        ESCROW_STATS.save(storage, &totals)?;
     }
 
-    if let Some(addr) = game_contract {
-        config.game_contract = Some(deps.api.addr_validate(&addr)?);
-    }
-    if let Some(bps) = rake_bps {
-        config.rake_bps = bps;
-    }
-    if let Some(addr) = rake_recipient {
-        config.rake_recipient = deps.api.addr_validate(&addr)?;
-    }
-    if let Some(min) = min_wager {
-        config.min_wager = min;
-    }
-    if let Some(max) = max_wager {
-        config.max_wager = max;
-    }
-    if let Some(timeout) = timeout_seconds {
-        config.timeout_seconds = timeout;
+    let day = now / SECONDS_PER_DAY;
+    if !DAILY_DEPOSITORS.has(storage, (day, player)) {
+        DAILY_DEPOSITORS.save(storage, (day, player), &Empty {})?;
+        let mut daily = DAILY_STATS.may_load(storage, day)?.unwrap_or_default();
+        daily.unique_players += 1;
+        DAILY_STATS.save(storage, day, &daily)?;
     }
-
-    CONFIG.save(deps.storage, &config)?;
-
-    Ok(Response::new().add_attribute("action", "update_config"))
+    Ok(())
 }
 
-#[entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+/// The operation a message belongs to, if a Pauser can halt it. Refund
+/// paths (Cancel, ClaimTimeout, Withdraw) are never halted.
+fn pausable_operation(msg: &ExecuteMsg) -> Option<Operation> {
     match msg {
-        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
-        QueryMsg::Escrow { game_id } => to_json_binary(&query_escrow(deps, game_id)?),
-        QueryMsg::Stats {} => to_json_binary(&query_stats(deps)?),
+        ExecuteMsg::CreateEscrow { .. } => Some(Operation::CreateEscrow),
+        ExecuteMsg::Deposit { .. } => Some(Operation::Deposit),
+        ExecuteMsg::OfferDouble { .. }
+        | ExecuteMsg::DoubleDeposit { .. }
+        | ExecuteMsg::RejectDouble { .. } => Some(Operation::Double),
+        ExecuteMsg::Settle { .. }
+        | ExecuteMsg::SettleWithMultiplier { .. }
+        | ExecuteMsg::Resettle { .. }
+        | ExecuteMsg::PayOwed { .. } => Some(Operation::Settlement),
+        _ => None,
     }
 }
 
-fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
-    let config = CONFIG.load(deps.storage)?;
-    Ok(ConfigResponse {
-        admin: config.admin,
-        game_contract: config.game_contract,
-        usdc_denom: config.usdc_denom,
-        rake_bps: config.rake_bps,
-        rake_recipient: config.rake_recipient,
-        min_wager: config.min_wager,
-        max_wager: config.max_wager,
-        timeout_seconds: config.timeout_seconds,
-    })
+fn ensure_not_paused(storage: &dyn Storage, operation: &Operation) -> Result<(), ContractError> {
+    let key = format!("{:?}", operation);
+    if PAUSED.has(storage, key.clone()) || is_withdraw_only(storage)? {
+        return Err(ContractError::Paused { operation: key });
+    }
+    Ok(())
 }
 
-fn query_escrow(deps: Deps, game_id: String) -> StdResult<EscrowResponse> {
-    let escrow = ESCROWS.load(deps.storage, &game_id)?;
-    Ok(EscrowResponse {
-        game_id: escrow.game_id,
-        player_a: escrow.player_a,
-        player_b: escrow.player_b,
-        wager_amount: escrow.wager_amount,
-        player_a_deposited: escrow.player_a_deposited,
-        player_b_deposited: escrow.player_b_deposited,
-        status: format!("{:?}", escrow.status),
-        created_at: escrow.created_at,
-        settled_at: escrow.settled_at,
-        cube_value: escrow.cube_value,
-        pending_double: escrow.pending_double.map(|pd| PendingDoubleResponse {
-            doubler: pd.doubler,
-            responder: pd.responder,
-            new_cube_value: pd.new_cube_value,
-            additional_deposit: pd.additional_deposit,
-            doubler_deposited: pd.doubler_deposited,
-            responder_deposited: pd.responder_deposited,
-        }),
-    })
+fn is_withdraw_only(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(WITHDRAW_ONLY.may_load(storage)?.unwrap_or_default())
 }
 
-fn query_stats(deps: Deps) -> StdResult<StatsResponse> {
-    Ok(StatsResponse {
-        total_rake_collected: TOTAL_RAKE_COLLECTED.load(deps.storage)?,
-        total_games_settled: TOTAL_GAMES_SETTLED.load(deps.storage)?,
-    })
+fn execute_set_paused(
+    deps: DepsMut,
+    info: MessageInfo,
+    operations: Vec<Operation>,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ROLES.ensure(deps.storage, &config.admin, &Role::Pauser, &info.sender)?;
+
+    let mut names = Vec::with_capacity(operations.len());
+    for operation in &operations {
+        let key = format!("{:?}", operation);
+        if paused {
+            PAUSED.save(deps.storage, key.clone(), &Empty {})?;
+        } else {
+            PAUSED.remove(deps.storage, key.clone());
+        }
+        names.push(key);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", if paused { "pause" } else { "unpause" })
+        .add_attribute("operations", names.join(",")))
 }
 
-#[entry_point]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    // Migration from bool deposits to u128 deposits:
-    // On-chain migration would iterate ESCROWS and convert:
-    //   player_X_deposited: true  → wager_amount
-    //   player_X_deposited: false → 0
-    //   cube_value: 1 (default)
-    //   pending_double: None
-    // For fresh deploys, this is a no-op.
-    Ok(Response::new().add_attribute("action", "migrate"))
+fn execute_set_withdraw_only(
+    deps: DepsMut,
+    info: MessageInfo,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ROLES.ensure(deps.storage, &config.admin, &Role::Pauser, &info.sender)?;
+
+    WITHDRAW_ONLY.save(deps.storage, &enabled)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_withdraw_only")
+        .add_attribute("enabled", enabled.to_string()))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use cosmwasm_std::testing::{
-        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
-    };
-    use cosmwasm_std::{coins, OwnedDeps};
+fn execute_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    game_id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut escrow =
+        ESCROWS
+            .may_load(deps.storage, &game_id)?
+            .ok_or(ContractError::EscrowNotFound {
+                game_id: game_id.clone(),
+            })?;
 
-    fn addr(name: &str) -> String {
-        MockApi::default().addr_make(name).to_string()
+    // Once one player has withdrawn the escrow can never settle, so the
+    // other may follow even after withdraw-only mode ends
+    if escrow.status != EscrowStatus::Withdrawn && !is_withdraw_only(deps.storage)? {
+        return Err(ContractError::NotWithdrawOnly {});
     }
 
-    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
-        let mut deps = mock_dependencies();
-        let msg = InstantiateMsg {
-            usdc_denom: "uusdc".to_string(),
-            rake_bps: 500, // 5%
-            rake_recipient: addr("treasury"),
-            min_wager: 1_000_000,   // 1 USDC
-            max_wager: 1_000_000_000, // 1000 USDC
-            timeout_seconds: 300,
-            game_contract: None,
-        };
-        let info = mock_info(&addr("admin"), &[]);
-        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
-        deps
+    // Funds are still held for any escrow that has not paid out or refunded
+    if escrow.status != EscrowStatus::AwaitingDeposits
+        && escrow.status != EscrowStatus::Active
+        && escrow.status != EscrowStatus::AwaitingDoubleDeposits
+        && escrow.status != EscrowStatus::Withdrawn
+    {
+        return Err(ContractError::InvalidEscrowStatus {
+            expected: "AwaitingDeposits, Active, AwaitingDoubleDeposits, or Withdrawn".to_string(),
+            got: format!("{:?}", escrow.status),
+        });
     }
 
-    fn create_and_deposit_both(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
-        let create_msg = ExecuteMsg::CreateEscrow {
-            game_id: "game1".to_string(),
-            player_a: addr("player_a"),
-            player_b: addr("player_b"),
-            wager_amount: 5_000_000u128,
-        };
-        execute(
-            deps.as_mut(),
-            mock_env(),
-            mock_info(&addr("admin"), &[]),
-            create_msg,
-        )
+    // Withdraw the caller's cumulative deposits, including any double
+    let deposited = if info.sender == escrow.player_a {
+        &mut escrow.player_a_deposited
+    } else if info.sender == escrow.player_b {
+        &mut escrow.player_b_deposited
+    } else {
+        return Err(ContractError::NotAPlayer {});
+    };
+    let refund_amount = std::mem::take(deposited);
+    if refund_amount == 0 {
+        return Err(ContractError::NothingToWithdraw {});
+    }
+
+    // Tell the game the first time, so it closes the game rather than
+    // settling an escrow that can no longer pay out
+    let mut response = Response::new();
+    if escrow.status != EscrowStatus::Withdrawn {
+        if let Some(game_hook) = &config.game_hook {
+            let msg = WasmMsg::Execute {
+                contract_addr: game_hook.to_string(),
+                msg: to_json_binary(&GameHookMsg::EscrowWithdrawn {
+                    game_id: game_id.clone(),
+                })?,
+                funds: vec![],
+            };
+            response = response.add_submessage(
+                SubMsg::reply_on_error(msg, GAME_HOOK_REPLY_ID)
+                    .with_payload(game_id.as_bytes().to_vec()),
+            );
+        }
+    }
+
+    escrow.status = EscrowStatus::Withdrawn;
+    escrow.settled_at = Some(env.block.time.seconds());
+    escrow.pending_double = None;
+    ESCROWS.save(deps.storage, &game_id, &escrow)?;
+
+    Ok(response
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![Coin {
+                denom: config.usdc_denom,
+                amount: Uint128::from(refund_amount),
+            }],
+        })
+        .add_attribute("action", "withdraw")
+        .add_attribute("game_id", game_id)
+        .add_attribute("player", info.sender.to_string())
+        .add_attribute("amount", refund_amount.to_string()))
+}
+
+fn execute_propose_new_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+    expires_in: Option<u64>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    let expires_in = expires_in.unwrap_or(DEFAULT_ADMIN_TRANSFER_EXPIRY);
+    if expires_in == 0 || expires_in > MAX_ADMIN_TRANSFER_EXPIRY {
+        return Err(ContractError::InvalidAdminTransferExpiry {
+            seconds: expires_in,
+            max: MAX_ADMIN_TRANSFER_EXPIRY,
+        });
+    }
+
+    let pending = PendingAdmin {
+        address: deps.api.addr_validate(&address)?,
+        expires_at: env.block.time.seconds() + expires_in,
+    };
+    PENDING_ADMIN.save(deps.storage, &pending)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose_new_admin")
+        .add_attribute("pending_admin", pending.address)
+        .add_attribute("expires_at", pending.expires_at.to_string()))
+}
+
+fn execute_accept_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let pending = PENDING_ADMIN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingAdmin {})?;
+    if info.sender != pending.address {
+        return Err(ContractError::Unauthorized {});
+    }
+    if env.block.time.seconds() >= pending.expires_at {
+        return Err(ContractError::AdminTransferExpired {
+            expires_at: pending.expires_at,
+        });
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+    let previous = std::mem::replace(&mut config.admin, pending.address);
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_admin")
+        .add_attribute("previous_admin", previous)
+        .add_attribute("admin", config.admin))
+}
+
+fn execute_cancel_admin_transfer(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    if !PENDING_ADMIN.exists(deps.storage) {
+        return Err(ContractError::NoPendingAdmin {});
+    }
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new().add_attribute("action", "cancel_admin_transfer"))
+}
+
+fn execute_renounce_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Grants could never be revoked afterwards, so none may outlive the admin
+    if !ROLES.is_empty(deps.storage) {
+        return Err(ContractError::RolesRemain {});
+    }
+
+    // The contract never sends messages to itself, so this leaves no one
+    // able to act as admin
+    config.admin = env.contract.address;
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "renounce_admin")
+        .add_attribute("previous_admin", info.sender))
+}
+
+fn execute_grant_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ROLES.execute_grant(deps, &config.admin, &info.sender, &role, &address)?)
+}
+
+fn execute_revoke_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ROLES.execute_revoke(deps, &config.admin, &info.sender, &role, &address)?)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    rake_bps: Option<u16>,
+    rake_recipient: Option<String>,
+    min_wager: Option<u128>,
+    max_wager: Option<u128>,
+    timeout_seconds: Option<u64>,
+    game_hook: Option<String>,
+    game_contract: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    ROLES.ensure(
+        deps.storage,
+        &config.admin,
+        &Role::ConfigAdmin,
+        &info.sender,
+    )?;
+
+    // The pre-roles field replaced the game contract, so it replaces every
+    // Reporter; rotating a key through it must not leave the old one
+    // authorized. Only the admin can change roles.
+    let mut response = Response::new().add_attribute("action", "update_config");
+    if let Some(addr) = game_contract {
+        if info.sender != config.admin {
+            return Err(ContractError::Unauthorized {});
+        }
+        let replaced = ROLES.replace(
+            deps.storage,
+            &Role::Reporter,
+            &deps.api.addr_validate(&addr)?,
+        )?;
+        for addr in replaced {
+            response = response.add_attribute("revoked", format!("{:?}:{addr}", Role::Reporter));
+        }
+    }
+
+    if let Some(bps) = rake_bps {
+        config.rake_bps = bps;
+    }
+    if let Some(addr) = rake_recipient {
+        config.rake_recipient = deps.api.addr_validate(&addr)?;
+    }
+    if let Some(min) = min_wager {
+        config.min_wager = min;
+    }
+    if let Some(max) = max_wager {
+        config.max_wager = max;
+    }
+    if let Some(timeout) = timeout_seconds {
+        config.timeout_seconds = timeout;
+    }
+    if let Some(addr) = game_hook {
+        config.game_hook = Some(deps.api.addr_validate(&addr)?);
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(response)
+}
+
+#[entry_point]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::Roles {} => to_json_binary(&query_roles(deps)?),
+        QueryMsg::PendingAdmin {} => to_json_binary(&query_pending_admin(deps)?),
+        QueryMsg::PauseStatus {} => to_json_binary(&query_pause_status(deps)?),
+        QueryMsg::Escrow { game_id } => to_json_binary(&query_escrow(deps, game_id)?),
+        QueryMsg::FindEscrow { game_id } => to_json_binary(&query_find_escrow(deps, game_id)?),
+        QueryMsg::Owed { address } => to_json_binary(&query_owed(deps, address)?),
+        QueryMsg::Stats {} => to_json_binary(&query_stats(deps)?),
+        QueryMsg::DailyStats {
+            start_time,
+            end_time,
+            limit,
+        } => to_json_binary(&query_daily_stats(deps, start_time, end_time, limit)?),
+    }
+}
+
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        admin: config.admin,
+        usdc_denom: config.usdc_denom,
+        rake_bps: config.rake_bps,
+        rake_recipient: config.rake_recipient,
+        min_wager: config.min_wager,
+        max_wager: config.max_wager,
+        timeout_seconds: config.timeout_seconds,
+        game_hook: config.game_hook,
+        game_contract: ROLES
+            .members(deps.storage, &Role::Reporter)?
+            .into_iter()
+            .next(),
+    })
+}
+
+fn query_pause_status(deps: Deps) -> StdResult<PauseStatusResponse> {
+    let paused = [
+        Operation::CreateEscrow,
+        Operation::Deposit,
+        Operation::Double,
+        Operation::Settlement,
+    ]
+    .into_iter()
+    .filter(|operation| PAUSED.has(deps.storage, format!("{:?}", operation)))
+    .collect();
+    Ok(PauseStatusResponse {
+        paused,
+        withdraw_only: is_withdraw_only(deps.storage)?,
+    })
+}
+
+fn query_pending_admin(deps: Deps) -> StdResult<PendingAdminResponse> {
+    Ok(PendingAdminResponse {
+        admin: CONFIG.load(deps.storage)?.admin,
+        pending: PENDING_ADMIN.may_load(deps.storage)?,
+    })
+}
+
+fn query_roles(deps: Deps) -> StdResult<RolesResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let roles = [
+        Role::ConfigAdmin,
+        Role::Operator,
+        Role::Reporter,
+        Role::Pauser,
+    ]
+    .into_iter()
+    .map(|role| {
+        let members = ROLES.members(deps.storage, &role)?;
+        Ok(RoleMembers { role, members })
+    })
+    .collect::<StdResult<Vec<_>>>()?;
+    Ok(RolesResponse {
+        admin: config.admin,
+        roles,
+    })
+}
+
+fn query_escrow(deps: Deps, game_id: String) -> StdResult<EscrowResponse> {
+    escrow_response(deps, ESCROWS.load(deps.storage, &game_id)?)
+}
+
+fn query_find_escrow(deps: Deps, game_id: String) -> StdResult<Option<EscrowResponse>> {
+    ESCROWS
+        .may_load(deps.storage, &game_id)?
+        .map(|escrow| escrow_response(deps, escrow))
+        .transpose()
+}
+
+fn escrow_response(deps: Deps, escrow: Escrow) -> StdResult<EscrowResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let total_pot = escrow.player_a_deposited + escrow.player_b_deposited;
+    let rake = matches!(
+        escrow.status,
+        EscrowStatus::AwaitingDeposits
+            | EscrowStatus::Active
+            | EscrowStatus::AwaitingDoubleDeposits
+    )
+    .then(|| total_pot * config.rake_bps as u128 / 10_000);
+    Ok(EscrowResponse {
+        game_id: escrow.game_id,
+        player_a: escrow.player_a,
+        player_b: escrow.player_b,
+        wager_amount: escrow.wager_amount,
+        player_a_deposited: escrow.player_a_deposited,
+        player_b_deposited: escrow.player_b_deposited,
+        status: format!("{:?}", escrow.status),
+        created_at: escrow.created_at,
+        settled_at: escrow.settled_at,
+        cube_value: escrow.cube_value,
+        pending_double: escrow.pending_double.map(|pd| PendingDoubleResponse {
+            doubler: pd.doubler,
+            responder: pd.responder,
+            new_cube_value: pd.new_cube_value,
+            additional_deposit: pd.additional_deposit,
+            doubler_deposited: pd.doubler_deposited,
+            responder_deposited: pd.responder_deposited,
+        }),
+        winner: escrow.winner,
+        multiplier: escrow.multiplier,
+        owed: escrow.owed,
+        projected_payout: rake.map(|rake| total_pot - rake),
+        projected_rake: rake,
+    })
+}
+
+fn query_owed(deps: Deps, address: String) -> StdResult<OwedResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    Ok(OwedResponse {
+        owed: OWED_BALANCES
+            .may_load(deps.storage, &address)?
+            .unwrap_or_default(),
+        address,
+    })
+}
+
+fn query_stats(deps: Deps) -> StdResult<StatsResponse> {
+    Ok(StatsResponse {
+        total_rake_collected: TOTAL_RAKE_COLLECTED.load(deps.storage)?,
+        total_games_settled: TOTAL_GAMES_SETTLED.load(deps.storage)?,
+        totals: ESCROW_STATS.may_load(deps.storage)?.unwrap_or_default(),
+    })
+}
+
+fn query_daily_stats(
+    deps: Deps,
+    start_time: Option<u64>,
+    end_time: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<DailyStatsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = start_time.map(|t| Bound::inclusive(t / SECONDS_PER_DAY));
+    let max = end_time.map(|t| Bound::inclusive(t / SECONDS_PER_DAY));
+
+    let days = DAILY_STATS
+        .range(deps.storage, min, max, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(day, stats)| DailyStats {
+                day_start: day * SECONDS_PER_DAY,
+                stats,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(DailyStatsResponse { days })
+}
+
+#[entry_point]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        // The deposit stands either way; the game can still be started
+        // with StartGame
+        GAME_HOOK_REPLY_ID => Ok(Response::new()
+            .add_attribute("action", "game_hook_failed")
+            .add_attribute("game_id", String::from_utf8_lossy(&msg.payload))),
+        id => Err(StdError::generic_err(format!("unknown reply id {id}")).into()),
+    }
+}
+
+#[entry_point]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Migration from bool deposits to u128 deposits:
+    // On-chain migration would iterate ESCROWS and convert:
+    //   player_X_deposited: true  → wager_amount
+    //   player_X_deposited: false → 0
+    //   cube_value: 1 (default)
+    //   pending_double: None
+    // For fresh deploys, this is a no-op.
+
+    // The single game contract address becomes a Reporter
+    let mut config = CONFIG.load(deps.storage)?;
+    if let Some(addr) = config.game_contract.take() {
+        ROLES.grant(deps.storage, &Role::Reporter, &addr)?;
+        CONFIG.save(deps.storage, &config)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "migrate"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{
+        message_info, mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{coins, Addr, CosmosMsg, OwnedDeps};
+
+    fn addr(name: &str) -> String {
+        sender(name).to_string()
+    }
+
+    fn sender(name: &str) -> Addr {
+        MockApi::default().addr_make(name)
+    }
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            usdc_denom: "uusdc".to_string(),
+            rake_bps: 500, // 5%
+            rake_recipient: addr("treasury"),
+            min_wager: 1_000_000,     // 1 USDC
+            max_wager: 1_000_000_000, // 1000 USDC
+            timeout_seconds: 300,
+            game_contract: None,
+        };
+        let info = message_info(&sender("admin"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        deps
+    }
+
+    fn create_and_deposit_both(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let create_msg = ExecuteMsg::CreateEscrow {
+            game_id: "game1".to_string(),
+            player_a: addr("player_a"),
+            player_b: addr("player_b"),
+            wager_amount: 5_000_000u128,
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&sender("admin"), &[]),
+            create_msg,
+        )
         .unwrap();
 
         // Player A deposits
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("player_a"), &coins(5_000_000, "uusdc")),
+            message_info(&sender("player_a"), &coins(5_000_000, "uusdc")),
             ExecuteMsg::Deposit {
                 game_id: "game1".to_string(),
             },
@@ -946,7 +1690,7 @@ mod tests {
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("player_b"), &coins(5_000_000, "uusdc")),
+            message_info(&sender("player_b"), &coins(5_000_000, "uusdc")),
             ExecuteMsg::Deposit {
                 game_id: "game1".to_string(),
             },
@@ -963,7 +1707,7 @@ mod tests {
             player_b: addr("player_b"),
             wager_amount: 5_000_000u128,
         };
-        let info = mock_info(&addr("admin"), &[]);
+        let info = message_info(&sender("admin"), &[]);
         let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
         assert_eq!(res.attributes.len(), 3);
 
@@ -973,6 +1717,14 @@ mod tests {
         assert_eq!(escrow.player_a_deposited, 0);
         assert_eq!(escrow.player_b_deposited, 0);
         assert!(escrow.pending_double.is_none());
+
+        // FindEscrow answers null, not an error, for an escrow never created
+        let found = query_find_escrow(deps.as_ref(), "game1".to_string()).unwrap();
+        assert_eq!(found.unwrap().game_id, "game1");
+        assert!(query_find_escrow(deps.as_ref(), "game2".to_string())
+            .unwrap()
+            .is_none());
+        assert!(query_escrow(deps.as_ref(), "game2".to_string()).is_err());
     }
 
     #[test]
@@ -987,7 +1739,7 @@ mod tests {
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("admin"), &[]),
+            message_info(&sender("admin"), &[]),
             create_msg,
         )
         .unwrap();
@@ -995,7 +1747,7 @@ mod tests {
         let deposit_msg = ExecuteMsg::Deposit {
             game_id: "game1".to_string(),
         };
-        let info = mock_info(&addr("player_a"), &coins(5_000_000, "uusdc"));
+        let info = message_info(&sender("player_a"), &coins(5_000_000, "uusdc"));
         let res = execute(deps.as_mut(), mock_env(), info, deposit_msg);
         assert!(res.is_ok());
 
@@ -1017,56 +1769,255 @@ mod tests {
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("admin"), &[]),
-            create_msg,
+            message_info(&sender("admin"), &[]),
+            create_msg,
+        )
+        .unwrap();
+
+        let deposit_msg = ExecuteMsg::Deposit {
+            game_id: "game1".to_string(),
+        };
+        let info = message_info(&sender("player_a"), &coins(10_000_000, "uusdc"));
+        let res = execute(deps.as_mut(), mock_env(), info, deposit_msg);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_non_player_deposit_fails() {
+        let mut deps = setup();
+        let create_msg = ExecuteMsg::CreateEscrow {
+            game_id: "game1".to_string(),
+            player_a: addr("player_a"),
+            player_b: addr("player_b"),
+            wager_amount: 5_000_000u128,
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&sender("admin"), &[]),
+            create_msg,
+        )
+        .unwrap();
+
+        let deposit_msg = ExecuteMsg::Deposit {
+            game_id: "game1".to_string(),
+        };
+        let info = message_info(&sender("random_person"), &coins(5_000_000, "uusdc"));
+        let res = execute(deps.as_mut(), mock_env(), info, deposit_msg);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_unauthorized_create_fails() {
+        let mut deps = setup();
+        let msg = ExecuteMsg::CreateEscrow {
+            game_id: "game1".to_string(),
+            player_a: addr("player_a"),
+            player_b: addr("player_b"),
+            wager_amount: 5_000_000u128,
+        };
+        let info = message_info(&sender("not_admin"), &[]);
+        let res = execute(deps.as_mut(), mock_env(), info, msg);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_roles_grant_and_revoke() {
+        let mut deps = setup();
+        let grant = ExecuteMsg::GrantRole {
+            role: Role::Reporter,
+            address: addr("game"),
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&sender("game"), &[]),
+            grant.clone(),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&sender("admin"), &[]),
+            grant,
+        )
+        .unwrap();
+
+        // A Reporter can create escrows but not change the config
+        let create = ExecuteMsg::CreateEscrow {
+            game_id: "game1".to_string(),
+            player_a: addr("player_a"),
+            player_b: addr("player_b"),
+            wager_amount: 5_000_000u128,
+        };
+        let game = message_info(&sender("game"), &[]);
+        execute(deps.as_mut(), mock_env(), game.clone(), create).unwrap();
+        let update = ExecuteMsg::UpdateConfig {
+            rake_bps: Some(100),
+            rake_recipient: None,
+            min_wager: None,
+            max_wager: None,
+            timeout_seconds: None,
+            game_hook: None,
+            game_contract: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), game.clone(), update);
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+
+        let roles = query_roles(deps.as_ref()).unwrap();
+        assert_eq!(roles.admin, sender("admin"));
+        let reporters = roles.roles.iter().find(|r| r.role == Role::Reporter);
+        assert_eq!(reporters.unwrap().members, vec![sender("game")]);
+
+        // The pre-roles game_contract field replaces the Reporter, so the
+        // previous game contract is no longer one
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&sender("admin"), &[]),
+            ExecuteMsg::UpdateConfig {
+                rake_bps: None,
+                rake_recipient: None,
+                min_wager: None,
+                max_wager: None,
+                timeout_seconds: None,
+                game_hook: None,
+                game_contract: Some(addr("game-2")),
+            },
+        )
+        .unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|a| a.key == "revoked" && a.value == format!("Reporter:{}", sender("game"))));
+        assert_eq!(
+            query_config(deps.as_ref()).unwrap().game_contract,
+            Some(sender("game-2"))
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&sender("admin"), &[]),
+            ExecuteMsg::RevokeRole {
+                role: Role::Reporter,
+                address: addr("game"),
+            },
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            game,
+            ExecuteMsg::Cancel {
+                game_id: "game1".to_string(),
+            },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+    }
+
+    #[test]
+    fn test_admin_transfer() {
+        let mut deps = setup();
+        let propose = ExecuteMsg::ProposeNewAdmin {
+            address: addr("multisig"),
+            expires_in: None,
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&sender("admin"), &[]),
+            propose,
         )
         .unwrap();
 
-        let deposit_msg = ExecuteMsg::Deposit {
-            game_id: "game1".to_string(),
-        };
-        let info = mock_info(&addr("player_a"), &coins(10_000_000, "uusdc"));
-        let res = execute(deps.as_mut(), mock_env(), info, deposit_msg);
-        assert!(res.is_err());
-    }
-
-    #[test]
-    fn test_non_player_deposit_fails() {
-        let mut deps = setup();
-        let create_msg = ExecuteMsg::CreateEscrow {
-            game_id: "game1".to_string(),
-            player_a: addr("player_a"),
-            player_b: addr("player_b"),
-            wager_amount: 5_000_000u128,
-        };
+        // Nothing changes until the new admin accepts
+        assert_eq!(CONFIG.load(&deps.storage).unwrap().admin, sender("admin"));
+        let mut late = mock_env();
+        late.block.time = late.block.time.plus_seconds(DEFAULT_ADMIN_TRANSFER_EXPIRY);
+        let res = execute(
+            deps.as_mut(),
+            late,
+            message_info(&sender("multisig"), &[]),
+            ExecuteMsg::AcceptAdmin {},
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::AdminTransferExpired { .. }
+        ));
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("admin"), &[]),
-            create_msg,
+            message_info(&sender("multisig"), &[]),
+            ExecuteMsg::AcceptAdmin {},
         )
         .unwrap();
+        assert_eq!(
+            CONFIG.load(&deps.storage).unwrap().admin,
+            sender("multisig")
+        );
+        assert!(PENDING_ADMIN.may_load(&deps.storage).unwrap().is_none());
 
-        let deposit_msg = ExecuteMsg::Deposit {
-            game_id: "game1".to_string(),
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&sender("admin"), &[]),
+            ExecuteMsg::RenounceAdmin {},
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+
+        // A cancelled transfer can no longer be accepted
+        let run = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                   who: &str,
+                   msg: ExecuteMsg| {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&sender(who), &[]),
+                msg,
+            )
         };
-        let info = mock_info(&addr("random_person"), &coins(5_000_000, "uusdc"));
-        let res = execute(deps.as_mut(), mock_env(), info, deposit_msg);
-        assert!(res.is_err());
-    }
+        run(
+            &mut deps,
+            "multisig",
+            ExecuteMsg::ProposeNewAdmin {
+                address: addr("admin"),
+                expires_in: None,
+            },
+        )
+        .unwrap();
+        run(&mut deps, "multisig", ExecuteMsg::CancelAdminTransfer {}).unwrap();
+        assert!(PENDING_ADMIN.may_load(&deps.storage).unwrap().is_none());
+        assert_eq!(
+            run(&mut deps, "admin", ExecuteMsg::AcceptAdmin {}).unwrap_err(),
+            ContractError::NoPendingAdmin {}
+        );
 
-    #[test]
-    fn test_unauthorized_create_fails() {
-        let mut deps = setup();
-        let msg = ExecuteMsg::CreateEscrow {
-            game_id: "game1".to_string(),
-            player_a: addr("player_a"),
-            player_b: addr("player_b"),
-            wager_amount: 5_000_000u128,
+        // Renouncing needs every grant revoked first, since nothing could
+        // revoke them afterwards
+        let pauser = |grant: bool| {
+            let (role, address) = (Role::Pauser, addr("ops"));
+            if grant {
+                ExecuteMsg::GrantRole { role, address }
+            } else {
+                ExecuteMsg::RevokeRole { role, address }
+            }
         };
-        let info = mock_info(&addr("not_admin"), &[]);
-        let res = execute(deps.as_mut(), mock_env(), info, msg);
-        assert!(res.is_err());
+        run(&mut deps, "multisig", pauser(true)).unwrap();
+        assert_eq!(
+            run(&mut deps, "multisig", ExecuteMsg::RenounceAdmin {}).unwrap_err(),
+            ContractError::RolesRemain {}
+        );
+        run(&mut deps, "multisig", pauser(false)).unwrap();
+        run(&mut deps, "multisig", ExecuteMsg::RenounceAdmin {}).unwrap();
+        assert_eq!(
+            CONFIG.load(&deps.storage).unwrap().admin,
+            mock_env().contract.address
+        );
+        assert_eq!(
+            run(&mut deps, "multisig", pauser(true)).unwrap_err(),
+            ContractError::Unauthorized {}
+        );
     }
 
     #[test]
@@ -1088,7 +2039,7 @@ mod tests {
         let res = execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("admin"), &[]),
+            message_info(&sender("admin"), &[]),
             ExecuteMsg::OfferDouble {
                 game_id: "game1".to_string(),
                 doubler: addr("player_a"),
@@ -1119,7 +2070,7 @@ mod tests {
         let res = execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("admin"), &[]),
+            message_info(&sender("admin"), &[]),
             ExecuteMsg::OfferDouble {
                 game_id: "game1".to_string(),
                 doubler: addr("player_a"),
@@ -1138,7 +2089,7 @@ mod tests {
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("admin"), &[]),
+            message_info(&sender("admin"), &[]),
             ExecuteMsg::OfferDouble {
                 game_id: "game1".to_string(),
                 doubler: addr("player_a"),
@@ -1151,7 +2102,7 @@ mod tests {
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("player_a"), &coins(5_000_000, "uusdc")),
+            message_info(&sender("player_a"), &coins(5_000_000, "uusdc")),
             ExecuteMsg::DoubleDeposit {
                 game_id: "game1".to_string(),
             },
@@ -1167,7 +2118,7 @@ mod tests {
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("player_b"), &coins(5_000_000, "uusdc")),
+            message_info(&sender("player_b"), &coins(5_000_000, "uusdc")),
             ExecuteMsg::DoubleDeposit {
                 game_id: "game1".to_string(),
             },
@@ -1191,7 +2142,7 @@ mod tests {
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("admin"), &[]),
+            message_info(&sender("admin"), &[]),
             ExecuteMsg::OfferDouble {
                 game_id: "game1".to_string(),
                 doubler: addr("player_a"),
@@ -1204,7 +2155,7 @@ mod tests {
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("player_a"), &coins(5_000_000, "uusdc")),
+            message_info(&sender("player_a"), &coins(5_000_000, "uusdc")),
             ExecuteMsg::DoubleDeposit {
                 game_id: "game1".to_string(),
             },
@@ -1215,7 +2166,7 @@ mod tests {
         let res = execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("player_a"), &coins(5_000_000, "uusdc")),
+            message_info(&sender("player_a"), &coins(5_000_000, "uusdc")),
             ExecuteMsg::DoubleDeposit {
                 game_id: "game1".to_string(),
             },
@@ -1232,7 +2183,7 @@ mod tests {
         let res = execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("admin"), &[]),
+            message_info(&sender("admin"), &[]),
             ExecuteMsg::RejectDouble {
                 game_id: "game1".to_string(),
                 rejecter: addr("player_b"),
@@ -1259,7 +2210,7 @@ mod tests {
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("admin"), &[]),
+            message_info(&sender("admin"), &[]),
             ExecuteMsg::OfferDouble {
                 game_id: "game1".to_string(),
                 doubler: addr("player_a"),
@@ -1272,7 +2223,7 @@ mod tests {
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("player_a"), &coins(5_000_000, "uusdc")),
+            message_info(&sender("player_a"), &coins(5_000_000, "uusdc")),
             ExecuteMsg::DoubleDeposit {
                 game_id: "game1".to_string(),
             },
@@ -1283,7 +2234,7 @@ mod tests {
         let res = execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("admin"), &[]),
+            message_info(&sender("admin"), &[]),
             ExecuteMsg::RejectDouble {
                 game_id: "game1".to_string(),
                 rejecter: addr("player_b"),
@@ -1307,7 +2258,7 @@ mod tests {
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("admin"), &[]),
+            message_info(&sender("admin"), &[]),
             ExecuteMsg::OfferDouble {
                 game_id: "game1".to_string(),
                 doubler: addr("player_a"),
@@ -1318,7 +2269,7 @@ mod tests {
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("player_a"), &coins(5_000_000, "uusdc")),
+            message_info(&sender("player_a"), &coins(5_000_000, "uusdc")),
             ExecuteMsg::DoubleDeposit {
                 game_id: "game1".to_string(),
             },
@@ -1327,7 +2278,7 @@ mod tests {
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("player_b"), &coins(5_000_000, "uusdc")),
+            message_info(&sender("player_b"), &coins(5_000_000, "uusdc")),
             ExecuteMsg::DoubleDeposit {
                 game_id: "game1".to_string(),
             },
@@ -1340,11 +2291,17 @@ mod tests {
             .bank
             .update_balance(env.contract.address.to_string(), coins(20_000_000, "uusdc"));
 
+        let escrow = query_escrow(deps.as_ref(), "game1".to_string()).unwrap();
+        assert_eq!(
+            (escrow.projected_payout, escrow.projected_rake),
+            (Some(19_000_000), Some(1_000_000))
+        );
+
         // Settle — pot is 20M, rake 5% = 1M, payout = 19M
         let res = execute(
             deps.as_mut(),
             env,
-            mock_info(&addr("admin"), &[]),
+            message_info(&sender("admin"), &[]),
             ExecuteMsg::Settle {
                 game_id: "game1".to_string(),
                 winner: addr("player_a"),
@@ -1360,10 +2317,36 @@ mod tests {
             .attributes
             .iter()
             .any(|a| a.key == "rake" && a.value == "1000000"));
+        assert!(res
+            .attributes
+            .iter()
+            .any(|a| a.key == "winner_stake" && a.value == "10000000"));
         assert!(res
             .attributes
             .iter()
             .any(|a| a.key == "cube_value" && a.value == "2"));
+
+        // A settled escrow has nothing left to project
+        let escrow = query_escrow(deps.as_ref(), "game1".to_string()).unwrap();
+        assert_eq!(
+            (escrow.projected_payout, escrow.projected_rake),
+            (None, None)
+        );
+
+        // Everything happened on the same day, so the bucket matches the totals
+        let totals = query_stats(deps.as_ref()).unwrap().totals;
+        assert_eq!(totals.escrows_created, 1);
+        assert_eq!(totals.volume_deposited, 20_000_000);
+        assert_eq!(totals.volume_settled, 20_000_000);
+        assert_eq!(totals.rake_collected, 1_000_000);
+        assert_eq!(totals.doubles_offered, 1);
+        assert_eq!(totals.doubles_accepted, 1);
+        assert_eq!(totals.unique_players, 2);
+        let days = query_daily_stats(deps.as_ref(), None, None, None)
+            .unwrap()
+            .days;
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].stats, totals);
     }
 
     #[test]
@@ -1381,7 +2364,7 @@ mod tests {
         let res = execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("admin"), &[]),
+            message_info(&sender("admin"), &[]),
             ExecuteMsg::OfferDouble {
                 game_id: "game1".to_string(),
                 doubler: addr("player_a"),
@@ -1400,7 +2383,7 @@ mod tests {
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("admin"), &[]),
+            message_info(&sender("admin"), &[]),
             ExecuteMsg::OfferDouble {
                 game_id: "game1".to_string(),
                 doubler: addr("player_a"),
@@ -1411,7 +2394,7 @@ mod tests {
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("player_a"), &coins(5_000_000, "uusdc")),
+            message_info(&sender("player_a"), &coins(5_000_000, "uusdc")),
             ExecuteMsg::DoubleDeposit {
                 game_id: "game1".to_string(),
             },
@@ -1420,7 +2403,7 @@ mod tests {
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("player_b"), &coins(5_000_000, "uusdc")),
+            message_info(&sender("player_b"), &coins(5_000_000, "uusdc")),
             ExecuteMsg::DoubleDeposit {
                 game_id: "game1".to_string(),
             },
@@ -1433,7 +2416,7 @@ mod tests {
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("admin"), &[]),
+            message_info(&sender("admin"), &[]),
             ExecuteMsg::OfferDouble {
                 game_id: "game1".to_string(),
                 doubler: addr("player_b"),
@@ -1444,7 +2427,7 @@ mod tests {
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("player_b"), &coins(10_000_000, "uusdc")),
+            message_info(&sender("player_b"), &coins(10_000_000, "uusdc")),
             ExecuteMsg::DoubleDeposit {
                 game_id: "game1".to_string(),
             },
@@ -1453,7 +2436,7 @@ mod tests {
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("player_a"), &coins(10_000_000, "uusdc")),
+            message_info(&sender("player_a"), &coins(10_000_000, "uusdc")),
             ExecuteMsg::DoubleDeposit {
                 game_id: "game1".to_string(),
             },
@@ -1476,7 +2459,7 @@ mod tests {
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("admin"), &[]),
+            message_info(&sender("admin"), &[]),
             ExecuteMsg::OfferDouble {
                 game_id: "game1".to_string(),
                 doubler: addr("player_a"),
@@ -1487,7 +2470,7 @@ mod tests {
         execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("player_a"), &coins(5_000_000, "uusdc")),
+            message_info(&sender("player_a"), &coins(5_000_000, "uusdc")),
             ExecuteMsg::DoubleDeposit {
                 game_id: "game1".to_string(),
             },
@@ -1498,7 +2481,7 @@ mod tests {
         let res = execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(&addr("admin"), &[]),
+            message_info(&sender("admin"), &[]),
             ExecuteMsg::Cancel {
                 game_id: "game1".to_string(),
             },
@@ -1508,4 +2491,450 @@ mod tests {
         // Should refund player_a's 10M (5M initial + 5M double) and player_b's 5M
         assert_eq!(res.messages.len(), 2); // Two refund messages
     }
+
+    #[test]
+    fn test_resettle_records_corrected_winner() {
+        let mut deps = setup();
+        create_and_deposit_both(&mut deps);
+        let env = mock_env();
+        deps.querier
+            .bank
+            .update_balance(env.contract.address.to_string(), coins(10_000_000, "uusdc"));
+
+        let resettle = |winner: &str, multiplier: Option<u32>| ExecuteMsg::Resettle {
+            game_id: "game1".to_string(),
+            winner: addr(winner),
+            multiplier,
+        };
+
+        // Still Active: resettling is just settling
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&sender("admin"), &[]),
+            resettle("player_a", None),
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 2);
+        let escrow = ESCROWS.load(&deps.storage, "game1").unwrap();
+        assert_eq!(escrow.status, EscrowStatus::Settled);
+        assert_eq!(escrow.winner, Some(sender("player_a")));
+
+        // Overturned after payout: nothing moves, the winner is owed the pot
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&sender("not_admin"), &[]),
+            resettle("player_b", None),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+
+        // The winner standing with a corrected result type only records it
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&sender("admin"), &[]),
+            resettle("player_a", Some(2)),
+        )
+        .unwrap();
+        assert!(res.messages.is_empty());
+        let escrow = ESCROWS.load(&deps.storage, "game1").unwrap();
+        assert_eq!(
+            (escrow.status, escrow.multiplier),
+            (EscrowStatus::Settled, 2)
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&sender("admin"), &[]),
+            resettle("player_b", None),
+        )
+        .unwrap();
+        assert!(res.messages.is_empty());
+        assert!(res
+            .attributes
+            .iter()
+            .any(|a| a.key == "owed" && a.value == "9500000"));
+        // and no payout is reported, since none was made
+        assert!(res
+            .attributes
+            .iter()
+            .any(|a| a.key == "payout" && a.value == "0"));
+        let escrow = ESCROWS.load(&deps.storage, "game1").unwrap();
+        assert_eq!(escrow.status, EscrowStatus::Resettled);
+        assert_eq!(escrow.winner, Some(sender("player_b")));
+        let owed = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>| {
+            query_owed(deps.as_ref(), addr("player_b")).unwrap().owed
+        };
+        assert_eq!(owed(&deps), 9_500_000);
+
+        // What is owed is paid down through the escrow to the winner
+        let pay = ExecuteMsg::PayOwed {
+            game_id: "game1".to_string(),
+        };
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&sender("treasury"), &coins(9_600_000, "uusdc")),
+            pay.clone(),
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::OverpaidOwed {
+                owed: 9_500_000,
+                received: 9_600_000
+            }
+        );
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&sender("treasury"), &coins(4_000_000, "uusdc")),
+            pay.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: addr("player_b"),
+                amount: coins(4_000_000, "uusdc"),
+            })
+        );
+        assert_eq!(owed(&deps), 5_500_000);
+        execute(
+            deps.as_mut(),
+            env,
+            message_info(&sender("treasury"), &coins(5_500_000, "uusdc")),
+            pay,
+        )
+        .unwrap();
+        assert_eq!(owed(&deps), 0);
+        let escrow = ESCROWS.load(&deps.storage, "game1").unwrap();
+        assert_eq!((escrow.status, escrow.owed), (EscrowStatus::Settled, 0));
+    }
+
+    #[test]
+    fn test_pause_and_withdraw_only() {
+        let mut deps = setup();
+        let admin = message_info(&sender("admin"), &[]);
+        let guardian = message_info(&sender("guardian"), &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::GrantRole {
+                role: Role::Pauser,
+                address: addr("guardian"),
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::UpdateConfig {
+                rake_bps: None,
+                rake_recipient: None,
+                min_wager: None,
+                max_wager: None,
+                timeout_seconds: None,
+                game_hook: Some(addr("game")),
+                game_contract: None,
+            },
+        )
+        .unwrap();
+        create_and_deposit_both(&mut deps);
+        let create = ExecuteMsg::CreateEscrow {
+            game_id: "game3".to_string(),
+            player_a: addr("player_a"),
+            player_b: addr("player_b"),
+            wager_amount: 5_000_000u128,
+        };
+        execute(deps.as_mut(), mock_env(), admin.clone(), create).unwrap();
+        for player in ["player_a", "player_b"] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&sender(player), &coins(5_000_000, "uusdc")),
+                ExecuteMsg::Deposit {
+                    game_id: "game3".to_string(),
+                },
+            )
+            .unwrap();
+        }
+
+        // Settlement alone is halted; creating escrows still works
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            guardian.clone(),
+            ExecuteMsg::Pause {
+                operations: vec![Operation::Settlement],
+            },
+        )
+        .unwrap();
+        let settle = ExecuteMsg::Settle {
+            game_id: "game1".to_string(),
+            winner: addr("player_a"),
+        };
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), settle);
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::Paused {
+                operation: "Settlement".to_string()
+            }
+        );
+        let create = ExecuteMsg::CreateEscrow {
+            game_id: "game2".to_string(),
+            player_a: addr("player_a"),
+            player_b: addr("player_b"),
+            wager_amount: 5_000_000u128,
+        };
+        execute(deps.as_mut(), mock_env(), admin.clone(), create).unwrap();
+
+        // Withdrawals need withdraw-only mode, which halts everything else
+        let withdraw = ExecuteMsg::Withdraw {
+            game_id: "game1".to_string(),
+        };
+        let player_a = message_info(&sender("player_a"), &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            player_a.clone(),
+            withdraw.clone(),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::NotWithdrawOnly {});
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            guardian.clone(),
+            ExecuteMsg::SetWithdrawOnly { enabled: true },
+        )
+        .unwrap();
+        let deposit = ExecuteMsg::Deposit {
+            game_id: "game2".to_string(),
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&sender("player_a"), &coins(5_000_000, "uusdc")),
+            deposit,
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::Paused {
+                operation: "Deposit".to_string()
+            }
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            player_a.clone(),
+            withdraw.clone(),
+        )
+        .unwrap();
+        // The game is told the escrow can no longer settle
+        assert_eq!(res.messages.len(), 2);
+        assert_eq!(res.messages[0].id, GAME_HOOK_REPLY_ID);
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: addr("game"),
+                msg: to_json_binary(&GameHookMsg::EscrowWithdrawn {
+                    game_id: "game1".to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            player_a.clone(),
+            withdraw.clone(),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::NothingToWithdraw {});
+        let game3 = ExecuteMsg::Withdraw {
+            game_id: "game3".to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), player_a, game3).unwrap();
+
+        // Leaving withdraw-only mode strands nobody: player_b can still
+        // withdraw from game1, and cancelling game3 refunds them
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            guardian,
+            ExecuteMsg::SetWithdrawOnly { enabled: false },
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&sender("player_b"), &[]),
+            withdraw,
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 1);
+        let escrow = ESCROWS.load(&deps.storage, "game1").unwrap();
+        assert_eq!(escrow.status, EscrowStatus::Withdrawn);
+        assert_eq!(escrow.player_a_deposited + escrow.player_b_deposited, 0);
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            admin,
+            ExecuteMsg::Cancel {
+                game_id: "game3".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[..],
+            [SubMsg::new(BankMsg::Send {
+                to_address: addr("player_b"),
+                amount: coins(5_000_000, "uusdc"),
+            })]
+        );
+        let escrow = ESCROWS.load(&deps.storage, "game3").unwrap();
+        assert_eq!(escrow.status, EscrowStatus::Cancelled);
+
+        let status = query_pause_status(deps.as_ref()).unwrap();
+        assert_eq!(status.paused, vec![Operation::Settlement]);
+        assert!(!status.withdraw_only);
+    }
+
+    #[test]
+    fn test_activation_notifies_game_hook() {
+        let mut deps = setup();
+
+        // Without a hook, activation sends nothing
+        create_and_deposit_both(&mut deps);
+        let escrow = ESCROWS.load(&deps.storage, "game1").unwrap();
+        assert_eq!(escrow.status, EscrowStatus::Active);
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&sender("admin"), &[]),
+            ExecuteMsg::UpdateConfig {
+                rake_bps: None,
+                rake_recipient: None,
+                min_wager: None,
+                max_wager: None,
+                timeout_seconds: None,
+                game_hook: Some(addr("game")),
+                game_contract: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            query_config(deps.as_ref()).unwrap().game_hook,
+            Some(sender("game"))
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&sender("admin"), &[]),
+            ExecuteMsg::CreateEscrow {
+                game_id: "game2".to_string(),
+                player_a: addr("player_a"),
+                player_b: addr("player_b"),
+                wager_amount: 5_000_000,
+            },
+        )
+        .unwrap();
+        let deposit = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, player: &str| {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&sender(player), &coins(5_000_000, "uusdc")),
+                ExecuteMsg::Deposit {
+                    game_id: "game2".to_string(),
+                },
+            )
+            .unwrap()
+        };
+        assert!(deposit(&mut deps, "player_a").messages.is_empty());
+
+        // The second deposit activates the escrow and notifies the game
+        let res = deposit(&mut deps, "player_b");
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].id, GAME_HOOK_REPLY_ID);
+        assert_eq!(res.messages[0].reply_on, cosmwasm_std::ReplyOn::Error);
+        match &res.messages[0].msg {
+            cosmwasm_std::CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => {
+                assert_eq!(*contract_addr, addr("game"));
+                assert_eq!(
+                    *msg,
+                    to_json_binary(&GameHookMsg::EscrowActivated {
+                        game_id: "game2".to_string()
+                    })
+                    .unwrap()
+                );
+            }
+            other => panic!("unexpected message {other:?}"),
+        }
+
+        // A failing hook does not undo the deposit
+        let res = reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: GAME_HOOK_REPLY_ID,
+                payload: Binary::from(b"game2".as_slice()),
+                gas_used: 0,
+                result: cosmwasm_std::SubMsgResult::Err("game not found".to_string()),
+            },
+        )
+        .unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|a| a.key == "game_id" && a.value == "game2"));
+
+        // A rejected double tells the game what the forfeit paid
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&sender("admin"), &[]),
+            ExecuteMsg::RejectDouble {
+                game_id: "game2".to_string(),
+                rejecter: addr("player_b"),
+            },
+        )
+        .unwrap();
+        let hook = res
+            .messages
+            .iter()
+            .find(|sub| sub.id == GAME_HOOK_REPLY_ID)
+            .unwrap();
+        assert_eq!(hook.reply_on, cosmwasm_std::ReplyOn::Error);
+        match &hook.msg {
+            cosmwasm_std::CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => {
+                assert_eq!(*contract_addr, addr("game"));
+                assert_eq!(
+                    *msg,
+                    to_json_binary(&GameHookMsg::EscrowForfeited {
+                        game_id: "game2".to_string(),
+                        winner: addr("player_a"),
+                        loser: addr("player_b"),
+                        payout: 9_500_000,
+                        rake: 500_000,
+                        winner_stake: 5_000_000,
+                        loser_stake: 5_000_000,
+                        cube_value: 1,
+                    })
+                    .unwrap()
+                );
+            }
+            other => panic!("unexpected message {other:?}"),
+        }
+    }
 }
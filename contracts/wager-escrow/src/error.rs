@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use platform_roles::RoleError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -9,6 +10,27 @@ pub enum ContractError {
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("{operation} is paused")]
+    Paused { operation: String },
+
+    #[error("Withdrawals are only open in withdraw-only mode")]
+    NotWithdrawOnly {},
+
+    #[error("Nothing to withdraw")]
+    NothingToWithdraw {},
+
+    #[error("No admin transfer is pending")]
+    NoPendingAdmin {},
+
+    #[error("Admin transfer expired at {expires_at}")]
+    AdminTransferExpired { expires_at: u64 },
+
+    #[error("Revoke every granted role before renouncing admin")]
+    RolesRemain {},
+
+    #[error("Invalid admin transfer expiry {seconds}s: must be between 1 and {max}")]
+    InvalidAdminTransferExpiry { seconds: u64, max: u64 },
+
     #[error("Escrow already exists for game {game_id}")]
     EscrowAlreadyExists { game_id: String },
 
@@ -52,9 +74,21 @@ pub enum ContractError {
     #[error("Player has already deposited for this double")]
     AlreadyDepositedDouble {},
 
+    #[error("Payment of {received} exceeds the {owed} owed")]
+    OverpaidOwed { owed: u128, received: u128 },
+
     #[error("Invalid cube value: {value}")]
     InvalidCubeValue { value: u32 },
 
     #[error("Cube value {value} exceeds maximum of {max}")]
     CubeValueExceedsMax { value: u32, max: u32 },
 }
+
+impl From<RoleError> for ContractError {
+    fn from(err: RoleError) -> Self {
+        match err {
+            RoleError::Std(err) => ContractError::Std(err),
+            RoleError::Unauthorized {} => ContractError::Unauthorized {},
+        }
+    }
+}
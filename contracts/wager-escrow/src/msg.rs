@@ -1,6 +1,8 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Addr;
 
+use crate::state::{EscrowStats, Operation, PendingAdmin, Role};
+
 #[cw_serde]
 pub struct InstantiateMsg {
     pub usdc_denom: String,
@@ -9,6 +11,7 @@ pub struct InstantiateMsg {
     pub min_wager: u128,
     pub max_wager: u128,
     pub timeout_seconds: u64,
+    /// Initial Reporter; more can be granted with `GrantRole`
     pub game_contract: Option<String>,
 }
 
@@ -25,11 +28,8 @@ pub enum ExecuteMsg {
     /// Deposit wager into escrow. Send funds with this message.
     Deposit { game_id: String },
 
-    /// Settle the game and pay the winner. Called by game contract or admin.
-    Settle {
-        game_id: String,
-        winner: String,
-    },
+    /// Settle the game and pay the winner. Called by a Reporter.
+    Settle { game_id: String, winner: String },
 
     /// Settle with a multiplier (for gammon=2x, backgammon=3x).
     /// The multiplier affects the rake calculation but not the payout
@@ -40,14 +40,32 @@ pub enum ExecuteMsg {
         multiplier: u32,
     },
 
-    /// Cancel game and refund both players. Called by game contract or admin.
+    /// Re-settle after a disputed result is overturned. Called by a
+    /// Reporter. An escrow that has not paid out yet is settled to
+    /// `winner`. Funds already paid cannot be recovered, so a settled escrow
+    /// is marked Resettled with the corrected winner and the amount owed,
+    /// which is booked to the `Owed` ledger. `multiplier` corrects the
+    /// result multiplier, including when the winner stands.
+    Resettle {
+        game_id: String,
+        winner: String,
+        multiplier: Option<u32>,
+    },
+
+    /// Anyone: pay towards what a Resettled escrow's winner is owed. Send
+    /// funds with this message, at most the amount owed; they are passed
+    /// on to the winner. The escrow is Settled once nothing is owed.
+    PayOwed { game_id: String },
+
+    /// Cancel game and refund both players, or whatever is left in a
+    /// Withdrawn escrow. Called by a Reporter or an Operator.
     Cancel { game_id: String },
 
     /// Claim timeout if opponent hasn't deposited within timeout period.
     ClaimTimeout { game_id: String },
 
     /// Offer a double — transitions escrow to AwaitingDoubleDeposits.
-    /// Called by game contract or admin after both players accept the double.
+    /// Called by a Reporter after both players accept the double.
     OfferDouble {
         game_id: String,
         doubler: String,
@@ -58,20 +76,65 @@ pub enum ExecuteMsg {
     DoubleDeposit { game_id: String },
 
     /// Reject a double — forfeit game, pay current pot to doubler.
-    /// Called by game contract or admin.
-    RejectDouble {
-        game_id: String,
-        rejecter: String,
+    /// Called by a Reporter. The game hook is sent what was paid.
+    RejectDouble { game_id: String, rejecter: String },
+
+    /// Pauser: halt the given operations until they are unpaused
+    Pause { operations: Vec<Operation> },
+
+    /// Pauser: resume the given operations
+    Unpause { operations: Vec<Operation> },
+
+    /// Pauser: enter or leave withdraw-only mode. While it is on every
+    /// operation is halted and players can `Withdraw` their deposits.
+    SetWithdrawOnly { enabled: bool },
+
+    /// Player, in withdraw-only mode: reclaim your own deposits from an
+    /// unsettled escrow. The escrow is marked Withdrawn and cannot be
+    /// settled afterwards, and the game hook is told so. The other player
+    /// can withdraw from a Withdrawn escrow at any time, or be refunded by
+    /// `Cancel`.
+    Withdraw { game_id: String },
+
+    /// Admin: propose `address` as the next admin. It takes over once it
+    /// sends `AcceptAdmin` before the proposal expires (default 7 days, at
+    /// most 30). Replaces any earlier proposal.
+    ProposeNewAdmin {
+        address: String,
+        expires_in: Option<u64>,
     },
 
-    /// Admin: update configuration
+    /// Proposed admin: accept a pending admin transfer
+    AcceptAdmin {},
+
+    /// Admin: withdraw a pending admin transfer
+    CancelAdminTransfer {},
+
+    /// Admin: give up admin rights for good. The contract becomes its own
+    /// admin, so nothing can change the configuration or roles afterwards.
+    /// Every granted role must be revoked first.
+    RenounceAdmin {},
+
+    /// Admin: give `address` a role
+    GrantRole { role: Role, address: String },
+
+    /// Admin: take a role away from `address`
+    RevokeRole { role: Role, address: String },
+
+    /// ConfigAdmin: update configuration
     UpdateConfig {
-        game_contract: Option<String>,
         rake_bps: Option<u16>,
         rake_recipient: Option<String>,
         min_wager: Option<u128>,
         max_wager: Option<u128>,
         timeout_seconds: Option<u64>,
+        /// Game contract to notify when an escrow becomes Active or is
+        /// forfeited by a rejected double
+        game_hook: Option<String>,
+        /// Deprecated: replaces every Reporter with this address, as it
+        /// replaced the game contract before roles. Admin only; use
+        /// `GrantRole`/`RevokeRole`.
+        game_contract: Option<String>,
     },
 }
 
@@ -84,23 +147,80 @@ pub enum QueryMsg {
     #[returns(ConfigResponse)]
     Config {},
 
+    /// Holders of each role, besides the admin who holds them all
+    #[returns(RolesResponse)]
+    Roles {},
+
+    /// The current admin and any transfer awaiting acceptance
+    #[returns(PendingAdminResponse)]
+    PendingAdmin {},
+
+    /// Operations currently halted by a Pauser
+    #[returns(PauseStatusResponse)]
+    PauseStatus {},
+
     #[returns(EscrowResponse)]
     Escrow { game_id: String },
 
+    /// Like `Escrow`, but null instead of an error when there is none
+    #[returns(Option<EscrowResponse>)]
+    FindEscrow { game_id: String },
+
+    /// What a player is owed across Resettled escrows
+    #[returns(OwedResponse)]
+    Owed { address: String },
+
     #[returns(StatsResponse)]
     Stats {},
+
+    /// Escrow stats per UTC day, oldest first, for days with activity
+    /// between `start_time` and `end_time` (inclusive, in seconds). Page by
+    /// passing the day after the last one returned as `start_time`.
+    #[returns(DailyStatsResponse)]
+    DailyStats {
+        start_time: Option<u64>,
+        end_time: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
 pub struct ConfigResponse {
     pub admin: Addr,
-    pub game_contract: Option<Addr>,
     pub usdc_denom: String,
     pub rake_bps: u16,
     pub rake_recipient: Addr,
     pub min_wager: u128,
     pub max_wager: u128,
     pub timeout_seconds: u64,
+    pub game_hook: Option<Addr>,
+    /// Deprecated: the first Reporter, kept for older clients. The `Roles`
+    /// query lists every holder.
+    pub game_contract: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct RoleMembers {
+    pub role: Role,
+    pub members: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct RolesResponse {
+    pub admin: Addr,
+    pub roles: Vec<RoleMembers>,
+}
+
+#[cw_serde]
+pub struct PauseStatusResponse {
+    pub paused: Vec<Operation>,
+    pub withdraw_only: bool,
+}
+
+#[cw_serde]
+pub struct PendingAdminResponse {
+    pub admin: Addr,
+    pub pending: Option<PendingAdmin>,
 }
 
 #[cw_serde]
@@ -120,6 +240,15 @@ pub struct EscrowResponse {
     pub cube_value: u32,
     /// Pending double info (if any)
     pub pending_double: Option<PendingDoubleResponse>,
+    pub winner: Option<Addr>,
+    /// Result multiplier it was settled at; 0 until settled
+    pub multiplier: u32,
+    /// Still owed to `winner` after a resettlement
+    pub owed: u128,
+    /// The current pot split as a settlement would: winner's payout and
+    /// rake. None once the escrow can no longer settle.
+    pub projected_payout: Option<u128>,
+    pub projected_rake: Option<u128>,
 }
 
 #[cw_serde]
@@ -132,8 +261,27 @@ pub struct PendingDoubleResponse {
     pub responder_deposited: bool,
 }
 
+#[cw_serde]
+pub struct OwedResponse {
+    pub address: Addr,
+    pub owed: u128,
+}
+
 #[cw_serde]
 pub struct StatsResponse {
     pub total_rake_collected: u128,
     pub total_games_settled: u64,
+    pub totals: EscrowStats,
+}
+
+#[cw_serde]
+pub struct DailyStats {
+    /// Start of the UTC day, in seconds
+    pub day_start: u64,
+    pub stats: EscrowStats,
+}
+
+#[cw_serde]
+pub struct DailyStatsResponse {
+    pub days: Vec<DailyStats>,
 }
@@ -1,11 +1,14 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Empty};
 use cw_storage_plus::{Item, Map};
+use platform_roles::Roles;
 
 #[cw_serde]
 pub struct Config {
     pub admin: Addr,
-    /// Only the game contract can settle matches
+    /// Game contract from before roles existed; `migrate` moves it into the
+    /// Reporter role
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub game_contract: Option<Addr>,
     /// Wager token denom (Gammon token factory denom or IBC USDC)
     pub usdc_denom: String,
@@ -19,6 +22,44 @@ pub struct Config {
     pub max_wager: u128,
     /// Seconds before timeout forfeit is allowed
     pub timeout_seconds: u64,
+    /// Game contract sent `EscrowActivated` once both players have deposited,
+    /// and `EscrowForfeited` when a rejected double pays out
+    #[serde(default)]
+    pub game_hook: Option<Addr>,
+}
+
+/// Operations a Pauser can halt independently of each other
+#[cw_serde]
+pub enum Operation {
+    /// Creating escrows
+    CreateEscrow,
+    /// Initial wager deposits
+    Deposit,
+    /// Offering, funding and rejecting doubles
+    Double,
+    /// Paying out settled and resettled games
+    Settlement,
+}
+
+/// An admin transfer the proposed admin has not yet accepted
+#[cw_serde]
+pub struct PendingAdmin {
+    pub address: Addr,
+    pub expires_at: u64,
+}
+
+/// Permissions the admin can grant to other addresses. The admin holds
+/// every role implicitly.
+#[cw_serde]
+pub enum Role {
+    /// Updates the configuration
+    ConfigAdmin,
+    /// Cancels escrows and refunds both players
+    Operator,
+    /// Game contracts: create, double, settle and cancel escrows
+    Reporter,
+    /// Pauses the contract
+    Pauser,
 }
 
 #[cw_serde]
@@ -39,6 +80,17 @@ pub struct Escrow {
     pub cube_value: u32,
     /// Pending double offer awaiting deposits from both players
     pub pending_double: Option<PendingDouble>,
+    /// Player the pot was paid to (or is owed to, after a resettlement)
+    #[serde(default)]
+    pub winner: Option<Addr>,
+    /// Result multiplier the escrow was settled at (2 for a gammon, 3 for a
+    /// backgammon); 0 until it is settled
+    #[serde(default)]
+    pub multiplier: u32,
+    /// Part of the payout `winner` is still owed after a resettlement; see
+    /// `PayOwed`
+    #[serde(default)]
+    pub owed: u128,
 }
 
 #[cw_serde]
@@ -73,9 +125,54 @@ pub enum EscrowStatus {
     TimedOut,
     /// Game forfeited (e.g., double rejected)
     Forfeited,
+    /// Result overturned after the pot was paid out; `winner` is owed it
+    /// until `PayOwed` covers it, which marks the escrow Settled
+    Resettled,
+    /// A player reclaimed their deposits while the contract was
+    /// withdraw-only; the other's, if any, can still be withdrawn or
+    /// cancelled
+    Withdrawn,
+}
+
+/// Escrow activity counters, kept as all-time totals and per UTC day
+#[cw_serde]
+#[derive(Default)]
+pub struct EscrowStats {
+    pub escrows_created: u64,
+    /// Wagers and double stakes deposited by players
+    pub volume_deposited: u128,
+    /// Pots paid out, including forfeits on a rejected double
+    pub games_settled: u64,
+    /// Those pots before rake
+    pub volume_settled: u128,
+    pub rake_collected: u128,
+    pub doubles_offered: u64,
+    /// Doubles both players funded
+    pub doubles_accepted: u64,
+    pub doubles_rejected: u64,
+    /// Distinct players who deposited: ever in the totals, or that day in a
+    /// daily bucket
+    pub unique_players: u64,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
+/// Admin transfer awaiting acceptance; see `ProposeNewAdmin`
+pub const PENDING_ADMIN: Item<PendingAdmin> = Item::new("pending_admin");
+/// Role holders keyed by (role, address); see `Role`
+pub const ROLES: Roles = Roles::new("roles");
+/// Halted operations keyed by name; see `Operation`
+pub const PAUSED: Map<String, Empty> = Map::new("paused");
+/// Every operation is halted and players may withdraw their own deposits
+pub const WITHDRAW_ONLY: Item<bool> = Item::new("withdraw_only");
 pub const ESCROWS: Map<&str, Escrow> = Map::new("escrows");
+/// What each player is owed across Resettled escrows
+pub const OWED_BALANCES: Map<&Addr, u128> = Map::new("owed_balances");
 pub const TOTAL_RAKE_COLLECTED: Item<u128> = Item::new("total_rake");
 pub const TOTAL_GAMES_SETTLED: Item<u64> = Item::new("total_settled");
+pub const ESCROW_STATS: Item<EscrowStats> = Item::new("escrow_stats");
+/// Keyed by UTC day number (block time in seconds / 86400)
+pub const DAILY_STATS: Map<u64, EscrowStats> = Map::new("daily_stats");
+/// Every player who has deposited
+pub const DEPOSITORS: Map<&Addr, Empty> = Map::new("depositors");
+/// Players who deposited on a UTC day, keyed by (day, player)
+pub const DAILY_DEPOSITORS: Map<(u64, &Addr), Empty> = Map::new("daily_depositors");